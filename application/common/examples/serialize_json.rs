@@ -5,7 +5,8 @@ fn main() {
         BankAccountCommand, BankAccountRefCommand, OpenAccountCommand, OpenAccountRefCommand,
     };
     use common::commands::CommandId;
-    use domain::aggregates::bank_account::{AccountName, EmailAddress};
+    use domain::aggregates::bank_account::{AccountName, AccountTier, AccountType, EmailAddress};
+    use domain::Currency;
 
     let account_name =
         AccountName::from_primitives("山田".to_string(), "太郎".to_string()).unwrap();
@@ -16,6 +17,9 @@ fn main() {
         OpenAccountRefCommand {
             account_name: &account_name,
             email_address: &email_address,
+            currency: Currency::JPY,
+            tier: AccountTier::Standard,
+            account_type: AccountType::Checking,
         },
         command_id,
     );
@@ -31,6 +35,9 @@ fn main() {
         OpenAccountCommand {
             account_name,
             email_address,
+            currency: Currency::JPY,
+            tier: AccountTier::Standard,
+            account_type: AccountType::Checking,
         },
         command_id,
     );
@@ -1,19 +1,56 @@
 use super::CommandId;
-use domain::aggregates::atm::AtmLocation;
+use domain::aggregates::atm::{AtmId, AtmLocation, NoteBreakdown};
+use domain::Currency;
 
 #[cfg(feature = "server")]
 use serde::Deserialize;
 
-#[cfg(feature = "frontend")]
+#[cfg(any(feature = "server", feature = "frontend"))]
 use serde::Serialize;
 
-/// Atm登録のコマンド
+/// Atm登録のコマンド．カセットに入れる紙幣を額面ごとに指定する
 #[cfg(feature = "server")]
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[cfg_attr(feature = "fake", derive(fake::Dummy))]
 pub struct RegisterAtmCommand {
     pub location: AtmLocation,
-    pub total_cash: f64,
+    pub currency: Currency,
+    pub notes: NoteBreakdown,
+}
+
+/// Atmのカセットに紙幣をチャージするコマンド．入金のサガが発行し，サガとともに保存する
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "fake", derive(fake::Dummy))]
+pub struct ChargeAtmCashCommand {
+    pub atm_id: AtmId,
+    pub notes: NoteBreakdown,
+}
+
+/// Atmを撤去するコマンド
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[cfg_attr(feature = "fake", derive(fake::Dummy))]
+pub struct DecommissionAtmCommand {
+    pub atm_id: AtmId,
+}
+
+/// Atmを移設するコマンド
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[cfg_attr(feature = "fake", derive(fake::Dummy))]
+pub struct RelocateAtmCommand {
+    pub atm_id: AtmId,
+    pub location: AtmLocation,
+}
+
+/// Atmを一時的に停止するコマンド．out_of_serviceがfalseの場合は稼働を再開する
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[cfg_attr(feature = "fake", derive(fake::Dummy))]
+pub struct SetAtmOutOfServiceCommand {
+    pub atm_id: AtmId,
+    pub out_of_service: bool,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -24,7 +61,39 @@ pub struct RegisterAtmCommand {
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct RegisterAtmRefCommand<'a> {
     pub location: &'a AtmLocation,
-    pub total_cash: f64,
+    pub currency: Currency,
+    pub notes: &'a NoteBreakdown,
+}
+
+/// Atmのカセットに紙幣をチャージするコマンド(参照)
+#[cfg(feature = "frontend")]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChargeAtmCashRefCommand<'a> {
+    pub atm_id: AtmId,
+    pub notes: &'a NoteBreakdown,
+}
+
+/// Atmを撤去するコマンド(参照)
+#[cfg(feature = "frontend")]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecommissionAtmRefCommand {
+    pub atm_id: AtmId,
+}
+
+/// Atmを移設するコマンド(参照)
+#[cfg(feature = "frontend")]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RelocateAtmRefCommand<'a> {
+    pub atm_id: AtmId,
+    pub location: &'a AtmLocation,
+}
+
+/// Atmを一時的に停止するコマンド(参照)
+#[cfg(feature = "frontend")]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SetAtmOutOfServiceRefCommand {
+    pub atm_id: AtmId,
+    pub out_of_service: bool,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -35,6 +104,24 @@ pub struct RegisterAtmRefCommand<'a> {
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub enum AtmCommand {
     RegisterAtmCommand(RegisterAtmCommand, CommandId),
+    ChargeAtmCashCommand(ChargeAtmCashCommand, CommandId),
+    DecommissionAtmCommand(DecommissionAtmCommand, CommandId),
+    RelocateAtmCommand(RelocateAtmCommand, CommandId),
+    SetAtmOutOfServiceCommand(SetAtmOutOfServiceCommand, CommandId),
+}
+
+#[cfg(feature = "server")]
+impl AtmCommand {
+    /// コマンドのid
+    pub fn command_id(&self) -> CommandId {
+        match self {
+            AtmCommand::RegisterAtmCommand(_, id)
+            | AtmCommand::ChargeAtmCashCommand(_, id)
+            | AtmCommand::DecommissionAtmCommand(_, id)
+            | AtmCommand::RelocateAtmCommand(_, id)
+            | AtmCommand::SetAtmOutOfServiceCommand(_, id) => *id,
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -44,6 +131,10 @@ pub enum AtmCommand {
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum AtmRefCommand<'a> {
     RegisterAtmCommand(RegisterAtmRefCommand<'a>, CommandId),
+    ChargeAtmCashCommand(ChargeAtmCashRefCommand<'a>, CommandId),
+    DecommissionAtmCommand(DecommissionAtmRefCommand, CommandId),
+    RelocateAtmCommand(RelocateAtmRefCommand<'a>, CommandId),
+    SetAtmOutOfServiceCommand(SetAtmOutOfServiceRefCommand, CommandId),
 }
 
 #[cfg(all(test, all(feature = "server", feature = "frontend")))]
@@ -54,16 +145,19 @@ mod serde_test {
     fn atm_command() {
         use super::{AtmCommand, AtmRefCommand, RegisterAtmCommand, RegisterAtmRefCommand};
 
-        use domain::aggregates::atm::AtmLocation;
+        use domain::aggregates::atm::{AtmLocation, NoteBreakdown};
+        use domain::Currency;
 
         let location = Faker.fake::<AtmLocation>();
-        let total_cash = (1000_000.0..10_000_000.0).fake();
+        let currency = Faker.fake::<Currency>();
+        let notes = Faker.fake::<NoteBreakdown>();
         let command_id = Faker.fake();
 
         let atm_ref_command = AtmRefCommand::RegisterAtmCommand(
             RegisterAtmRefCommand {
                 location: &location,
-                total_cash,
+                currency,
+                notes: &notes,
             },
             command_id,
         );
@@ -75,11 +169,40 @@ mod serde_test {
         let atm_command = AtmCommand::RegisterAtmCommand(
             RegisterAtmCommand {
                 location,
-                total_cash,
+                currency,
+                notes,
+            },
+            command_id,
+        );
+
+        assert_eq!(atm_command_from_json, atm_command);
+    }
+
+    #[test]
+    fn relocate_atm_command() {
+        use super::{AtmCommand, AtmRefCommand, RelocateAtmCommand, RelocateAtmRefCommand};
+
+        use domain::aggregates::atm::{AtmId, AtmLocation};
+
+        let atm_id = Faker.fake::<AtmId>();
+        let location = Faker.fake::<AtmLocation>();
+        let command_id = Faker.fake();
+
+        let atm_ref_command = AtmRefCommand::RelocateAtmCommand(
+            RelocateAtmRefCommand {
+                atm_id,
+                location: &location,
             },
             command_id,
         );
 
+        let atm_command_from_json: AtmCommand =
+            serde_json::from_str(serde_json::to_string(&atm_ref_command).unwrap().as_str())
+                .unwrap();
+
+        let atm_command =
+            AtmCommand::RelocateAtmCommand(RelocateAtmCommand { atm_id, location }, command_id);
+
         assert_eq!(atm_command_from_json, atm_command);
     }
 }
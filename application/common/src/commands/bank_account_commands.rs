@@ -1,6 +1,10 @@
 use super::CommandId;
 use domain::aggregates::atm::AtmId;
-use domain::aggregates::bank_account::{AccountName, BankAccountId, EmailAddress};
+use domain::aggregates::bank_account::{
+    AccountName, AccountTier, AccountType, BankAccountId, CheckNumber, EmailAddress,
+    EmailChangeToken, OwnerRole,
+};
+use domain::{Currency, Money};
 
 use serde::{Deserialize, Serialize};
 
@@ -11,14 +15,21 @@ use serde::{Deserialize, Serialize};
 pub struct OpenAccountCommand {
     pub account_name: AccountName,
     pub email_address: EmailAddress,
+    pub currency: Currency,
+    /// 引き出し限度額を決めるアカウントの区分．省略した場合はStandard
+    #[serde(default)]
+    pub tier: AccountTier,
+    /// 口座の種類．省略した場合はChecking
+    #[serde(default)]
+    pub account_type: AccountType,
 }
 
-/// 預金するコマンド
+/// 預金するコマンド．口座と異なる通貨の場合は口座の通貨に換算して預金する
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[cfg_attr(feature = "fake", derive(fake::Dummy))]
 pub struct DepositMoneyCommand {
     pub account_id: BankAccountId,
-    pub amount: f64,
+    pub amount: Money,
     pub atm_id: AtmId,
 }
 
@@ -27,7 +38,7 @@ pub struct DepositMoneyCommand {
 #[cfg_attr(feature = "fake", derive(fake::Dummy))]
 pub struct WithdrawMoneyCommand {
     pub account_id: BankAccountId,
-    pub amount: f64,
+    pub amount: Money,
     pub atm_id: AtmId,
 }
 
@@ -37,9 +48,110 @@ pub struct WithdrawMoneyCommand {
 #[cfg_attr(feature = "fake", derive(fake::Dummy))]
 pub struct WriteCheckCommand {
     pub account_id: BankAccountId,
-    pub amount: f64,
-    /// 外部マイクロサービスについての処理であるため，プリミティブな型
-    pub check_number: String,
+    pub amount: Money,
+    pub check_number: CheckNumber,
+}
+
+/// 他のアカウントへ送金するコマンド．金額は送金元の通貨で指定する
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "fake", derive(fake::Dummy))]
+pub struct TransferMoneyCommand {
+    pub from_account_id: BankAccountId,
+    pub to_account_id: BankAccountId,
+    pub amount: Money,
+}
+
+/// アカウントを凍結するコマンド
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "fake", derive(fake::Dummy))]
+pub struct FreezeAccountCommand {
+    pub account_id: BankAccountId,
+    /// 凍結の理由
+    pub reason: String,
+}
+
+/// アカウントの凍結を解除するコマンド
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "fake", derive(fake::Dummy))]
+pub struct UnfreezeAccountCommand {
+    pub account_id: BankAccountId,
+}
+
+/// アカウントを解約するコマンド
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "fake", derive(fake::Dummy))]
+pub struct CloseAccountCommand {
+    pub account_id: BankAccountId,
+}
+
+/// 当座貸越の限度額を設定するコマンド．0を設定すると当座貸越を利用できなくなる
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "fake", derive(fake::Dummy))]
+pub struct SetOverdraftLimitCommand {
+    pub account_id: BankAccountId,
+    pub limit: Money,
+}
+
+/// 共同名義人を追加するコマンド．Primaryを指定すると既存の主名義人はSecondaryになる
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "fake", derive(fake::Dummy))]
+pub struct AddOwnerCommand {
+    pub account_id: BankAccountId,
+    pub account_name: AccountName,
+    pub email_address: EmailAddress,
+    pub role: OwnerRole,
+}
+
+/// 共同名義人を削除するコマンド
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "fake", derive(fake::Dummy))]
+pub struct RemoveOwnerCommand {
+    pub account_id: BankAccountId,
+    pub email_address: EmailAddress,
+}
+
+/// メールアドレスの変更を要求するコマンド．新しいメールアドレスに確認のトークンが送信される
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "fake", derive(fake::Dummy))]
+pub struct RequestEmailChangeCommand {
+    pub account_id: BankAccountId,
+    /// 変更後のメールアドレス
+    pub email_address: EmailAddress,
+}
+
+/// 送信されたトークンでメールアドレスの変更を確定するコマンド
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "fake", derive(fake::Dummy))]
+pub struct ConfirmEmailChangeCommand {
+    pub account_id: BankAccountId,
+    pub token: EmailChangeToken,
+}
+
+/// 小切手の決済を記録するコマンド．小切手決済サービスの結果を受けてサーバー内部で発行する
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "fake", derive(fake::Dummy))]
+pub struct ClearCheckCommand {
+    pub account_id: BankAccountId,
+    pub check_number: CheckNumber,
+    pub amount: Money,
+}
+
+/// 不渡りとなった小切手の金額を返金する補償コマンド．小切手決済サービスの結果を受けてサーバー内部で発行する
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "fake", derive(fake::Dummy))]
+pub struct RefundBouncedCheckCommand {
+    pub account_id: BankAccountId,
+    pub check_number: CheckNumber,
+    pub amount: Money,
+    /// 不渡りの理由
+    pub reason: String,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -51,6 +163,9 @@ pub struct WriteCheckCommand {
 pub struct OpenAccountRefCommand<'a> {
     pub account_name: &'a AccountName,
     pub email_address: &'a EmailAddress,
+    pub currency: Currency,
+    pub tier: AccountTier,
+    pub account_type: AccountType,
 }
 
 /// 小切手の発行を行うコマンド(参照)
@@ -58,9 +173,52 @@ pub struct OpenAccountRefCommand<'a> {
 #[derive(Debug, PartialEq, Clone, Serialize)]
 pub struct WriteCheckRefCommand<'a> {
     pub account_id: BankAccountId,
-    pub amount: f64,
-    /// 外部マイクロサービスについての処理であるため，プリミティブな型
-    pub check_number: &'a String,
+    pub amount: Money,
+    pub check_number: &'a CheckNumber,
+}
+
+/// アカウントを凍結するコマンド(参照)
+#[cfg(feature = "frontend")]
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct FreezeAccountRefCommand<'a> {
+    pub account_id: BankAccountId,
+    /// 凍結の理由
+    pub reason: &'a String,
+}
+
+/// 共同名義人を追加するコマンド(参照)
+#[cfg(feature = "frontend")]
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct AddOwnerRefCommand<'a> {
+    pub account_id: BankAccountId,
+    pub account_name: &'a AccountName,
+    pub email_address: &'a EmailAddress,
+    pub role: OwnerRole,
+}
+
+/// 共同名義人を削除するコマンド(参照)
+#[cfg(feature = "frontend")]
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct RemoveOwnerRefCommand<'a> {
+    pub account_id: BankAccountId,
+    pub email_address: &'a EmailAddress,
+}
+
+/// メールアドレスの変更を要求するコマンド(参照)
+#[cfg(feature = "frontend")]
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct RequestEmailChangeRefCommand<'a> {
+    pub account_id: BankAccountId,
+    /// 変更後のメールアドレス
+    pub email_address: &'a EmailAddress,
+}
+
+/// 送信されたトークンでメールアドレスの変更を確定するコマンド(参照)
+#[cfg(feature = "frontend")]
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct ConfirmEmailChangeRefCommand<'a> {
+    pub account_id: BankAccountId,
+    pub token: &'a EmailChangeToken,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -74,6 +232,73 @@ pub enum BankAccountCommand {
     DepositMoneyCommand(DepositMoneyCommand, CommandId),
     WithdrawMoneyCommand(WithdrawMoneyCommand, CommandId),
     WriteCheckCommand(WriteCheckCommand, CommandId),
+    TransferMoneyCommand(TransferMoneyCommand, CommandId),
+    FreezeAccountCommand(FreezeAccountCommand, CommandId),
+    UnfreezeAccountCommand(UnfreezeAccountCommand, CommandId),
+    CloseAccountCommand(CloseAccountCommand, CommandId),
+    SetOverdraftLimitCommand(SetOverdraftLimitCommand, CommandId),
+    AddOwnerCommand(AddOwnerCommand, CommandId),
+    RemoveOwnerCommand(RemoveOwnerCommand, CommandId),
+    RequestEmailChangeCommand(RequestEmailChangeCommand, CommandId),
+    ConfirmEmailChangeCommand(ConfirmEmailChangeCommand, CommandId),
+}
+
+#[cfg(feature = "server")]
+impl BankAccountCommand {
+    /// コマンドのid
+    pub fn command_id(&self) -> CommandId {
+        match self {
+            BankAccountCommand::OpenAccountCommand(_, id)
+            | BankAccountCommand::DepositMoneyCommand(_, id)
+            | BankAccountCommand::WithdrawMoneyCommand(_, id)
+            | BankAccountCommand::WriteCheckCommand(_, id)
+            | BankAccountCommand::TransferMoneyCommand(_, id)
+            | BankAccountCommand::FreezeAccountCommand(_, id)
+            | BankAccountCommand::UnfreezeAccountCommand(_, id)
+            | BankAccountCommand::CloseAccountCommand(_, id)
+            | BankAccountCommand::SetOverdraftLimitCommand(_, id)
+            | BankAccountCommand::AddOwnerCommand(_, id)
+            | BankAccountCommand::RemoveOwnerCommand(_, id)
+            | BankAccountCommand::RequestEmailChangeCommand(_, id)
+            | BankAccountCommand::ConfirmEmailChangeCommand(_, id) => *id,
+        }
+    }
+    /// コマンドの名前
+    pub fn command_name(&self) -> &'static str {
+        match self {
+            BankAccountCommand::OpenAccountCommand(..) => "OpenAccountCommand",
+            BankAccountCommand::DepositMoneyCommand(..) => "DepositMoneyCommand",
+            BankAccountCommand::WithdrawMoneyCommand(..) => "WithdrawMoneyCommand",
+            BankAccountCommand::WriteCheckCommand(..) => "WriteCheckCommand",
+            BankAccountCommand::TransferMoneyCommand(..) => "TransferMoneyCommand",
+            BankAccountCommand::FreezeAccountCommand(..) => "FreezeAccountCommand",
+            BankAccountCommand::UnfreezeAccountCommand(..) => "UnfreezeAccountCommand",
+            BankAccountCommand::CloseAccountCommand(..) => "CloseAccountCommand",
+            BankAccountCommand::SetOverdraftLimitCommand(..) => "SetOverdraftLimitCommand",
+            BankAccountCommand::AddOwnerCommand(..) => "AddOwnerCommand",
+            BankAccountCommand::RemoveOwnerCommand(..) => "RemoveOwnerCommand",
+            BankAccountCommand::RequestEmailChangeCommand(..) => "RequestEmailChangeCommand",
+            BankAccountCommand::ConfirmEmailChangeCommand(..) => "ConfirmEmailChangeCommand",
+        }
+    }
+    /// コマンドの対象となるアカウントのid．アカウントの開設の場合はNone，送金の場合は送金元
+    pub fn account_id(&self) -> Option<BankAccountId> {
+        match self {
+            BankAccountCommand::OpenAccountCommand(..) => None,
+            BankAccountCommand::DepositMoneyCommand(cmd, _) => Some(cmd.account_id),
+            BankAccountCommand::WithdrawMoneyCommand(cmd, _) => Some(cmd.account_id),
+            BankAccountCommand::WriteCheckCommand(cmd, _) => Some(cmd.account_id),
+            BankAccountCommand::TransferMoneyCommand(cmd, _) => Some(cmd.from_account_id),
+            BankAccountCommand::FreezeAccountCommand(cmd, _) => Some(cmd.account_id),
+            BankAccountCommand::UnfreezeAccountCommand(cmd, _) => Some(cmd.account_id),
+            BankAccountCommand::CloseAccountCommand(cmd, _) => Some(cmd.account_id),
+            BankAccountCommand::SetOverdraftLimitCommand(cmd, _) => Some(cmd.account_id),
+            BankAccountCommand::AddOwnerCommand(cmd, _) => Some(cmd.account_id),
+            BankAccountCommand::RemoveOwnerCommand(cmd, _) => Some(cmd.account_id),
+            BankAccountCommand::RequestEmailChangeCommand(cmd, _) => Some(cmd.account_id),
+            BankAccountCommand::ConfirmEmailChangeCommand(cmd, _) => Some(cmd.account_id),
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -87,6 +312,15 @@ pub enum BankAccountRefCommand<'a> {
     DepositMoneyCommand(DepositMoneyCommand, CommandId),
     WithdrawMoneyCommand(WithdrawMoneyCommand, CommandId),
     WriteCheckCommand(WriteCheckRefCommand<'a>, CommandId),
+    TransferMoneyCommand(TransferMoneyCommand, CommandId),
+    FreezeAccountCommand(FreezeAccountRefCommand<'a>, CommandId),
+    UnfreezeAccountCommand(UnfreezeAccountCommand, CommandId),
+    CloseAccountCommand(CloseAccountCommand, CommandId),
+    SetOverdraftLimitCommand(SetOverdraftLimitCommand, CommandId),
+    AddOwnerCommand(AddOwnerRefCommand<'a>, CommandId),
+    RemoveOwnerCommand(RemoveOwnerRefCommand<'a>, CommandId),
+    RequestEmailChangeCommand(RequestEmailChangeRefCommand<'a>, CommandId),
+    ConfirmEmailChangeCommand(ConfirmEmailChangeRefCommand<'a>, CommandId),
 }
 
 #[cfg(all(test, all(feature = "server", feature = "frontend")))]
@@ -99,16 +333,25 @@ mod serde_test {
             BankAccountCommand, BankAccountRefCommand, OpenAccountCommand, OpenAccountRefCommand,
         };
         use crate::commands::CommandId;
-        use domain::aggregates::bank_account::{AccountName, EmailAddress};
+        use domain::aggregates::bank_account::{
+            AccountName, AccountTier, AccountType, EmailAddress,
+        };
+        use domain::Currency;
 
         let account_name: AccountName = Faker.fake();
         let email_address: EmailAddress = Faker.fake();
+        let currency: Currency = Faker.fake();
+        let tier: AccountTier = Faker.fake();
+        let account_type: AccountType = Faker.fake();
         let command_id = CommandId::generate();
 
         let open_account_ref_command = BankAccountRefCommand::OpenAccountCommand(
             OpenAccountRefCommand {
                 account_name: &account_name,
                 email_address: &email_address,
+                currency,
+                tier,
+                account_type,
             },
             command_id,
         );
@@ -124,6 +367,9 @@ mod serde_test {
             OpenAccountCommand {
                 account_name,
                 email_address,
+                currency,
+                tier,
+                account_type,
             },
             command_id,
         );
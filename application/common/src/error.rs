@@ -1,3 +1,7 @@
+mod problem_details;
+
+pub use problem_details::ProblemDetails;
+
 use domain::DomainError;
 
 use serde::{Deserialize, Serialize};
@@ -13,6 +17,10 @@ pub enum ApplicationError {
     #[error("ApplicationError::RecordNotFoundError: {0}")]
     RecordNotFound(String),
 
+    /// 並行処理・重複による競合のエラー
+    #[error("ApplicationError::ConflictError: {0}")]
+    ConflictError(String),
+
     /// その他のインフラに関するエラー
     #[error("ApplicationError::OtherInfraError: {0}")]
     OtherInfraError(String),
@@ -34,10 +42,56 @@ pub enum ApplicationError {
     QueryResultError(String),
 }
 
+impl ApplicationError {
+    /// 機械可読な安定したエラーコード
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApplicationError::DomainError(e) => e.code(),
+            ApplicationError::RecordNotFound(_) => "RECORD_NOT_FOUND",
+            ApplicationError::ConflictError(_) => "CONFLICT",
+            ApplicationError::OtherInfraError(_) => "OTHER_INFRA_ERROR",
+            ApplicationError::JsonRejectionError(_) => "JSON_REJECTION",
+            ApplicationError::SerdeError(_) => "SERDE_ERROR",
+            ApplicationError::FetchError(_) => "FETCH_ERROR",
+            ApplicationError::QueryResultError(_) => "QUERY_RESULT_ERROR",
+        }
+    }
+    /// エラーの種類を表す短い説明
+    pub fn title(&self) -> &'static str {
+        match self {
+            ApplicationError::DomainError(e) => e.title(),
+            ApplicationError::RecordNotFound(_) => "Record not found",
+            ApplicationError::ConflictError(_) => "Conflict",
+            ApplicationError::OtherInfraError(_) => "Infrastructure error",
+            ApplicationError::JsonRejectionError(_) => "Invalid request body",
+            ApplicationError::SerdeError(_) => "Serialization error",
+            ApplicationError::FetchError(_) => "Fetch error",
+            ApplicationError::QueryResultError(_) => "Invalid query result",
+        }
+    }
+    /// エラーに対応するHTTPステータスコード
+    pub fn status(&self) -> u16 {
+        match self {
+            // バリデーション
+            ApplicationError::DomainError(DomainError::DomainParseError(_))
+            | ApplicationError::JsonRejectionError(_) => 400,
+            ApplicationError::RecordNotFound(_) => 404,
+            ApplicationError::ConflictError(_) => 409,
+            // ビジネスルール違反
+            ApplicationError::DomainError(_) => 422,
+            _ => 500,
+        }
+    }
+}
+
 #[cfg(feature = "server")]
 mod server {
-    use super::ApplicationError;
-    use axum::{http::StatusCode, response::IntoResponse, Json};
+    use super::{ApplicationError, ProblemDetails};
+    use axum::{
+        http::{header, StatusCode},
+        response::IntoResponse,
+        Json,
+    };
     use infrastructure::InfraError;
 
     // -------------------------------------------------------------------------------------------------
@@ -47,7 +101,9 @@ mod server {
         fn from(value: InfraError) -> Self {
             match value {
                 e @ InfraError::RecordNotFoundError(_) => Self::RecordNotFound(e.to_string()),
-                e @ _ => Self::OtherInfraError(e.to_string()),
+                e @ InfraError::ConflictError(_) => Self::ConflictError(e.to_string()),
+                InfraError::DomainError(e) => Self::DomainError(e),
+                e => Self::OtherInfraError(e.to_string()),
             }
         }
     }
@@ -59,16 +115,19 @@ mod server {
     }
 
     // -------------------------------------------------------------------------------------------------
-    // IntoResponse(StatusCode, ApplicationError)
+    // IntoResponse(StatusCode, ProblemDetails)
 
     impl IntoResponse for ApplicationError {
         fn into_response(self) -> axum::response::Response {
-            match self {
-                Self::JsonRejectionError(_) => {
-                    (StatusCode::BAD_REQUEST, Json(self)).into_response()
-                }
-                _ => (StatusCode::INTERNAL_SERVER_ERROR, Json(self)).into_response(),
-            }
+            let status_code =
+                StatusCode::from_u16(self.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+            (
+                status_code,
+                [(header::CONTENT_TYPE, ProblemDetails::CONTENT_TYPE)],
+                Json(ProblemDetails::from(&self)),
+            )
+                .into_response()
         }
     }
 }
@@ -92,3 +151,52 @@ mod frontend {
         }
     }
 }
+
+// -------------------------------------------------------------------------------------------------
+// test
+
+#[cfg(test)]
+mod test {
+    use super::ApplicationError;
+    use domain::{Balance, BankAccountError, DomainError, Money};
+
+    #[test]
+    fn status_and_code() {
+        let cases: [(ApplicationError, u16, &str); 5] = [
+            (
+                DomainError::DomainParseError("Invalid.".to_string()).into(),
+                400,
+                "DOMAIN_PARSE_ERROR",
+            ),
+            (
+                ApplicationError::RecordNotFound("Not found.".to_string()),
+                404,
+                "RECORD_NOT_FOUND",
+            ),
+            (
+                ApplicationError::ConflictError("Conflict.".to_string()),
+                409,
+                "CONFLICT",
+            ),
+            (
+                DomainError::from(BankAccountError::WithdrawExceedBalanceError {
+                    amount: "1000 JPY".parse::<Money>().unwrap(),
+                    balance: "500 JPY".parse::<Balance>().unwrap(),
+                })
+                .into(),
+                422,
+                "WITHDRAW_EXCEED_BALANCE",
+            ),
+            (
+                ApplicationError::OtherInfraError("Connection refused.".to_string()),
+                500,
+                "OTHER_INFRA_ERROR",
+            ),
+        ];
+
+        for (error, status, code) in cases {
+            assert_eq!(error.status(), status, "{error}");
+            assert_eq!(error.code(), code, "{error}");
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use super::ApplicationError;
+use domain::{AtmError, BankAccountError, DomainError, MoneyError};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// problem detailsのtypeの基底となるURI参照
+const PROBLEM_TYPE_BASE: &str = "/problems/";
+
+// -------------------------------------------------------------------------------------------------
+// ProblemDetails
+
+/// RFC 7807のproblem details形式で表したApplicationError
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProblemDetails {
+    /// 問題の種類を表すURI参照
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// 問題の種類を表す短い説明
+    pub title: String,
+    /// HTTPステータスコード
+    pub status: u16,
+    /// 問題の詳細な説明
+    pub detail: String,
+    /// 機械可読な安定したエラーコード(拡張メンバー)
+    pub code: String,
+    /// エラーのフィールドを表す拡張メンバー
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+impl ProblemDetails {
+    /// problem detailsのメディアタイプ
+    pub const CONTENT_TYPE: &'static str = "application/problem+json";
+}
+
+/// エラーのDisplayをdetailとして利用する
+fn error_detail<E: std::error::Error>(error: &E) -> String {
+    error.to_string().trim().to_string()
+}
+
+/// 外部タグ付きでシリアライズしたバリアントのフィールドを取得する．
+/// ユニットバリアントはバリアント名の文字列にシリアライズされるため，フィールドは空となる
+fn variant_fields<E: Serialize>(error: &E) -> Map<String, Value> {
+    match serde_json::to_value(error) {
+        Ok(Value::Object(map)) => match map.into_iter().next() {
+            Some((_, Value::Object(fields))) => fields,
+            _ => Map::new(),
+        },
+        _ => Map::new(),
+    }
+}
+
+/// バリアント名とフィールドからエラーをデシリアライズする．フィールドが空の場合はユニットバリアントとする
+fn from_variant_fields<E: DeserializeOwned>(
+    variant_name: &str,
+    fields: Map<String, Value>,
+) -> Result<E, ApplicationError> {
+    let value = if fields.is_empty() {
+        Value::String(variant_name.to_string())
+    } else {
+        let mut map = Map::new();
+        map.insert(variant_name.to_string(), Value::Object(fields));
+        Value::Object(map)
+    };
+
+    serde_json::from_value(value).map_err(|e| ApplicationError::SerdeError(e.to_string()))
+}
+
+impl From<&ApplicationError> for ProblemDetails {
+    fn from(error: &ApplicationError) -> Self {
+        // 文字列を持つバリアントはその文字列をdetailとし，フィールドを持つバリアントはフィールドを拡張メンバーとする
+        let (detail, extensions) = match error {
+            ApplicationError::DomainError(DomainError::BankAccountError(e)) => {
+                (error_detail(e), variant_fields(e))
+            }
+            ApplicationError::DomainError(DomainError::AtmError(e)) => {
+                (error_detail(e), variant_fields(e))
+            }
+            ApplicationError::DomainError(DomainError::MoneyError(e)) => {
+                (error_detail(e), variant_fields(e))
+            }
+            ApplicationError::DomainError(DomainError::DomainParseError(s))
+            | ApplicationError::RecordNotFound(s)
+            | ApplicationError::ConflictError(s)
+            | ApplicationError::OtherInfraError(s)
+            | ApplicationError::JsonRejectionError(s)
+            | ApplicationError::SerdeError(s)
+            | ApplicationError::FetchError(s)
+            | ApplicationError::QueryResultError(s) => (s.clone(), Map::new()),
+        };
+
+        let code = error.code();
+
+        Self {
+            problem_type: format!(
+                "{PROBLEM_TYPE_BASE}{}",
+                code.to_lowercase().replace('_', "-")
+            ),
+            title: error.title().to_string(),
+            status: error.status(),
+            detail,
+            code: code.to_string(),
+            extensions,
+        }
+    }
+}
+
+impl From<ProblemDetails> for ApplicationError {
+    fn from(problem: ProblemDetails) -> Self {
+        let ProblemDetails {
+            detail,
+            code,
+            extensions,
+            ..
+        } = problem;
+
+        if let Some(variant_name) = BankAccountError::variant_name_from_code(&code) {
+            return from_variant_fields::<BankAccountError>(variant_name, extensions)
+                .map_or_else(|e| e, |e| DomainError::from(e).into());
+        }
+
+        if let Some(variant_name) = AtmError::variant_name_from_code(&code) {
+            return from_variant_fields::<AtmError>(variant_name, extensions)
+                .map_or_else(|e| e, |e| DomainError::from(e).into());
+        }
+
+        if let Some(variant_name) = MoneyError::variant_name_from_code(&code) {
+            return from_variant_fields::<MoneyError>(variant_name, extensions)
+                .map_or_else(|e| e, |e| DomainError::from(e).into());
+        }
+
+        match code.as_str() {
+            "DOMAIN_PARSE_ERROR" => DomainError::DomainParseError(detail).into(),
+            "RECORD_NOT_FOUND" => ApplicationError::RecordNotFound(detail),
+            "CONFLICT" => ApplicationError::ConflictError(detail),
+            "OTHER_INFRA_ERROR" => ApplicationError::OtherInfraError(detail),
+            "JSON_REJECTION" => ApplicationError::JsonRejectionError(detail),
+            "SERDE_ERROR" => ApplicationError::SerdeError(detail),
+            "FETCH_ERROR" => ApplicationError::FetchError(detail),
+            "QUERY_RESULT_ERROR" => ApplicationError::QueryResultError(detail),
+            _ => ApplicationError::SerdeError(format!("Unknown problem code: {code}")),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// test
+
+#[cfg(test)]
+mod test {
+    use super::{ApplicationError, ProblemDetails};
+    use domain::{Balance, BankAccountError, DomainError, Money, MoneyError};
+
+    fn round_trip(error: &ApplicationError) -> (serde_json::Value, ApplicationError) {
+        let json = serde_json::to_value(ProblemDetails::from(error)).unwrap();
+        let problem: ProblemDetails = serde_json::from_value(json.clone()).unwrap();
+
+        (json, problem.into())
+    }
+
+    #[test]
+    fn problem_details_with_extensions() {
+        let amount: Money = "1000 JPY".parse().unwrap();
+        let balance: Balance = "500 JPY".parse().unwrap();
+        let error: ApplicationError =
+            DomainError::from(BankAccountError::WithdrawExceedBalanceError { amount, balance })
+                .into();
+
+        let (json, error_from_problem) = round_trip(&error);
+
+        assert_eq!(json["type"], "/problems/withdraw-exceed-balance");
+        assert_eq!(json["status"], 422);
+        assert_eq!(json["code"], "WITHDRAW_EXCEED_BALANCE");
+        assert_eq!(json["amount"], "1000 JPY");
+        assert_eq!(json["balance"], "500 JPY");
+
+        assert!(matches!(
+            error_from_problem,
+            ApplicationError::DomainError(DomainError::BankAccountError(
+                BankAccountError::WithdrawExceedBalanceError { amount: a, balance: b }
+            )) if a == amount && b == balance
+        ));
+    }
+
+    #[test]
+    fn problem_details_of_unit_variants() {
+        let errors: Vec<DomainError> = vec![
+            BankAccountError::NoPendingEmailChangeError.into(),
+            BankAccountError::InvalidEmailChangeTokenError.into(),
+            MoneyError::OverflowError.into(),
+        ];
+
+        for error in errors.into_iter() {
+            let error: ApplicationError = error.into();
+            let (json, error_from_problem) = round_trip(&error);
+
+            assert_eq!(json["code"], error.code());
+            // フィールドの無いバリアントも同じドメインのエラーに戻る
+            assert!(
+                matches!(error_from_problem, ApplicationError::DomainError(_)),
+                "{error_from_problem:?}"
+            );
+            assert_eq!(error_from_problem.to_string(), error.to_string());
+        }
+    }
+
+    #[test]
+    fn problem_details_with_detail() {
+        let error = ApplicationError::RecordNotFound("Not found id: xxx".to_string());
+
+        let (json, error_from_problem) = round_trip(&error);
+
+        assert_eq!(json["status"], 404);
+        assert_eq!(json["detail"], "Not found id: xxx");
+        assert_eq!(error_from_problem.to_string(), error.to_string());
+    }
+}
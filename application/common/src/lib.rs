@@ -1,5 +1,6 @@
 mod error;
 pub mod query_statement;
+pub mod read_models;
 
-pub use error::ApplicationError;
+pub use error::{ApplicationError, ProblemDetails};
 pub mod commands;
@@ -0,0 +1,2 @@
+pub mod ledger_entry;
+pub mod rejected_command;
@@ -0,0 +1,399 @@
+use domain::aggregates::atm::AtmId;
+use domain::aggregates::bank_account::{BankAccountId, CheckNumber};
+use domain::events::bank_account_events::BankAccountEvent;
+use domain::{Balance, Currency, Decimal, DomainError, Money};
+
+use sea_orm::entity::prelude::*;
+use sea_orm::ActiveValue::{NotSet, Set};
+use serde::{Deserialize, Serialize};
+
+// -------------------------------------------------------------------------------------------------
+// LedgerEntryKind
+
+/// 記帳の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(32))")]
+pub enum LedgerEntryKind {
+    /// Atmでの預け入れ
+    #[sea_orm(string_value = "Deposit")]
+    Deposit,
+    /// Atmでの引き出し
+    #[sea_orm(string_value = "Withdrawal")]
+    Withdrawal,
+    /// 小切手の引き落とし
+    #[sea_orm(string_value = "Check")]
+    Check,
+    /// 不渡りとなった小切手の返金
+    #[sea_orm(string_value = "CheckRefund")]
+    CheckRefund,
+    /// 他のアカウントへの送金
+    #[sea_orm(string_value = "TransferOut")]
+    TransferOut,
+    /// 他のアカウントからの入金
+    #[sea_orm(string_value = "TransferIn")]
+    TransferIn,
+    /// 当座貸越の手数料
+    #[sea_orm(string_value = "OverdraftFee")]
+    OverdraftFee,
+    /// 利息
+    #[sea_orm(string_value = "Interest")]
+    Interest,
+}
+
+// -------------------------------------------------------------------------------------------------
+// sea_orm用Model
+
+/// 口座の取引ごとの記帳．BankAccountEventから作成する読み取りモデル
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "ledger_entry")]
+pub struct Model {
+    /// 記帳の順序を表す連番
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    #[sea_orm(indexed)]
+    pub account_id: BankAccountId,
+    pub kind: LedgerEntryKind,
+    /// 口座の通貨での金額．入金は正，出金は負とする
+    pub amount: Decimal,
+    /// 記帳後の残高
+    pub balance: Decimal,
+    pub currency: Currency,
+    /// 預け入れ・引き出しを行ったAtm
+    pub atm_id: Option<AtmId>,
+    /// 小切手番号
+    pub check_number: Option<CheckNumber>,
+    /// 送金の相手のアカウント
+    pub counterparty_account_id: Option<BankAccountId>,
+    #[sea_orm(indexed)]
+    pub recorded_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// 記帳の参照先
+#[derive(Default)]
+struct EntryReference {
+    atm_id: Option<AtmId>,
+    check_number: Option<CheckNumber>,
+    counterparty_account_id: Option<BankAccountId>,
+}
+
+impl ActiveModel {
+    /// 残高の変わるイベントから記帳を作成する．残高の変わらないイベントはNone
+    pub fn from_event(
+        event: &BankAccountEvent,
+        recorded_at: DateTimeUtc,
+    ) -> Result<Option<Self>, DomainError> {
+        use BankAccountEvent::*;
+        use LedgerEntryKind as Kind;
+
+        let credit = |money: Money| money.amount();
+        let debit = |money: Money| -money.amount();
+
+        let (account_id, kind, amount, balance, reference) = match event {
+            CustomerDepositedMoneyEvent(e) => (
+                e.account_id,
+                Kind::Deposit,
+                // 預け入れた現金はAtmの通貨のため，口座の通貨に換算する
+                credit(e.applied_rate.convert(e.amount)?),
+                e.balance,
+                EntryReference {
+                    atm_id: Some(e.atm_id),
+                    ..Default::default()
+                },
+            ),
+            CustomerWithdrewCashEvent(e) => (
+                e.account_id,
+                Kind::Withdrawal,
+                debit(e.amount),
+                e.balance,
+                EntryReference {
+                    atm_id: Some(e.atm_id),
+                    ..Default::default()
+                },
+            ),
+            CustomerWroteCheckEvent(e) => (
+                e.account_id,
+                Kind::Check,
+                debit(e.amount),
+                e.balance,
+                EntryReference {
+                    check_number: Some(e.check_number.clone()),
+                    ..Default::default()
+                },
+            ),
+            CheckBouncedEvent(e) => (
+                e.account_id,
+                Kind::CheckRefund,
+                credit(e.amount),
+                e.balance,
+                EntryReference {
+                    check_number: Some(e.check_number.clone()),
+                    ..Default::default()
+                },
+            ),
+            MoneyTransferredOutEvent(e) => (
+                e.account_id,
+                Kind::TransferOut,
+                debit(e.amount),
+                e.balance,
+                EntryReference {
+                    counterparty_account_id: Some(e.to_account_id),
+                    ..Default::default()
+                },
+            ),
+            MoneyTransferredInEvent(e) => (
+                e.account_id,
+                Kind::TransferIn,
+                credit(e.amount),
+                e.balance,
+                EntryReference {
+                    counterparty_account_id: Some(e.from_account_id),
+                    ..Default::default()
+                },
+            ),
+            OverdraftFeeChargedEvent(e) => (
+                e.account_id,
+                Kind::OverdraftFee,
+                debit(e.fee),
+                e.balance,
+                EntryReference::default(),
+            ),
+            InterestAccruedEvent(e) => (
+                e.account_id,
+                Kind::Interest,
+                credit(e.interest),
+                e.balance,
+                EntryReference::default(),
+            ),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(Self {
+            id: NotSet,
+            account_id: Set(account_id),
+            kind: Set(kind),
+            amount: Set(amount),
+            balance: Set(balance.amount()),
+            currency: Set(balance.currency()),
+            atm_id: Set(reference.atm_id),
+            check_number: Set(reference.check_number),
+            counterparty_account_id: Set(reference.counterparty_account_id),
+            recorded_at: Set(recorded_at),
+        }))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Statement
+
+/// 期間内の記帳と期首・期末の残高をまとめた取引明細
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Statement {
+    pub account_id: BankAccountId,
+    pub currency: Currency,
+    /// 期間の開始(含む)
+    pub from: DateTimeUtc,
+    /// 期間の終了(含まない)
+    pub to: DateTimeUtc,
+    pub opening_balance: Decimal,
+    pub closing_balance: Decimal,
+    /// 記帳順の記帳
+    pub entries: Vec<Model>,
+}
+
+impl Statement {
+    /// previousは期間より前の最後の記帳，nextは期間より後の最初の記帳．
+    /// 期首の残高は，previousが無い場合は期間内の最初の記帳の前の残高とし，期間内にも記帳が無い場合はnextの前の残高とする．
+    /// 記帳が一つも無い場合は記帳の導入前から変わっていないアカウントの現在の残高とする
+    pub fn new(
+        account_id: BankAccountId,
+        current_balance: Balance,
+        from: DateTimeUtc,
+        to: DateTimeUtc,
+        previous: Option<Model>,
+        entries: Vec<Model>,
+        next: Option<Model>,
+    ) -> Self {
+        let opening_balance = match (previous, entries.first(), next) {
+            (Some(previous), _, _) => previous.balance,
+            (None, Some(first), _) => first.balance - first.amount,
+            (None, None, Some(next)) => next.balance - next.amount,
+            (None, None, None) => current_balance.amount(),
+        };
+        let closing_balance = entries.last().map_or(opening_balance, |last| last.balance);
+
+        Self {
+            account_id,
+            currency: current_balance.currency(),
+            from,
+            to,
+            opening_balance,
+            closing_balance,
+            entries,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// test
+
+#[cfg(test)]
+mod test {
+    use super::{ActiveModel, LedgerEntryKind, Model, Statement};
+    use domain::aggregates::bank_account::CheckNumber;
+    use domain::events::bank_account_events::{
+        AccountFrozenEvent, BankAccountEvent, CustomerDepositedMoneyEvent, CustomerWroteCheckEvent,
+    };
+    use domain::{Balance, Currency, Decimal, ExchangeRate, Money};
+
+    use chrono::{TimeZone, Utc};
+    use fake::{Fake, Faker};
+    use sea_orm::ActiveValue::{NotSet, Set};
+    use sea_orm::TryIntoModel;
+
+    #[test]
+    fn entry_from_event() {
+        let recorded_at = Utc.with_ymd_and_hms(2026, 10, 1, 9, 0, 0).unwrap();
+        let account_id = Faker.fake();
+        let atm_id = Faker.fake();
+
+        // 預け入れはAtmの通貨から口座の通貨に換算する
+        let event: BankAccountEvent = CustomerDepositedMoneyEvent {
+            account_id,
+            amount: Money::new(Decimal::new(10, 0), Currency::USD).unwrap(),
+            balance: Balance::new(Decimal::new(2500, 0), Currency::JPY).unwrap(),
+            atm_id,
+            applied_rate: ExchangeRate::new(Currency::USD, Currency::JPY, Decimal::new(150, 0))
+                .unwrap(),
+        }
+        .into();
+        let entry = ActiveModel::from_event(&event, recorded_at)
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.id, NotSet);
+        assert_eq!(entry.kind, Set(LedgerEntryKind::Deposit));
+        assert_eq!(entry.amount, Set(Decimal::new(1500, 0)));
+        assert_eq!(entry.currency, Set(Currency::JPY));
+        assert_eq!(entry.atm_id, Set(Some(atm_id)));
+
+        let check_number: CheckNumber = Faker.fake();
+        let event: BankAccountEvent = CustomerWroteCheckEvent {
+            account_id,
+            check_number: check_number.clone(),
+            amount: Money::new(Decimal::new(500, 0), Currency::JPY).unwrap(),
+            balance: Balance::new(Decimal::new(2000, 0), Currency::JPY).unwrap(),
+        }
+        .into();
+        let entry = ActiveModel::from_event(&event, recorded_at)
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.kind, Set(LedgerEntryKind::Check));
+        assert_eq!(entry.amount, Set(Decimal::new(-500, 0)));
+        assert_eq!(entry.balance, Set(Decimal::new(2000, 0)));
+        assert_eq!(entry.check_number, Set(Some(check_number)));
+        assert_eq!(entry.atm_id, Set(None));
+
+        // 残高の変わらないイベントは記帳しない
+        let event: BankAccountEvent = AccountFrozenEvent {
+            account_id,
+            reason: "suspicious".to_string(),
+        }
+        .into();
+        assert!(ActiveModel::from_event(&event, recorded_at)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn statement_balances() {
+        let account_id = Faker.fake();
+        let from = Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap();
+        let current_balance = Balance::new(Decimal::new(2200, 0), Currency::JPY).unwrap();
+
+        let entry = |id: i64, amount: i64, balance: i64| -> Model {
+            let mut entry = ActiveModel::from_event(
+                &CustomerWroteCheckEvent {
+                    account_id,
+                    check_number: Faker.fake(),
+                    amount: Money::new(Decimal::new(-amount, 0), Currency::JPY).unwrap(),
+                    balance: Balance::new(Decimal::new(balance, 0), Currency::JPY).unwrap(),
+                }
+                .into(),
+                from,
+            )
+            .unwrap()
+            .unwrap();
+            entry.id = Set(id);
+            entry.try_into_model().unwrap()
+        };
+
+        let statement = Statement::new(
+            account_id,
+            current_balance,
+            from,
+            to,
+            Some(entry(1, -100, 3000)),
+            vec![entry(2, -500, 2500), entry(3, -300, 2200)],
+            None,
+        );
+        assert_eq!(statement.opening_balance, Decimal::new(3000, 0));
+        assert_eq!(statement.closing_balance, Decimal::new(2200, 0));
+
+        // 期間より前の記帳が無い場合は最初の記帳の前の残高
+        let statement = Statement::new(
+            account_id,
+            current_balance,
+            from,
+            to,
+            None,
+            vec![entry(2, -500, 2500)],
+            None,
+        );
+        assert_eq!(statement.opening_balance, Decimal::new(3000, 0));
+        assert_eq!(statement.closing_balance, Decimal::new(2500, 0));
+
+        // 期間内に記帳が無い場合は期首と期末の残高が等しい
+        let statement = Statement::new(
+            account_id,
+            current_balance,
+            from,
+            to,
+            Some(entry(1, -100, 3000)),
+            Vec::new(),
+            None,
+        );
+        assert_eq!(statement.closing_balance, statement.opening_balance);
+
+        // 期間より前・期間内に記帳が無い場合は期間より後の最初の記帳の前の残高．現在の残高は後の取引を含むため使わない
+        let statement = Statement::new(
+            account_id,
+            current_balance,
+            from,
+            to,
+            None,
+            Vec::new(),
+            Some(entry(4, -300, 2200)),
+        );
+        assert_eq!(statement.opening_balance, Decimal::new(2500, 0));
+        assert_eq!(statement.closing_balance, Decimal::new(2500, 0));
+
+        // 記帳が一つも無い場合はアカウントの現在の残高
+        let statement = Statement::new(
+            account_id,
+            current_balance,
+            from,
+            to,
+            None,
+            Vec::new(),
+            None,
+        );
+        assert_eq!(statement.currency, Currency::JPY);
+        assert_eq!(statement.opening_balance, Decimal::new(2200, 0));
+        assert_eq!(statement.closing_balance, Decimal::new(2200, 0));
+    }
+}
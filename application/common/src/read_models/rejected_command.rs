@@ -0,0 +1,42 @@
+use crate::commands::CommandId;
+use domain::Id;
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// -------------------------------------------------------------------------------------------------
+// RejectedCommandId
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RejectedCommandIdType;
+
+pub type RejectedCommandId = Id<RejectedCommandIdType>;
+
+// -------------------------------------------------------------------------------------------------
+// sea_orm用Model
+
+/// ドメインのルールにより拒否されたコマンドの監査ログ
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "rejected_command")]
+pub struct Model {
+    /// id
+    #[sea_orm(primary_key, auto_increment = false, unique)]
+    pub id: RejectedCommandId,
+    /// 拒否されたコマンドのid
+    pub command_id: CommandId,
+    /// 拒否されたコマンドの名前
+    pub command_name: String,
+    /// コマンドの対象となったアグリゲイトのid
+    pub aggregate_id: Option<Uuid>,
+    /// 拒否の理由となったエラーのコード
+    pub error_code: String,
+    /// 拒否の理由となったエラーの詳細
+    pub detail: String,
+    /// 拒否された日時
+    pub rejected_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -4,7 +4,8 @@ use serde::Deserialize;
 #[allow(dead_code)]
 #[derive(Deserialize, Debug)]
 struct QueryResult {
-    account_name: String,
+    first_name: String,
+    last_name: String,
 }
 
 #[tokio::main]
@@ -16,7 +17,7 @@ async fn main() -> Result<(), ApplicationError> {
     let query = QueryStatement::from_string(
         DatabaseBackend::Postgres,
         r#"
-SELECT "account_name" FROM "bank_account"
+SELECT "first_name", "last_name" FROM "bank_account"
     "#,
     );
 
@@ -26,9 +27,10 @@ SELECT "account_name" FROM "bank_account"
 
     let query_res_2 = frontend::query_all_custom::<QueryResult>(QueryStatement::from_select(
         DatabaseBackend::Postgres,
-        bank_account::orm::Entity::find()
-            .select_only()
-            .columns([bank_account::orm::Column::AccountName]),
+        bank_account::orm::Entity::find().select_only().columns([
+            bank_account::orm::Column::FirstName,
+            bank_account::orm::Column::LastName,
+        ]),
     ))
     .await?;
 
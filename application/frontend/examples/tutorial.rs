@@ -6,14 +6,30 @@ async fn main() {
     use frontend::CommandId;
     use frontend::{execute_atm_command, execute_bank_account_command};
     use frontend::{AtmCommand, BankAccountCommand};
+    use frontend::{Currency, Decimal, Money};
 
     // Atmの登録
     {
-        let location = atm::AtmLocation::new("東京都");
+        let location = atm::AtmLocation::new(
+            atm::AtmAddress::new(
+                "100-0005".to_string(),
+                "東京都".to_string(),
+                "千代田区".to_string(),
+                "丸の内1-9-1".to_string(),
+            )
+            .unwrap(),
+            atm::GeoPoint::new(35.681, 139.767).unwrap(),
+        );
+        // 一万円札と千円札をカセットに入れる
+        let notes = atm::NoteBreakdown::new([
+            (atm::Denomination::new(10000), 9_000),
+            (atm::Denomination::new(1000), 10_000),
+        ]);
         execute_atm_command(AtmCommand::RegisterAtmCommand(
             RegisterAtmRefCommand {
                 location: &location,
-                total_cash: 100_000_000.0,
+                currency: Currency::JPY,
+                notes: &notes,
             },
             CommandId::generate(),
         ))
@@ -21,7 +37,16 @@ async fn main() {
         .unwrap();
     }
     let atm = {
-        let location = atm::AtmLocation::new("東京都");
+        let location = atm::AtmLocation::new(
+            atm::AtmAddress::new(
+                "100-0005".to_string(),
+                "東京都".to_string(),
+                "千代田区".to_string(),
+                "丸の内1-9-1".to_string(),
+            )
+            .unwrap(),
+            atm::GeoPoint::new(35.681, 139.767).unwrap(),
+        );
         frontend::queries::atm_queries::atm_from_location(&location)
             .await
             .unwrap()
@@ -29,6 +54,21 @@ async fn main() {
     };
     println!("atm: {atm:?}");
 
+    // 最寄りのAtmの検索
+    {
+        let point = atm::GeoPoint::new(35.690, 139.700).unwrap();
+        let nearest_atms = frontend::queries::atm_queries::atm_nearest(&point, 3)
+            .await
+            .unwrap();
+        for location in nearest_atms.iter().filter_map(atm::Atm::location) {
+            println!(
+                "nearest atm: {}, {:.1} km",
+                location.address(),
+                location.coordinates().distance_km(&point)
+            );
+        }
+    }
+
     // 口座の開設
     {
         let account_name =
@@ -41,6 +81,9 @@ async fn main() {
             OpenAccountRefCommand {
                 account_name: &account_name,
                 email_address: &email_address,
+                currency: Currency::JPY,
+                tier: bank_account::AccountTier::Standard,
+                account_type: bank_account::AccountType::Checking,
             },
             CommandId::generate(),
         ))
@@ -58,6 +101,9 @@ async fn main() {
             OpenAccountRefCommand {
                 account_name: &account_name,
                 email_address: &email_address,
+                currency: Currency::JPY,
+                tier: bank_account::AccountTier::Standard,
+                account_type: bank_account::AccountType::Checking,
             },
             CommandId::generate(),
         ))
@@ -79,7 +125,7 @@ async fn main() {
         execute_bank_account_command(BankAccountCommand::DepositMoneyCommand(
             DepositMoneyCommand {
                 account_id: bank_account.id(),
-                amount: 100_000.0,
+                amount: Money::new(Decimal::from(100_000), Currency::JPY).unwrap(),
                 atm_id: atm.id(),
             },
             CommandId::generate(),
@@ -92,7 +138,7 @@ async fn main() {
         execute_bank_account_command(BankAccountCommand::DepositMoneyCommand(
             DepositMoneyCommand {
                 account_id: bank_account.id(),
-                amount: 10_000.0,
+                amount: Money::new(Decimal::from(10_000), Currency::JPY).unwrap(),
                 atm_id: atm.id(),
             },
             CommandId::generate(),
@@ -102,7 +148,16 @@ async fn main() {
     }
 
     let updated_atm = {
-        let location = atm::AtmLocation::new("東京都");
+        let location = atm::AtmLocation::new(
+            atm::AtmAddress::new(
+                "100-0005".to_string(),
+                "東京都".to_string(),
+                "千代田区".to_string(),
+                "丸の内1-9-1".to_string(),
+            )
+            .unwrap(),
+            atm::GeoPoint::new(35.681, 139.767).unwrap(),
+        );
         frontend::queries::atm_queries::atm_from_location(&location)
             .await
             .unwrap()
@@ -5,9 +5,12 @@ use crate::API_BASE_URL;
 
 use common::commands::atm_commands::AtmRefCommand;
 use common::commands::bank_account_commands::BankAccountRefCommand;
+use common::read_models::ledger_entry;
 use common::{query_statement::QueryStatement, ApplicationError};
+use domain::aggregates::bank_account::owner_orm;
 use domain::aggregates::{Atm, BankAccount};
 
+use futures::Stream;
 use serde::de::DeserializeOwned;
 
 /// BankAccountCommandを実行
@@ -36,6 +39,13 @@ pub async fn query_all_bank_account(
     inner::query_all_bank_account(API_BASE_URL, query_stmt).await
 }
 
+/// BankAccountの共同名義人に関するクエリを実行して結果を複数取得する．
+pub async fn query_all_bank_account_owner(
+    query_stmt: QueryStatement,
+) -> Result<Vec<owner_orm::Model>, ApplicationError> {
+    inner::query_all_bank_account_owner(API_BASE_URL, query_stmt).await
+}
+
 /// Atmに関するクエリを実行して結果を一つ取得する．
 pub async fn query_one_atm(query_stmt: QueryStatement) -> Result<Option<Atm>, ApplicationError> {
     inner::query_one_atm(API_BASE_URL, query_stmt).await
@@ -46,6 +56,20 @@ pub async fn query_all_atm(query_stmt: QueryStatement) -> Result<Vec<Atm>, Appli
     inner::query_all_atm(API_BASE_URL, query_stmt).await
 }
 
+/// 口座の記帳に関するクエリを実行して結果を一つ取得する．
+pub async fn query_one_ledger_entry(
+    query_stmt: QueryStatement,
+) -> Result<Option<ledger_entry::Model>, ApplicationError> {
+    inner::query_one_ledger_entry(API_BASE_URL, query_stmt).await
+}
+
+/// 口座の記帳に関するクエリを実行して結果を複数取得する．
+pub async fn query_all_ledger_entry(
+    query_stmt: QueryStatement,
+) -> Result<Vec<ledger_entry::Model>, ApplicationError> {
+    inner::query_all_ledger_entry(API_BASE_URL, query_stmt).await
+}
+
 /// カスタムクエリを実行して結果を一つ取得する．
 pub async fn query_one_custom<T: DeserializeOwned>(
     query_stmt: QueryStatement,
@@ -59,3 +83,24 @@ pub async fn query_all_custom<T: DeserializeOwned>(
 ) -> Result<Vec<T>, ApplicationError> {
     inner::query_all_custom(API_BASE_URL, query_stmt).await
 }
+
+/// BankAccountに関するクエリを実行して結果をストリームとして取得する．全ての結果をメモリに保持しない．
+pub fn query_stream_bank_account(
+    query_stmt: QueryStatement,
+) -> impl Stream<Item = Result<BankAccount, ApplicationError>> {
+    inner::query_stream_bank_account(API_BASE_URL, query_stmt)
+}
+
+/// Atmに関するクエリを実行して結果をストリームとして取得する．
+pub fn query_stream_atm(
+    query_stmt: QueryStatement,
+) -> impl Stream<Item = Result<Atm, ApplicationError>> {
+    inner::query_stream_atm(API_BASE_URL, query_stmt)
+}
+
+/// カスタムクエリを実行して結果をストリームとして取得する．
+pub fn query_stream_custom<T: DeserializeOwned>(
+    query_stmt: QueryStatement,
+) -> impl Stream<Item = Result<T, ApplicationError>> {
+    inner::query_stream_custom(API_BASE_URL, query_stmt)
+}
@@ -1,10 +1,13 @@
 /// モックテスト用にurlを引数とする関数を定義するモジュール
-use crate::utils::{deserialize_response, deserialize_response_unit};
+use crate::utils::{deserialize_response, deserialize_response_stream, deserialize_response_unit};
 use crate::{AtmCommand, BankAccountCommand};
 
+use common::read_models::ledger_entry;
 use common::{query_statement::QueryStatement, ApplicationError};
+use domain::aggregates::bank_account::owner_orm;
 use domain::aggregates::{Atm, BankAccount};
 
+use futures::Stream;
 use reqwest::Client;
 use serde::de::DeserializeOwned;
 
@@ -60,6 +63,19 @@ pub async fn query_all_bank_account(
     deserialize_response(response).await
 }
 
+pub async fn query_all_bank_account_owner(
+    base_url: &str,
+    query_stmt: QueryStatement,
+) -> Result<Vec<owner_orm::Model>, ApplicationError> {
+    let request = Client::new()
+        .post(&format!("{base_url}/query_all/bank_account_owner"))
+        .json(&query_stmt);
+
+    let response = request.send().await?;
+
+    deserialize_response(response).await
+}
+
 pub async fn query_one_atm(
     base_url: &str,
     query_stmt: QueryStatement,
@@ -86,6 +102,32 @@ pub async fn query_all_atm(
     deserialize_response(response).await
 }
 
+pub async fn query_one_ledger_entry(
+    base_url: &str,
+    query_stmt: QueryStatement,
+) -> Result<Option<ledger_entry::Model>, ApplicationError> {
+    let request = Client::new()
+        .post(&format!("{base_url}/query_one/ledger_entry"))
+        .json(&query_stmt);
+
+    let response = request.send().await?;
+
+    deserialize_response(response).await
+}
+
+pub async fn query_all_ledger_entry(
+    base_url: &str,
+    query_stmt: QueryStatement,
+) -> Result<Vec<ledger_entry::Model>, ApplicationError> {
+    let request = Client::new()
+        .post(&format!("{base_url}/query_all/ledger_entry"))
+        .json(&query_stmt);
+
+    let response = request.send().await?;
+
+    deserialize_response(response).await
+}
+
 pub async fn query_one_custom<T: DeserializeOwned>(
     base_url: &str,
     query_stmt: QueryStatement,
@@ -111,3 +153,40 @@ pub async fn query_all_custom<T: DeserializeOwned>(
 
     deserialize_response(response).await
 }
+
+pub fn query_stream_bank_account(
+    base_url: &str,
+    query_stmt: QueryStatement,
+) -> impl Stream<Item = Result<BankAccount, ApplicationError>> {
+    query_stream(format!("{base_url}/query_stream/bank_account"), query_stmt)
+}
+
+pub fn query_stream_atm(
+    base_url: &str,
+    query_stmt: QueryStatement,
+) -> impl Stream<Item = Result<Atm, ApplicationError>> {
+    query_stream(format!("{base_url}/query_stream/atm"), query_stmt)
+}
+
+pub fn query_stream_custom<T: DeserializeOwned>(
+    base_url: &str,
+    query_stmt: QueryStatement,
+) -> impl Stream<Item = Result<T, ApplicationError>> {
+    query_stream(format!("{base_url}/query_stream/custom"), query_stmt)
+}
+
+/// ストリームのクエリに共通する処理．リクエストの失敗はストリームの最初の要素として返る．
+fn query_stream<T: DeserializeOwned>(
+    url: String,
+    query_stmt: QueryStatement,
+) -> impl Stream<Item = Result<T, ApplicationError>> {
+    async_stream::try_stream! {
+        let request = Client::new().post(&url).json(&query_stmt);
+
+        let response = request.send().await?;
+
+        for await res in deserialize_response_stream::<T>(response).await? {
+            yield res?;
+        }
+    }
+}
@@ -8,6 +8,7 @@ pub use api_handler::*;
 pub use common::commands;
 pub use common::commands::CommandId;
 pub use common::query_statement;
+pub use common::read_models;
 pub use common::ApplicationError;
 
 use common::commands::{atm_commands::AtmRefCommand, bank_account_commands::BankAccountRefCommand};
@@ -16,6 +17,7 @@ pub type BankAccountCommand<'a> = BankAccountRefCommand<'a>;
 
 // domainからの再エクスポート
 pub use domain::aggregates;
+pub use domain::{Balance, Currency, Decimal, Money};
 
 use config::CONFIG;
 
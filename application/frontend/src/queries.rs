@@ -1,2 +1,3 @@
 pub mod atm_queries;
 pub mod bank_account_queries;
+pub mod ledger_queries;
@@ -4,7 +4,8 @@ mod inner {
         ApplicationError,
     };
     use domain::aggregates::atm::{self, Atm};
-    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+    use sea_orm::sea_query::Expr;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
 
     pub async fn atm_all(base_url: &str) -> Result<Vec<Atm>, ApplicationError> {
         let query = QueryStatement::from_select(DEFAULT_DB_BACKEND, atm::orm::Entity::find());
@@ -16,13 +17,47 @@ mod inner {
         base_url: &str,
         location: &atm::AtmLocation,
     ) -> Result<Option<Atm>, ApplicationError> {
+        let address = location.address();
         let query = QueryStatement::from_select(
             DEFAULT_DB_BACKEND,
-            atm::orm::Entity::find().filter(atm::orm::Column::Location.eq(location)),
+            atm::orm::Entity::find()
+                .filter(atm::orm::Column::PostalCode.eq(address.postal_code()))
+                .filter(atm::orm::Column::Region.eq(address.region()))
+                .filter(atm::orm::Column::City.eq(address.city()))
+                .filter(atm::orm::Column::Street.eq(address.street())),
         );
 
         crate::api_handler::inner::query_one_atm(base_url, query).await
     }
+
+    /// 最寄りのAtmを検索するクエリ．並び替えには経度の縮みを補正した緯度・経度の差の二乗和を用いる．
+    /// 場所が未登録のAtmは停止中だが，座標の無い行も明示的に除く
+    pub fn atm_nearest_query(point: &atm::GeoPoint, limit: u64) -> QueryStatement {
+        let distance = Expr::cust_with_values(
+            r#"power("latitude" - $1, 2) + power(("longitude" - $2) * cos(radians($1)), 2)"#,
+            [point.latitude(), point.longitude()],
+        );
+
+        QueryStatement::from_select(
+            DEFAULT_DB_BACKEND,
+            atm::orm::Entity::find()
+                .filter(atm::orm::Column::Status.eq(atm::AtmStatus::InService))
+                .filter(atm::orm::Column::Latitude.is_not_null())
+                .filter(atm::orm::Column::Longitude.is_not_null())
+                .order_by_asc(distance)
+                .limit(limit),
+        )
+    }
+
+    pub async fn atm_nearest(
+        base_url: &str,
+        point: &atm::GeoPoint,
+        limit: u64,
+    ) -> Result<Vec<Atm>, ApplicationError> {
+        let query = atm_nearest_query(point, limit);
+
+        crate::api_handler::inner::query_all_atm(base_url, query).await
+    }
 }
 
 use crate::API_BASE_URL;
@@ -34,9 +69,42 @@ pub async fn atm_all() -> Result<Vec<Atm>, ApplicationError> {
     inner::atm_all(API_BASE_URL).await
 }
 
-/// `location`を持つAtmを取得
+/// `location`の住所にあるAtmを取得
 pub async fn atm_from_location(
     location: &atm::AtmLocation,
 ) -> Result<Option<Atm>, ApplicationError> {
     inner::atm_from_location(API_BASE_URL, location).await
 }
+
+/// `point`から近い順に稼働中のAtmを`limit`件まで取得．距離は`GeoPoint::distance_km`で求められる
+pub async fn atm_nearest(point: &atm::GeoPoint, limit: u64) -> Result<Vec<Atm>, ApplicationError> {
+    inner::atm_nearest(API_BASE_URL, point, limit).await
+}
+
+// -------------------------------------------------------------------------------------------------
+// test
+
+#[cfg(test)]
+mod test {
+    use super::inner::atm_nearest_query;
+    use domain::aggregates::atm::GeoPoint;
+
+    #[test]
+    fn nearest_query_sql() {
+        let point = GeoPoint::new(35.681, 139.767).unwrap();
+        let sql: String = atm_nearest_query(&point, 3).into();
+
+        assert!(
+            sql.contains(
+                r#"WHERE "atm"."status" = 'InService' AND "atm"."latitude" IS NOT NULL AND "atm"."longitude" IS NOT NULL"#
+            ),
+            "{sql}"
+        );
+        assert!(
+            sql.contains(
+                r#"ORDER BY power("latitude" - 35.681, 2) + power(("longitude" - 139.767) * cos(radians(35.681)), 2) ASC LIMIT 3"#
+            ),
+            "{sql}"
+        );
+    }
+}
@@ -3,14 +3,65 @@ mod inner {
         query_statement::{QueryStatement, DEFAULT_DB_BACKEND},
         ApplicationError,
     };
-    use domain::aggregates::bank_account::{self, BankAccount, EmailAddress};
-    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+    use domain::aggregates::bank_account::{
+        self, owner_orm, BankAccount, BankAccountId, EmailAddress,
+    };
+    use futures::Stream;
+    use sea_orm::prelude::Uuid;
+    use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, QuerySelect, RelationTrait};
+    use std::collections::HashMap;
+
+    /// 指定したアカウントの共同名義人を取得するクエリ
+    pub fn owners_query(account_ids: impl IntoIterator<Item = BankAccountId>) -> QueryStatement {
+        QueryStatement::from_select(
+            DEFAULT_DB_BACKEND,
+            owner_orm::Entity::find().filter(owner_orm::Column::AccountId.is_in(account_ids)),
+        )
+    }
+
+    /// 共同名義人は子テーブルに保持するため，別のクエリで取得してアカウントに合わせる．
+    /// アカウントのidの型はHashを実装しないため，uuidで対応させる
+    async fn with_owners(
+        base_url: &str,
+        bank_accounts: Vec<BankAccount>,
+    ) -> Result<Vec<BankAccount>, ApplicationError> {
+        if bank_accounts.is_empty() {
+            return Ok(bank_accounts);
+        }
+
+        let query = owners_query(bank_accounts.iter().map(|bank_account| bank_account.id()));
+        let mut owners: HashMap<Uuid, Vec<owner_orm::Model>> = HashMap::new();
+        for owner in
+            crate::api_handler::inner::query_all_bank_account_owner(base_url, query).await?
+        {
+            owners.entry(owner.account_id.to_uuid()).or_default().push(owner);
+        }
+
+        Ok(bank_accounts
+            .into_iter()
+            .map(|bank_account| {
+                let owners = owners.remove(&bank_account.id().to_uuid()).unwrap_or_default();
+                bank_account.with_owner_models(owners)
+            })
+            .collect())
+    }
 
     pub async fn bank_account_all(base_url: &str) -> Result<Vec<BankAccount>, ApplicationError> {
         let query =
             QueryStatement::from_select(DEFAULT_DB_BACKEND, bank_account::orm::Entity::find());
 
-        crate::api_handler::inner::query_all_bank_account(base_url, query).await
+        let bank_accounts =
+            crate::api_handler::inner::query_all_bank_account(base_url, query).await?;
+        with_owners(base_url, bank_accounts).await
+    }
+
+    pub fn bank_account_all_stream(
+        base_url: &str,
+    ) -> impl Stream<Item = Result<BankAccount, ApplicationError>> {
+        let query =
+            QueryStatement::from_select(DEFAULT_DB_BACKEND, bank_account::orm::Entity::find());
+
+        crate::api_handler::inner::query_stream_bank_account(base_url, query)
     }
 
     pub async fn bank_account_from_email(
@@ -22,20 +73,89 @@ mod inner {
             bank_account::orm::Entity::find()
                 .filter(bank_account::orm::Column::EmailAddress.eq(email_address)),
         );
-        crate::api_handler::inner::query_one_bank_account(base_url, query).await
+        let bank_account =
+            crate::api_handler::inner::query_one_bank_account(base_url, query).await?;
+        Ok(with_owners(base_url, bank_account.into_iter().collect())
+            .await?
+            .pop())
+    }
+
+    pub async fn bank_accounts_from_owner_email(
+        base_url: &str,
+        email_address: &EmailAddress,
+    ) -> Result<Vec<BankAccount>, ApplicationError> {
+        let query = QueryStatement::from_select(
+            DEFAULT_DB_BACKEND,
+            bank_account::orm::Entity::find()
+                .join(
+                    sea_orm::JoinType::LeftJoin,
+                    bank_account::orm::Relation::Owner.def(),
+                )
+                .filter(
+                    Condition::any()
+                        .add(bank_account::orm::Column::EmailAddress.eq(email_address))
+                        .add(bank_account::owner_orm::Column::EmailAddress.eq(email_address)),
+                )
+                .distinct(),
+        );
+        let bank_accounts =
+            crate::api_handler::inner::query_all_bank_account(base_url, query).await?;
+        with_owners(base_url, bank_accounts).await
     }
 }
 
 use crate::API_BASE_URL;
 use common::ApplicationError;
 use domain::aggregates::{bank_account::EmailAddress, BankAccount};
+use futures::Stream;
 
 pub async fn bank_account_all() -> Result<Vec<BankAccount>, ApplicationError> {
     inner::bank_account_all(API_BASE_URL).await
 }
 
+/// 全てのBankAccountをストリームとして取得．全件の走査でもメモリに全て保持しない．
+/// 共同名義人は取得しない．
+pub fn bank_account_all_stream() -> impl Stream<Item = Result<BankAccount, ApplicationError>> {
+    inner::bank_account_all_stream(API_BASE_URL)
+}
+
 pub async fn bank_account_from_email(
     email_address: &EmailAddress,
 ) -> Result<Option<BankAccount>, ApplicationError> {
     inner::bank_account_from_email(API_BASE_URL, email_address).await
 }
+
+/// 主名義人・共同名義人のいずれかとしてemail_addressを含むBankAccountを取得
+pub async fn bank_accounts_from_owner_email(
+    email_address: &EmailAddress,
+) -> Result<Vec<BankAccount>, ApplicationError> {
+    inner::bank_accounts_from_owner_email(API_BASE_URL, email_address).await
+}
+
+// -------------------------------------------------------------------------------------------------
+// test
+
+#[cfg(test)]
+mod test {
+    use super::inner::owners_query;
+    use domain::aggregates::bank_account::BankAccountId;
+
+    #[test]
+    fn owners_query_sql() {
+        let ids = [BankAccountId::generate(), BankAccountId::generate()];
+
+        let sql: String = owners_query(ids).into();
+        assert!(
+            sql.starts_with(r#"SELECT "bank_account_owner"."id""#),
+            "{sql}"
+        );
+        assert!(
+            sql.contains(&format!(
+                r#""bank_account_owner"."account_id" IN ('{}', '{}')"#,
+                ids[0].to_uuid(),
+                ids[1].to_uuid()
+            )),
+            "{sql}"
+        );
+    }
+}
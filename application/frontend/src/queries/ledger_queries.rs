@@ -0,0 +1,168 @@
+mod inner {
+    use common::{
+        query_statement::{QueryStatement, DEFAULT_DB_BACKEND},
+        read_models::ledger_entry::{self, Statement},
+        ApplicationError,
+    };
+    use domain::aggregates::bank_account::{self, BankAccountId};
+
+    use chrono::{DateTime, Utc};
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+
+    /// 期間内の記帳を記帳順に取得するクエリ
+    pub fn ledger_entries_query(
+        account_id: BankAccountId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> QueryStatement {
+        QueryStatement::from_select(
+            DEFAULT_DB_BACKEND,
+            ledger_entry::Entity::find()
+                .filter(ledger_entry::Column::AccountId.eq(account_id))
+                .filter(ledger_entry::Column::RecordedAt.gte(from))
+                .filter(ledger_entry::Column::RecordedAt.lt(to))
+                .order_by_asc(ledger_entry::Column::Id),
+        )
+    }
+
+    /// 期間より前の最後の記帳を取得するクエリ
+    pub fn previous_ledger_entry_query(
+        account_id: BankAccountId,
+        from: DateTime<Utc>,
+    ) -> QueryStatement {
+        QueryStatement::from_select(
+            DEFAULT_DB_BACKEND,
+            ledger_entry::Entity::find()
+                .filter(ledger_entry::Column::AccountId.eq(account_id))
+                .filter(ledger_entry::Column::RecordedAt.lt(from))
+                .order_by_desc(ledger_entry::Column::Id)
+                .limit(1),
+        )
+    }
+
+    /// 期間より後の最初の記帳を取得するクエリ
+    pub fn next_ledger_entry_query(account_id: BankAccountId, to: DateTime<Utc>) -> QueryStatement {
+        QueryStatement::from_select(
+            DEFAULT_DB_BACKEND,
+            ledger_entry::Entity::find()
+                .filter(ledger_entry::Column::AccountId.eq(account_id))
+                .filter(ledger_entry::Column::RecordedAt.gte(to))
+                .order_by_asc(ledger_entry::Column::Id)
+                .limit(1),
+        )
+    }
+
+    pub async fn account_statement(
+        base_url: &str,
+        account_id: BankAccountId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Option<Statement>, ApplicationError> {
+        let query = QueryStatement::from_select(
+            DEFAULT_DB_BACKEND,
+            bank_account::orm::Entity::find().filter(bank_account::orm::Column::Id.eq(account_id)),
+        );
+        let Some(bank_account) =
+            crate::api_handler::inner::query_one_bank_account(base_url, query).await?
+        else {
+            return Ok(None);
+        };
+
+        let previous = crate::api_handler::inner::query_one_ledger_entry(
+            base_url,
+            previous_ledger_entry_query(account_id, from),
+        )
+        .await?;
+        let entries = crate::api_handler::inner::query_all_ledger_entry(
+            base_url,
+            ledger_entries_query(account_id, from, to),
+        )
+        .await?;
+        // 期間より前・期間内に記帳が無い場合のみ，期首の残高に期間より後の最初の記帳を利用する
+        let next = if previous.is_none() && entries.is_empty() {
+            crate::api_handler::inner::query_one_ledger_entry(
+                base_url,
+                next_ledger_entry_query(account_id, to),
+            )
+            .await?
+        } else {
+            None
+        };
+
+        Ok(Some(Statement::new(
+            account_id,
+            bank_account.balance(),
+            from,
+            to,
+            previous,
+            entries,
+            next,
+        )))
+    }
+}
+
+use crate::API_BASE_URL;
+use common::read_models::ledger_entry::Statement;
+use common::ApplicationError;
+use domain::aggregates::bank_account::BankAccountId;
+
+use chrono::{DateTime, Utc};
+
+/// `from`以降`to`より前の取引明細を取得．アカウントが存在しない場合はNone
+pub async fn account_statement(
+    account_id: BankAccountId,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Option<Statement>, ApplicationError> {
+    inner::account_statement(API_BASE_URL, account_id, from, to).await
+}
+
+// -------------------------------------------------------------------------------------------------
+// test
+
+#[cfg(test)]
+mod test {
+    use super::inner::{
+        ledger_entries_query, next_ledger_entry_query, previous_ledger_entry_query,
+    };
+    use domain::aggregates::bank_account::BankAccountId;
+
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn statement_query_sql() {
+        let account_id = BankAccountId::generate();
+        let from = Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap();
+
+        let sql: String = ledger_entries_query(account_id, from, to).into();
+        assert!(
+            sql.contains(r#""ledger_entry"."recorded_at" >= '2026-10-01 00:00:00 +00:00'"#),
+            "{sql}"
+        );
+        assert!(
+            sql.contains(r#""ledger_entry"."recorded_at" < '2026-11-01 00:00:00 +00:00'"#),
+            "{sql}"
+        );
+        assert!(
+            sql.ends_with(r#"ORDER BY "ledger_entry"."id" ASC"#),
+            "{sql}"
+        );
+
+        let sql: String = previous_ledger_entry_query(account_id, from).into();
+        assert!(
+            sql.ends_with(r#"ORDER BY "ledger_entry"."id" DESC LIMIT 1"#),
+            "{sql}"
+        );
+
+        let sql: String = next_ledger_entry_query(account_id, to).into();
+        assert!(
+            sql.contains(r#""ledger_entry"."recorded_at" >= '2026-11-01 00:00:00 +00:00'"#),
+            "{sql}"
+        );
+        assert!(
+            sql.ends_with(r#"ORDER BY "ledger_entry"."id" ASC LIMIT 1"#),
+            "{sql}"
+        );
+    }
+}
@@ -1,15 +1,16 @@
-use common::ApplicationError;
+use common::{ApplicationError, ProblemDetails};
 
+use futures::Stream;
 use reqwest::Response;
 use serde::de::DeserializeOwned;
 
-/// レスポンスのResultを特定の型とエラーにデシリアライズ
+/// レスポンスのResultを特定の型とエラーにデシリアライズ．エラーはproblem detailsから復元する．
 pub async fn deserialize_response<T: DeserializeOwned>(
     response: Response,
 ) -> Result<T, ApplicationError> {
     match response.status().is_success() {
         true => Ok(response.json::<T>().await?),
-        false => Err(response.json::<ApplicationError>().await?),
+        false => Err(response.json::<ProblemDetails>().await?.into()),
     }
 }
 
@@ -17,6 +18,89 @@ pub async fn deserialize_response<T: DeserializeOwned>(
 pub async fn deserialize_response_unit(response: Response) -> Result<(), ApplicationError> {
     match response.status().is_success() {
         true => Ok(()),
-        false => Err(response.json::<ApplicationError>().await?),
+        false => Err(response.json::<ProblemDetails>().await?.into()),
+    }
+}
+
+/// NDJSONのレスポンスを一行ずつ特定の型とエラーにデシリアライズするストリームに変換
+pub async fn deserialize_response_stream<T: DeserializeOwned>(
+    response: Response,
+) -> Result<impl Stream<Item = Result<T, ApplicationError>>, ApplicationError> {
+    match response.status().is_success() {
+        true => Ok(deserialize_ndjson_stream(response.bytes_stream())),
+        false => Err(response.json::<ProblemDetails>().await?.into()),
+    }
+}
+
+/// バイト列のストリームをNDJSONとみなし，一行ずつ`Result<T, ProblemDetails>`をデシリアライズしてエラーを復元する．
+/// 行の途中でチャンクが分かれていても良い．
+fn deserialize_ndjson_stream<T, B, E, S>(
+    byte_stream: S,
+) -> impl Stream<Item = Result<T, ApplicationError>>
+where
+    T: DeserializeOwned,
+    B: AsRef<[u8]>,
+    E: Into<ApplicationError>,
+    S: Stream<Item = Result<B, E>>,
+{
+    fn deserialize_line<T: DeserializeOwned>(line: &[u8]) -> Result<T, ApplicationError> {
+        serde_json::from_slice::<Result<T, ProblemDetails>>(line)
+            .map_err(|e| ApplicationError::SerdeError(e.to_string()))?
+            .map_err(Into::into)
+    }
+
+    async_stream::try_stream! {
+        let mut buf = Vec::<u8>::new();
+
+        for await chunk in byte_stream {
+            buf.extend_from_slice(chunk.map_err(Into::<ApplicationError>::into)?.as_ref());
+
+            while let Some(pos) = buf.iter().position(|b| *b == b'\n') {
+                let line = buf.drain(..=pos).collect::<Vec<_>>();
+                if !line.trim_ascii().is_empty() {
+                    yield deserialize_line::<T>(&line)?;
+                }
+            }
+        }
+
+        // 改行で終わらない最後の行
+        if !buf.trim_ascii().is_empty() {
+            yield deserialize_line::<T>(&buf)?;
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// test
+
+#[cfg(test)]
+mod test {
+    use super::deserialize_ndjson_stream;
+    use common::{ApplicationError, ProblemDetails};
+
+    use futures::StreamExt;
+
+    #[tokio::test]
+    async fn ndjson_split_across_chunks() {
+        let error_line = serde_json::to_vec(&Err::<(), _>(ProblemDetails::from(
+            &ApplicationError::RecordNotFound("x".to_string()),
+        )))
+        .unwrap();
+        let chunks: Vec<Result<Vec<u8>, ApplicationError>> = vec![
+            Ok(br#"{"Ok":1}"#.to_vec()),
+            Ok(b"\n{\"O".to_vec()),
+            Ok(b"k\":2}\n".to_vec()),
+            Ok([error_line.as_slice(), b"\n{\"Ok\":3}\n"].concat()),
+        ];
+
+        let items = deserialize_ndjson_stream::<u32, _, _, _>(futures::stream::iter(chunks))
+            .collect::<Vec<_>>()
+            .await;
+
+        // エラーの行でストリームは終了する
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].as_ref().unwrap(), &1);
+        assert_eq!(items[1].as_ref().unwrap(), &2);
+        assert!(matches!(items[2], Err(ApplicationError::RecordNotFound(_))));
     }
 }
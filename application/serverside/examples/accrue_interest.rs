@@ -0,0 +1,60 @@
+//! 月次の利息の計上を一度だけ実行する．cron等から定期的に実行することを想定している．
+//!
+//! `cargo run --example accrue_interest -- 2023-08-31` のように計上する日付を与える．省略した場合は前月の末日とする．
+//! 月の途中で実行すると，その月の利息を前倒しで計上し，その月を計上済みとしてしまうため．
+use infrastructure::bank_account_repository_impls::DbBankAccountRepository;
+use serverside::jobs::interest_accrual_job::InterestAccrualJob;
+
+use migration::{Migrator, MigratorTrait};
+
+use chrono::{Datelike, NaiveDate, Utc};
+use sea_orm::Database;
+use tracing::Level;
+use tracing_subscriber::FmtSubscriber;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // トレーシング
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(Level::INFO)
+        .finish();
+
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    let as_of = match std::env::args().nth(1) {
+        Some(date) => NaiveDate::parse_from_str(&date, "%Y-%m-%d")?,
+        None => Utc::now()
+            .date_naive()
+            .with_day(1)
+            .and_then(|first_day| first_day.pred_opt())
+            .expect("The previous month should exist."),
+    };
+
+    let db_url = std::env::var("DATABASE_URL").expect("DATABASE_URL envvar is not set.");
+    let db_connection = Database::connect(db_url).await?;
+    // マイグレーション
+    Migrator::up(&db_connection, None).await?;
+
+    let job = InterestAccrualJob::new(
+        DbBankAccountRepository::new(db_connection.clone()),
+        db_connection,
+    );
+    let report = job.run(as_of).await?;
+
+    for event in report.accrued.iter() {
+        println!(
+            "accrued: account: {}, period: {}, interest: {}, balance: {}",
+            event.account_id.to_uuid(),
+            event.period,
+            event.interest,
+            event.balance
+        );
+    }
+    println!(
+        "accrued: {}, skipped: {}",
+        report.accrued.len(),
+        report.skipped.len()
+    );
+
+    Ok(())
+}
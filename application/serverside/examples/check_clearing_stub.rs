@@ -0,0 +1,47 @@
+//! 開発用の小切手決済サービスのスタブ．test_apiのCheckClearingServiceの接続先として起動する．
+//!
+//! 小切手番号が"9"で始まる場合は不渡りとし，それ以外は決済する．
+use infrastructure::check_clearing::{CheckClearingRequest, CheckClearingResult};
+
+use config::CONFIG;
+
+use axum::{routing::post, Json, Router};
+use tracing::{info, Level};
+use tracing_subscriber::FmtSubscriber;
+
+async fn clear_check(Json(request): Json<CheckClearingRequest>) -> Json<CheckClearingResult> {
+    info!(
+        "Clearing check {} for {}",
+        request.check_number, request.amount
+    );
+
+    if request.check_number.as_str().starts_with('9') {
+        Json(CheckClearingResult::Bounced {
+            reason: "Refer to drawer".to_string(),
+        })
+    } else {
+        Json(CheckClearingResult::Cleared)
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // トレーシング
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(Level::INFO)
+        .finish();
+
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    let app = Router::new().route("/checks/clear", post(clear_check));
+
+    let addr = CONFIG
+        .CHECK_CLEARING_URL
+        .trim_start_matches("http://")
+        .parse()?;
+    axum::Server::bind(&addr)
+        .serve(app.into_make_service())
+        .await?;
+
+    Ok(())
+}
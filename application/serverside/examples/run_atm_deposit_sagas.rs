@@ -0,0 +1,57 @@
+//! 口座に入金された現金をAtmにチャージする．中断したチャージも再び行う．
+//! test_apiでも定期的に実行しているが，test_apiを停止している間などにcron等から実行することを想定している．
+//!
+//! `cargo run --example run_atm_deposit_sagas` で実行する．
+use infrastructure::atm_repository_impls::DbAtmRepository;
+use infrastructure::saga_repository_impls::DbSagaRepository;
+use serverside::event_handlers::atm_event_handlers::AtmEventBus;
+use serverside::jobs::atm_deposit_saga_job::AtmDepositSagaJob;
+use serverside::sagas::atm_deposit_saga::AtmDepositSagaManager;
+use serverside::sagas::SagaStore;
+
+use event_bus::EventBus;
+use migration::{Migrator, MigratorTrait};
+
+use chrono::Utc;
+use sea_orm::Database;
+use std::sync::Arc;
+use tracing::Level;
+use tracing_subscriber::FmtSubscriber;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // トレーシング
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(Level::INFO)
+        .finish();
+
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    let db_url = std::env::var("DATABASE_URL").expect("DATABASE_URL envvar is not set.");
+    let db_connection = Database::connect(db_url).await?;
+    // マイグレーション
+    Migrator::up(&db_connection, None).await?;
+
+    // チャージにより現金は減らないため，残高不足の通知は購読しない
+    let job = AtmDepositSagaJob::new(AtmDepositSagaManager::new(
+        SagaStore::new(DbSagaRepository::new(db_connection.clone())),
+        DbAtmRepository::new(db_connection.clone()),
+        db_connection,
+        Arc::new(AtmEventBus::new(EventBus::new())),
+    ));
+    let report = job.run(Utc::now()).await?;
+
+    for saga_id in report.charged.iter() {
+        println!("charged: saga: {saga_id}");
+    }
+    for (saga_id, e) in report.failed.iter() {
+        println!("failed: saga: {saga_id}, error: {e}");
+    }
+    println!(
+        "charged: {}, failed: {}",
+        report.charged.len(),
+        report.failed.len()
+    );
+
+    Ok(())
+}
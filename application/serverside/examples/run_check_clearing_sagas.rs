@@ -0,0 +1,79 @@
+//! 小切手の決済のサガを進める．中断したサガの再開と，期限までに結果が得られないサガの確認待ちへの移行も行う．
+//! test_apiでも定期的に実行しているが，test_apiを停止している間などにcron等から実行することを想定している．
+//!
+//! `cargo run --example run_check_clearing_sagas` で実行する．
+use infrastructure::bank_account_repository_impls::DbBankAccountRepository;
+use infrastructure::check_clearing::{HttpCheckClearingService, HttpClientPolicy};
+use infrastructure::saga_repository_impls::DbSagaRepository;
+use infrastructure::used_check_number_repository_impls::DbUsedCheckNumberRepository;
+use serverside::command_handlers::bank_account_command_handlers;
+use serverside::jobs::check_clearing_saga_job::CheckClearingSagaJob;
+use serverside::sagas::check_clearing_saga::CheckClearingSagaManager;
+use serverside::sagas::SagaStore;
+
+use config::CONFIG;
+use migration::{Migrator, MigratorTrait};
+
+use chrono::Utc;
+use sea_orm::Database;
+use tracing::Level;
+use tracing_subscriber::FmtSubscriber;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // トレーシング
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(Level::INFO)
+        .finish();
+
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    let db_url = std::env::var("DATABASE_URL").expect("DATABASE_URL envvar is not set.");
+    let db_connection = Database::connect(db_url).await?;
+    // マイグレーション
+    Migrator::up(&db_connection, None).await?;
+
+    let bank_account_repo = DbBankAccountRepository::new(db_connection.clone());
+    let used_check_number_repo = DbUsedCheckNumberRepository::new(db_connection.clone());
+    let job = CheckClearingSagaJob::new(CheckClearingSagaManager::new(
+        SagaStore::new(DbSagaRepository::new(db_connection.clone())),
+        HttpCheckClearingService::new(CONFIG.CHECK_CLEARING_URL, HttpClientPolicy::default())?,
+        Box::new(
+            bank_account_command_handlers::ClearCheckCommandHandler::new(
+                bank_account_repo.clone(),
+                db_connection.clone(),
+                used_check_number_repo.clone(),
+            ),
+        ),
+        Box::new(
+            bank_account_command_handlers::RefundBouncedCheckCommandHandler::new(
+                bank_account_repo,
+                db_connection,
+                used_check_number_repo,
+            ),
+        ),
+    ));
+    let report = job.run(Utc::now()).await?;
+
+    for saga_id in report.cleared.iter() {
+        println!("cleared: saga: {saga_id}");
+    }
+    for saga_id in report.refunded.iter() {
+        println!("refunded: saga: {saga_id}");
+    }
+    for saga_id in report.suspended.iter() {
+        println!("suspended: saga: {saga_id}");
+    }
+    for (saga_id, e) in report.failed.iter() {
+        println!("failed: saga: {saga_id}, error: {e}");
+    }
+    println!(
+        "cleared: {}, refunded: {}, suspended: {}, failed: {}",
+        report.cleared.len(),
+        report.refunded.len(),
+        report.suspended.len(),
+        report.failed.len()
+    );
+
+    Ok(())
+}
@@ -1,24 +1,42 @@
+use common::read_models::ledger_entry;
 use domain::aggregates::{atm, bank_account};
+use domain::services::StaticRateConversionService;
+use domain::{Currency, Decimal};
 use infrastructure::{
-    atm_repository_impls::DbAtmRepository, bank_account_repository_impls::DbBankAccountRepository,
+    atm_repository_impls::DbAtmRepository,
+    bank_account_repository_impls::DbBankAccountRepository,
+    check_clearing::{HttpCheckClearingService, HttpClientPolicy},
+    mailer::{FileMailer, Mailer, SmtpMailer},
+    saga_repository_impls::DbSagaRepository,
+    used_check_number_repository_impls::DbUsedCheckNumberRepository,
+    withdrawal_event_repository_impls::DbWithdrawalEventRepository,
 };
 use serverside::api_handlers;
+use serverside::audit::RejectedCommandAuditor;
 use serverside::command_handlers::{atm_command_handlers, bank_account_command_handlers};
-use serverside::event_handlers::bank_account_event_handlers;
+use serverside::event_handlers::{atm_event_handlers, bank_account_event_handlers};
+use serverside::jobs::{
+    atm_deposit_saga_job::AtmDepositSagaJob, check_clearing_saga_job::CheckClearingSagaJob,
+};
 use serverside::query_handlers::QueryHandler;
+use serverside::sagas::atm_deposit_saga::AtmDepositSagaManager;
+use serverside::sagas::check_clearing_saga::CheckClearingSagaManager;
+use serverside::sagas::SagaStore;
 
 use config::CONFIG;
 use event_bus::event_bus_from_subscribes;
 use migration::{Migrator, MigratorTrait};
 
 use axum::{routing::post, Router};
+use chrono::Utc;
 use lru::LruCache;
 use sea_orm::Database;
 use sea_orm::JsonValue;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
 use tower_http::cors::{Any, CorsLayer};
-use tracing::Level;
+use tracing::{info, Level};
 use tracing_subscriber::FmtSubscriber;
 
 #[tokio::main]
@@ -38,6 +56,38 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // リポジトリ
     let bank_account_repo = DbBankAccountRepository::new(db_connection.clone());
     let atm_repo = DbAtmRepository::new(db_connection.clone());
+    let withdrawal_event_repo = DbWithdrawalEventRepository::new(db_connection.clone());
+    let used_check_number_repo = DbUsedCheckNumberRepository::new(db_connection.clone());
+    let saga_repo = DbSagaRepository::new(db_connection.clone());
+
+    // 通貨換算サービス(テスト用の固定レート)
+    let conversion_service = StaticRateConversionService::new()
+        .with_rate(Currency::USD, Currency::JPY, Decimal::new(150, 0))?
+        .with_rate(Currency::EUR, Currency::JPY, Decimal::new(160, 0))?
+        .with_rate(Currency::EUR, Currency::USD, Decimal::new(107, 2))?;
+
+    // メール送信．SMTP_ADDRが設定されている場合はSMTPで送信し，それ以外はファイルに書き出す
+    let mail_from = CONFIG.MAIL_FROM_ADDRESS.to_string().try_into()?;
+    let mailer: Arc<dyn Mailer> = match std::env::var("SMTP_ADDR") {
+        Ok(smtp_addr) => Arc::new(SmtpMailer::new(
+            smtp_addr,
+            mail_from,
+            Duration::from_secs(CONFIG.SMTP_TIMEOUT_SECS),
+        )),
+        Err(_) => Arc::new(FileMailer::new(CONFIG.MAIL_DIR, mail_from)),
+    };
+
+    // 小切手決済サービス(examples/check_clearing_stub.rsで起動できる)
+    let check_clearing_service =
+        HttpCheckClearingService::new(CONFIG.CHECK_CLEARING_URL, HttpClientPolicy::default())?;
+
+    // Atmのイベントバス．Atmのコマンドハンドラと預金・引き出しのイベントハンドラで共有する
+    let atm_event_bus = Arc::new(atm_event_handlers::AtmEventBus::new(
+        event_bus_from_subscribes![atm_event_handlers::SendAtmCashLowAlertHandler::new(
+            mailer.clone(),
+            CONFIG.ATM_OPERATIONS_MAIL_ADDRESS.to_string().try_into()?
+        )],
+    ));
 
     // コマンドハンドラ
     let bank_account_command_handler = bank_account_command_handlers::BankAccountCommandHandler {
@@ -45,6 +95,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             bank_account_command_handlers::DepositMoneyCommandHandler::new(
                 bank_account_repo.clone(),
                 db_connection.clone(),
+                conversion_service.clone(),
+                atm_repo.clone(),
+                SagaStore::new(saga_repo.clone()),
             ),
         ),
         open_account_handler: Box::new(
@@ -57,32 +110,110 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             bank_account_command_handlers::WithdrawMoneyCommandHandler::new(
                 bank_account_repo.clone(),
                 db_connection.clone(),
+                withdrawal_event_repo,
+                atm_repo.clone(),
+                atm_event_bus.clone(),
             ),
         ),
         write_check_handler: Box::new(
             bank_account_command_handlers::WriteCheckCommandHandler::new(
                 bank_account_repo.clone(),
                 db_connection.clone(),
+                used_check_number_repo.clone(),
+                SagaStore::new(saga_repo.clone()),
+            ),
+        ),
+        transfer_money_handler: Box::new(
+            bank_account_command_handlers::TransferMoneyCommandHandler::new(
+                bank_account_repo.clone(),
+                db_connection.clone(),
+                conversion_service,
+            ),
+        ),
+        freeze_account_handler: Box::new(
+            bank_account_command_handlers::FreezeAccountCommandHandler::new(
+                bank_account_repo.clone(),
+                db_connection.clone(),
+            ),
+        ),
+        unfreeze_account_handler: Box::new(
+            bank_account_command_handlers::UnfreezeAccountCommandHandler::new(
+                bank_account_repo.clone(),
+                db_connection.clone(),
+            ),
+        ),
+        close_account_handler: Box::new(
+            bank_account_command_handlers::CloseAccountCommandHandler::new(
+                bank_account_repo.clone(),
+                db_connection.clone(),
+            ),
+        ),
+        set_overdraft_limit_handler: Box::new(
+            bank_account_command_handlers::SetOverdraftLimitCommandHandler::new(
+                bank_account_repo.clone(),
+                db_connection.clone(),
+            ),
+        ),
+        add_owner_handler: Box::new(bank_account_command_handlers::AddOwnerCommandHandler::new(
+            bank_account_repo.clone(),
+            db_connection.clone(),
+        )),
+        remove_owner_handler: Box::new(
+            bank_account_command_handlers::RemoveOwnerCommandHandler::new(
+                bank_account_repo.clone(),
+                db_connection.clone(),
+            ),
+        ),
+        request_email_change_handler: Box::new(
+            bank_account_command_handlers::RequestEmailChangeCommandHandler::new(
+                bank_account_repo.clone(),
+                db_connection.clone(),
+            ),
+        ),
+        confirm_email_change_handler: Box::new(
+            bank_account_command_handlers::ConfirmEmailChangeCommandHandler::new(
+                bank_account_repo.clone(),
+                db_connection.clone(),
             ),
         ),
         event_bus: bank_account_event_handlers::BankAccountEventBus::new(
             event_bus_from_subscribes![
-                bank_account_event_handlers::SendOpenAccountMailHandler::new(),
-                bank_account_event_handlers::AtmDepositHandler::new(
-                    atm_repo.clone(),
-                    db_connection.clone()
-                ),
-                bank_account_event_handlers::AtmWithdrawHandler::new(
-                    atm_repo.clone(),
-                    db_connection.clone()
-                ),
-                bank_account_event_handlers::ExternalWroteCheckHandler::new()
+                bank_account_event_handlers::SendOpenAccountMailHandler::new(mailer.clone()),
+                bank_account_event_handlers::SendEmailChangeMailHandler::new(mailer.clone()),
+                bank_account_event_handlers::SendLargeWithdrawalAlertHandler::new(
+                    bank_account_repo.clone(),
+                    mailer.clone()
+                )
             ],
         ),
         command_id_cache: Mutex::new(LruCache::new(10.try_into().unwrap())),
+        rejected_command_auditor: RejectedCommandAuditor::new(db_connection.clone()),
     };
 
     let atm_command_handler = atm_command_handlers::AtmCommandHandler {
+        charge_cash_command_handler: Box::new(
+            atm_command_handlers::ChargeAtmCashCommandHandler::new(
+                atm_repo.clone(),
+                db_connection.clone(),
+            ),
+        ),
+        decommission_command_handler: Box::new(
+            atm_command_handlers::DecommissionAtmCommandHandler::new(
+                atm_repo.clone(),
+                db_connection.clone(),
+            ),
+        ),
+        relocate_command_handler: Box::new(atm_command_handlers::RelocateAtmCommandHandler::new(
+            atm_repo.clone(),
+            db_connection.clone(),
+        )),
+        set_out_of_service_command_handler: Box::new(
+            atm_command_handlers::SetAtmOutOfServiceCommandHandler::new(
+                atm_repo.clone(),
+                db_connection.clone(),
+            ),
+        ),
+        event_bus: atm_event_bus.clone(),
         register_command_handler: Box::new(atm_command_handlers::RegisterAtmCommandHandler::new(
             atm_repo.clone(),
             db_connection.clone(),
@@ -90,13 +221,61 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         command_id_cache: Mutex::new(LruCache::new(10.try_into().unwrap())),
     };
 
+    // サガを進めるジョブ．入金・小切手の発行のコマンドがトランザクション内で開始したサガを定期的に進める
+    let atm_deposit_saga_job = AtmDepositSagaJob::new(AtmDepositSagaManager::new(
+        SagaStore::new(saga_repo.clone()),
+        atm_repo.clone(),
+        db_connection.clone(),
+        atm_event_bus.clone(),
+    ));
+    let check_clearing_saga_job = CheckClearingSagaJob::new(CheckClearingSagaManager::new(
+        SagaStore::new(saga_repo.clone()),
+        check_clearing_service,
+        Box::new(
+            bank_account_command_handlers::ClearCheckCommandHandler::new(
+                bank_account_repo.clone(),
+                db_connection.clone(),
+                used_check_number_repo.clone(),
+            ),
+        ),
+        Box::new(
+            bank_account_command_handlers::RefundBouncedCheckCommandHandler::new(
+                bank_account_repo.clone(),
+                db_connection.clone(),
+                used_check_number_repo.clone(),
+            ),
+        ),
+    ));
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(Duration::from_millis(CONFIG.SAGA_JOB_INTERVAL_MILLIS));
+        loop {
+            interval.tick().await;
+            // 失敗したサガはジョブの中で記録されるため，ここではサガを取得できない場合のみ記録する
+            if let Err(e) = atm_deposit_saga_job.run(Utc::now()).await {
+                info!("Failed to run AtmDepositSagaJob: {e}");
+            }
+            if let Err(e) = check_clearing_saga_job.run(Utc::now()).await {
+                info!("Failed to run CheckClearingSagaJob: {e}");
+            }
+        }
+    });
+
     // クエリハンドラ
     let bank_account_query_handler = Arc::new(QueryHandler::<bank_account::orm::Model>::new(
         db_connection.clone(),
     ));
 
+    let bank_account_owner_query_handler = Arc::new(
+        QueryHandler::<bank_account::owner_orm::Model>::new(db_connection.clone()),
+    );
+
     let atm_query_handler = Arc::new(QueryHandler::<atm::orm::Model>::new(db_connection.clone()));
 
+    let ledger_entry_query_handler = Arc::new(QueryHandler::<ledger_entry::Model>::new(
+        db_connection.clone(),
+    ));
+
     let custom_query_handler = Arc::new(QueryHandler::<JsonValue>::new(db_connection.clone()));
 
     // axumのルーター
@@ -132,6 +311,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 post(api_handlers::query_one_api_handler::<atm::orm::Model>),
             )
             .with_state(Arc::clone(&atm_query_handler))
+            .route(
+                "/ledger_entry",
+                post(api_handlers::query_one_api_handler::<ledger_entry::Model>),
+            )
+            .with_state(Arc::clone(&ledger_entry_query_handler))
             .route(
                 "/custom",
                 post(api_handlers::query_one_api_handler::<JsonValue>),
@@ -147,11 +331,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 post(api_handlers::query_all_api_handler::<bank_account::orm::Model>),
             )
             .with_state(Arc::clone(&bank_account_query_handler))
+            .route(
+                "/bank_account_owner",
+                post(api_handlers::query_all_api_handler::<bank_account::owner_orm::Model>),
+            )
+            .with_state(Arc::clone(&bank_account_owner_query_handler))
             .route(
                 "/atm",
                 post(api_handlers::query_all_api_handler::<atm::orm::Model>),
             )
             .with_state(Arc::clone(&atm_query_handler))
+            .route(
+                "/ledger_entry",
+                post(api_handlers::query_all_api_handler::<ledger_entry::Model>),
+            )
+            .with_state(Arc::clone(&ledger_entry_query_handler))
             .route(
                 "/custom",
                 post(api_handlers::query_all_api_handler::<JsonValue>),
@@ -159,6 +353,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .with_state(Arc::clone(&custom_query_handler)),
     );
 
+    let query_stream_router: Router<()> = Router::new().nest(
+        "/query_stream",
+        Router::new()
+            .route(
+                "/bank_account",
+                post(api_handlers::query_stream_api_handler::<bank_account::orm::Model>),
+            )
+            .with_state(Arc::clone(&bank_account_query_handler))
+            .route(
+                "/atm",
+                post(api_handlers::query_stream_api_handler::<atm::orm::Model>),
+            )
+            .with_state(Arc::clone(&atm_query_handler))
+            .route(
+                "/custom",
+                post(api_handlers::query_stream_api_handler::<JsonValue>),
+            )
+            .with_state(Arc::clone(&custom_query_handler)),
+    );
+
     let cors_layer = CorsLayer::new()
         .allow_methods(Any)
         .allow_headers(Any)
@@ -168,6 +382,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .merge(command_router)
         .merge(query_one_router)
         .merge(query_all_router)
+        .merge(query_stream_router)
         .layer(cors_layer);
 
     println!("server started: http://{}", CONFIG.TEST_API_ADDR);
@@ -1,14 +1,19 @@
 use crate::command_handlers::ApiHandleCommand;
 use crate::query_handlers::QueryHandler;
 use common::query_statement::QueryStatement;
-use common::ApplicationError;
+use common::{ApplicationError, ProblemDetails};
 
 use axum::{
+    body::StreamBody,
     extract::rejection::JsonRejection,
     extract::{Json, State},
+    http::header,
+    response::IntoResponse,
 };
+use futures::StreamExt;
 use sea_orm::FromQueryResult;
 use serde::Serialize;
+use std::convert::Infallible;
 use std::sync::Arc;
 
 // -------------------------------------------------------------------------------------------------
@@ -48,3 +53,36 @@ pub async fn query_all_api_handler<T: FromQueryResult + Serialize>(
     let res = query_handler.handle_query_all(query).await?;
     Ok(Json(res))
 }
+
+/// ジェネリックなクエリ(stream)に対するaxumハンドラ．結果を一行ずつ`Result<T, ProblemDetails>`のNDJSONとしてチャンク転送する．
+pub async fn query_stream_api_handler<T: FromQueryResult + Serialize + Send + 'static>(
+    State(query_handler): State<Arc<QueryHandler<T>>>,
+    query_res: Result<Json<QueryStatement>, JsonRejection>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let query = query_res?.0;
+
+    let mut stream = Box::pin(query_handler.handle_query_stream(query));
+
+    // 最初の要素がエラーの場合(クエリ自体の失敗など)はステータスコードでエラーを返す
+    let first = match stream.next().await {
+        Some(Err(e)) => return Err(e),
+        first => first,
+    };
+
+    let lines = futures::stream::iter(first).chain(stream).map(|res| {
+        // 途中のエラーもステータスコードによるエラーと同じproblem detailsとする
+        let res = res.map_err(|e| ProblemDetails::from(&e));
+        let mut line = serde_json::to_vec(&res).unwrap_or_else(|e| {
+            let error = ApplicationError::SerdeError(e.to_string());
+            serde_json::to_vec(&Err::<(), _>(ProblemDetails::from(&error))).unwrap()
+            // ProblemDetailsのシリアライズは失敗しない
+        });
+        line.push(b'\n');
+        Ok::<_, Infallible>(line)
+    });
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        StreamBody::new(lines),
+    ))
+}
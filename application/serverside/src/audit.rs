@@ -0,0 +1,64 @@
+use common::commands::CommandId;
+use common::read_models::rejected_command::{self, RejectedCommandId};
+use common::ApplicationError;
+use infrastructure::InfraError;
+
+use sea_orm::prelude::Uuid;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, IntoActiveModel};
+
+/// 拒否されたコマンドの監査ログの保存先
+#[async_trait::async_trait]
+pub trait RejectedCommandSink: Send + Sync {
+    async fn insert(&self, model: rejected_command::Model) -> Result<(), ApplicationError>;
+}
+
+#[async_trait::async_trait]
+impl RejectedCommandSink for DatabaseConnection {
+    async fn insert(&self, model: rejected_command::Model) -> Result<(), ApplicationError> {
+        model
+            .into_active_model()
+            .insert(self)
+            .await
+            .map_err(Into::<InfraError>::into)?;
+
+        Ok(())
+    }
+}
+
+/// ドメインのルールにより拒否されたコマンドを監査ログとして記録する
+pub struct RejectedCommandAuditor {
+    sink: Box<dyn RejectedCommandSink>,
+}
+
+impl RejectedCommandAuditor {
+    /// データベースに記録する
+    pub fn new(conn: DatabaseConnection) -> Self {
+        Self::with_sink(conn)
+    }
+    /// 任意の保存先に記録する
+    pub fn with_sink(sink: impl RejectedCommandSink + 'static) -> Self {
+        Self {
+            sink: Box::new(sink),
+        }
+    }
+    /// 拒否されたコマンドを記録する
+    pub async fn record(
+        &self,
+        command_id: CommandId,
+        command_name: &str,
+        aggregate_id: Option<Uuid>,
+        error: &ApplicationError,
+    ) -> Result<(), ApplicationError> {
+        self.sink
+            .insert(rejected_command::Model {
+                id: RejectedCommandId::generate(),
+                command_id,
+                command_name: command_name.to_string(),
+                aggregate_id,
+                error_code: error.code().to_string(),
+                detail: error.to_string().trim().to_string(),
+                rejected_at: chrono::Utc::now(),
+            })
+            .await
+    }
+}
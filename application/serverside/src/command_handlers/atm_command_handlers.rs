@@ -1,17 +1,22 @@
 use super::ApiHandleCommand;
+use crate::event_handlers::atm_event_handlers::AtmEventBus;
 use ddd_cqrs_core::{Aggregate, HandleCommand};
 
 use common::commands::atm_commands::AtmCommand;
-use common::commands::atm_commands::RegisterAtmCommand;
+use common::commands::atm_commands::{
+    ChargeAtmCashCommand, DecommissionAtmCommand, RegisterAtmCommand, RelocateAtmCommand,
+    SetAtmOutOfServiceCommand,
+};
 use common::commands::CommandId;
 use common::ApplicationError;
+use domain::aggregates::atm::Cassettes;
 use domain::aggregates::Atm;
 use domain::repositories::{AtmRepository, Transaction};
 use infrastructure::InfraError;
 
 use derive_new::new;
 use lru::LruCache;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 // -------------------------------------------------------------------------------------------------
 // RegisterAtmCommandHandler
@@ -36,10 +41,11 @@ impl<R: AtmRepository<Error = InfraError>> HandleCommand for RegisterAtmCommandH
 
         let RegisterAtmCommand {
             location,
-            total_cash,
+            currency,
+            notes,
         } = command;
 
-        let mut atm = Atm::from_domains(location, total_cash);
+        let mut atm = Atm::from_domains(location, Cassettes::new(currency, notes)?)?;
         let events = atm.domain_events_mut().take();
         self.repo.save(atm, Some(&transaction)).await?;
 
@@ -49,6 +55,165 @@ impl<R: AtmRepository<Error = InfraError>> HandleCommand for RegisterAtmCommandH
     }
 }
 
+// -------------------------------------------------------------------------------------------------
+// ChargeAtmCashCommandHandler
+
+/// Atmに現金をチャージするコマンドハンドラ
+#[derive(new)]
+pub struct ChargeAtmCashCommandHandler<R: AtmRepository<Error = InfraError>> {
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+}
+
+#[async_trait::async_trait]
+impl<R: AtmRepository<Error = InfraError>> HandleCommand for ChargeAtmCashCommandHandler<R> {
+    type Aggregate = Atm;
+    type Command = ChargeAtmCashCommand;
+    type Error = ApplicationError;
+
+    async fn handle_command(
+        &self,
+        command: Self::Command,
+    ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
+        let transaction = <R::Transaction as Transaction>::begin(&self.pool).await?;
+
+        let ChargeAtmCashCommand { atm_id, notes } = command;
+
+        let mut atm = self
+            .repo
+            .find_by_id_for_update(atm_id, &transaction)
+            .await?;
+        atm.charge_cash(notes)?;
+
+        let events = atm.domain_events_mut().take();
+        self.repo.edit(atm, Some(&transaction)).await?;
+
+        transaction.commit().await?;
+
+        Ok(events)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// DecommissionAtmCommandHandler
+
+/// Atmを撤去するコマンドハンドラ
+#[derive(new)]
+pub struct DecommissionAtmCommandHandler<R: AtmRepository<Error = InfraError>> {
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+}
+
+#[async_trait::async_trait]
+impl<R: AtmRepository<Error = InfraError>> HandleCommand for DecommissionAtmCommandHandler<R> {
+    type Aggregate = Atm;
+    type Command = DecommissionAtmCommand;
+    type Error = ApplicationError;
+
+    async fn handle_command(
+        &self,
+        command: Self::Command,
+    ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
+        let transaction = <R::Transaction as Transaction>::begin(&self.pool).await?;
+
+        let DecommissionAtmCommand { atm_id } = command;
+
+        let mut atm = self
+            .repo
+            .find_by_id_for_update(atm_id, &transaction)
+            .await?;
+        atm.decommission()?;
+
+        let events = atm.domain_events_mut().take();
+        self.repo.edit(atm, Some(&transaction)).await?;
+
+        transaction.commit().await?;
+
+        Ok(events)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// RelocateAtmCommandHandler
+
+/// Atmを移設するコマンドハンドラ
+#[derive(new)]
+pub struct RelocateAtmCommandHandler<R: AtmRepository<Error = InfraError>> {
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+}
+
+#[async_trait::async_trait]
+impl<R: AtmRepository<Error = InfraError>> HandleCommand for RelocateAtmCommandHandler<R> {
+    type Aggregate = Atm;
+    type Command = RelocateAtmCommand;
+    type Error = ApplicationError;
+
+    async fn handle_command(
+        &self,
+        command: Self::Command,
+    ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
+        let transaction = <R::Transaction as Transaction>::begin(&self.pool).await?;
+
+        let RelocateAtmCommand { atm_id, location } = command;
+
+        let mut atm = self
+            .repo
+            .find_by_id_for_update(atm_id, &transaction)
+            .await?;
+        atm.relocate(location)?;
+
+        let events = atm.domain_events_mut().take();
+        self.repo.edit(atm, Some(&transaction)).await?;
+
+        transaction.commit().await?;
+
+        Ok(events)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// SetAtmOutOfServiceCommandHandler
+
+/// Atmの停止・再開を行うコマンドハンドラ
+#[derive(new)]
+pub struct SetAtmOutOfServiceCommandHandler<R: AtmRepository<Error = InfraError>> {
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+}
+
+#[async_trait::async_trait]
+impl<R: AtmRepository<Error = InfraError>> HandleCommand for SetAtmOutOfServiceCommandHandler<R> {
+    type Aggregate = Atm;
+    type Command = SetAtmOutOfServiceCommand;
+    type Error = ApplicationError;
+
+    async fn handle_command(
+        &self,
+        command: Self::Command,
+    ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
+        let transaction = <R::Transaction as Transaction>::begin(&self.pool).await?;
+
+        let SetAtmOutOfServiceCommand {
+            atm_id,
+            out_of_service,
+        } = command;
+
+        let mut atm = self
+            .repo
+            .find_by_id_for_update(atm_id, &transaction)
+            .await?;
+        atm.set_out_of_service(out_of_service)?;
+
+        let events = atm.domain_events_mut().take();
+        self.repo.edit(atm, Some(&transaction)).await?;
+
+        transaction.commit().await?;
+
+        Ok(events)
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 // AtmCommandHandler
 
@@ -57,6 +222,31 @@ pub struct AtmCommandHandler {
     pub register_command_handler: Box<
         dyn HandleCommand<Command = RegisterAtmCommand, Aggregate = Atm, Error = ApplicationError>,
     >,
+    pub charge_cash_command_handler: Box<
+        dyn HandleCommand<
+            Command = ChargeAtmCashCommand,
+            Aggregate = Atm,
+            Error = ApplicationError,
+        >,
+    >,
+    pub decommission_command_handler: Box<
+        dyn HandleCommand<
+            Command = DecommissionAtmCommand,
+            Aggregate = Atm,
+            Error = ApplicationError,
+        >,
+    >,
+    pub relocate_command_handler: Box<
+        dyn HandleCommand<Command = RelocateAtmCommand, Aggregate = Atm, Error = ApplicationError>,
+    >,
+    pub set_out_of_service_command_handler: Box<
+        dyn HandleCommand<
+            Command = SetAtmOutOfServiceCommand,
+            Aggregate = Atm,
+            Error = ApplicationError,
+        >,
+    >,
+    pub event_bus: Arc<AtmEventBus>,
     pub command_id_cache: Mutex<LruCache<CommandId, ()>>,
 }
 
@@ -77,20 +267,170 @@ impl ApiHandleCommand for AtmCommandHandler {
     type Command = AtmCommand;
 
     async fn handle_command(&self, command: Self::Command) -> Result<(), ApplicationError> {
-        let _ = match command {
+        let command_id = command.command_id();
+
+        let res = match command {
             AtmCommand::RegisterAtmCommand(cmd, id) => {
                 if !self.check_command_duplicate(id) {
-                    self.register_command_handler.handle_command(cmd).await?
+                    self.register_command_handler.handle_command(cmd).await
                 } else {
                     if self.register_command_handler.allow_duplicate() {
-                        self.register_command_handler.handle_command(cmd).await?
+                        self.register_command_handler.handle_command(cmd).await
+                    } else {
+                        Ok(Vec::new())
+                    }
+                }
+            }
+            AtmCommand::ChargeAtmCashCommand(cmd, id) => {
+                if !self.check_command_duplicate(id) {
+                    self.charge_cash_command_handler.handle_command(cmd).await
+                } else {
+                    if self.charge_cash_command_handler.allow_duplicate() {
+                        self.charge_cash_command_handler.handle_command(cmd).await
+                    } else {
+                        Ok(Vec::new())
+                    }
+                }
+            }
+            AtmCommand::DecommissionAtmCommand(cmd, id) => {
+                if !self.check_command_duplicate(id) {
+                    self.decommission_command_handler.handle_command(cmd).await
+                } else {
+                    if self.decommission_command_handler.allow_duplicate() {
+                        self.decommission_command_handler.handle_command(cmd).await
                     } else {
-                        Vec::new()
+                        Ok(Vec::new())
+                    }
+                }
+            }
+            AtmCommand::RelocateAtmCommand(cmd, id) => {
+                if !self.check_command_duplicate(id) {
+                    self.relocate_command_handler.handle_command(cmd).await
+                } else {
+                    if self.relocate_command_handler.allow_duplicate() {
+                        self.relocate_command_handler.handle_command(cmd).await
+                    } else {
+                        Ok(Vec::new())
+                    }
+                }
+            }
+            AtmCommand::SetAtmOutOfServiceCommand(cmd, id) => {
+                if !self.check_command_duplicate(id) {
+                    self.set_out_of_service_command_handler
+                        .handle_command(cmd)
+                        .await
+                } else {
+                    if self.set_out_of_service_command_handler.allow_duplicate() {
+                        self.set_out_of_service_command_handler
+                            .handle_command(cmd)
+                            .await
+                    } else {
+                        Ok(Vec::new())
                     }
                 }
             }
         };
 
+        if res.is_err() {
+            // 失敗したコマンドはリトライできるように重複判定のキャッシュから取り除く
+            self.command_id_cache.lock().unwrap().pop(&command_id);
+        }
+        let events = res?;
+
+        // イベントのディスパッチ
+        for event in events.into_iter() {
+            self.event_bus.dispatch_event(event);
+        }
+
         Ok(())
     }
 }
+
+// -------------------------------------------------------------------------------------------------
+// test
+
+#[cfg(test)]
+mod test {
+    use super::AtmCommandHandler;
+    use crate::command_handlers::ApiHandleCommand;
+    use crate::event_handlers::atm_event_handlers::AtmEventBus;
+    use common::commands::atm_commands::{AtmCommand, SetAtmOutOfServiceCommand};
+    use common::commands::CommandId;
+    use common::ApplicationError;
+    use domain::aggregates::atm::AtmId;
+    use domain::aggregates::Atm;
+    use domain::events::atm_events::AtmEvent;
+
+    use ddd_cqrs_core::HandleCommand;
+    use event_bus::EventBus;
+
+    use lru::LruCache;
+    use std::marker::PhantomData;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// 決まった結果を返し，呼ばれた回数を数えるコマンドハンドラ
+    struct StubHandler<C> {
+        result: Result<Vec<AtmEvent>, ApplicationError>,
+        calls: Arc<AtomicUsize>,
+        command_type: PhantomData<fn(C)>,
+    }
+
+    impl<C> StubHandler<C> {
+        fn boxed(
+            result: Result<Vec<AtmEvent>, ApplicationError>,
+            calls: Arc<AtomicUsize>,
+        ) -> Box<Self> {
+            Box::new(Self {
+                result,
+                calls,
+                command_type: PhantomData,
+            })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl<C: Send + 'static> HandleCommand for StubHandler<C> {
+        type Command = C;
+        type Aggregate = Atm;
+        type Error = ApplicationError;
+
+        async fn handle_command(&self, _: Self::Command) -> Result<Vec<AtmEvent>, Self::Error> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.result.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_command_is_retryable() {
+        fn ok<C>() -> Box<StubHandler<C>> {
+            StubHandler::boxed(Ok(Vec::new()), Arc::default())
+        }
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let error = ApplicationError::OtherInfraError("Connection refused.".to_string());
+        let handler = AtmCommandHandler {
+            register_command_handler: ok(),
+            charge_cash_command_handler: ok(),
+            decommission_command_handler: ok(),
+            relocate_command_handler: ok(),
+            set_out_of_service_command_handler: StubHandler::boxed(Err(error), calls.clone()),
+            event_bus: Arc::new(AtmEventBus::new(EventBus::new())),
+            command_id_cache: Mutex::new(LruCache::new(10.try_into().unwrap())),
+        };
+
+        // 失敗したコマンドは重複とみなさず再実行する
+        let id = CommandId::generate();
+        for _ in 0..2 {
+            let command = AtmCommand::SetAtmOutOfServiceCommand(
+                SetAtmOutOfServiceCommand {
+                    atm_id: AtmId::generate(),
+                    out_of_service: true,
+                },
+                id,
+            );
+            assert!(handler.handle_command(command).await.is_err());
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
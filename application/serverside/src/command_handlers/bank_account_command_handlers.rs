@@ -1,34 +1,621 @@
 use super::ApiHandleCommand;
+use crate::audit::RejectedCommandAuditor;
+use crate::event_handlers::atm_event_handlers::AtmEventBus;
 use crate::event_handlers::bank_account_event_handlers::BankAccountEventBus;
+use crate::ledger::LedgerTransaction;
+use crate::sagas::atm_deposit_saga::AtmDepositSaga;
+use crate::sagas::check_clearing_saga::CheckClearingSaga;
+use crate::sagas::SagaStore;
 
+use config::CONFIG;
 use ddd_cqrs_core::{Aggregate, HandleCommand};
 
 use common::commands::bank_account_commands::BankAccountCommand;
 use common::commands::bank_account_commands::{
-    DepositMoneyCommand, OpenAccountCommand, WithdrawMoneyCommand, WriteCheckCommand,
+    AddOwnerCommand, ClearCheckCommand, CloseAccountCommand, ConfirmEmailChangeCommand,
+    DepositMoneyCommand, FreezeAccountCommand, OpenAccountCommand, RefundBouncedCheckCommand,
+    RemoveOwnerCommand, RequestEmailChangeCommand, SetOverdraftLimitCommand, TransferMoneyCommand,
+    UnfreezeAccountCommand, WithdrawMoneyCommand, WriteCheckCommand,
 };
 use common::commands::CommandId;
 use common::ApplicationError;
+use domain::aggregates::atm::NoteBreakdown;
+use domain::aggregates::bank_account::{AccountOwner, CheckSettlement};
 use domain::aggregates::BankAccount;
-use domain::repositories::{BankAccountRepository, Transaction};
+use domain::repositories::{
+    AtmRepository, BankAccountRepository, SagaRepository, Transaction, UsedCheckNumberRepository,
+    WithdrawalEventRepository,
+};
+use domain::services::CurrencyConversionService;
+use domain::DomainError;
 use infrastructure::InfraError;
 
-use derive_new::new;
-use lru::LruCache;
-use std::sync::Mutex;
+use chrono::{Duration, Utc};
+use derive_new::new;
+use lru::LruCache;
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+// -------------------------------------------------------------------------------------------------
+// OpenAccountCommandHandler
+
+#[derive(new)]
+pub struct OpenAccountCommandHandler<R: BankAccountRepository<Error = InfraError>> {
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+}
+
+#[async_trait::async_trait]
+impl<R: BankAccountRepository<Error = InfraError>> HandleCommand for OpenAccountCommandHandler<R> {
+    type Command = OpenAccountCommand;
+    type Aggregate = BankAccount;
+    type Error = ApplicationError;
+
+    async fn handle_command(
+        &self,
+        command: Self::Command,
+    ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
+        let transaction = R::Transaction::begin(&self.pool).await?;
+
+        let OpenAccountCommand {
+            account_name,
+            email_address,
+            currency,
+            tier,
+            account_type,
+        } = command;
+
+        let mut bank_account =
+            BankAccount::from_domains(email_address, account_name, currency, tier, account_type);
+        bank_account.open_account()?;
+
+        let events = bank_account.domain_events_mut().take();
+
+        self.repo.save(bank_account, Some(&transaction)).await?;
+
+        transaction.commit().await?;
+
+        Ok(events)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// DepositMoneyCommandHandler
+
+#[derive(new)]
+pub struct DepositMoneyCommandHandler<
+    R: BankAccountRepository<Error = InfraError>,
+    C: CurrencyConversionService,
+    A: AtmRepository<Error = InfraError, Transaction = R::Transaction>,
+    SR: SagaRepository<Error = InfraError, Transaction = R::Transaction>,
+> {
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+    conversion_service: C,
+    atm_repo: A,
+    saga_store: SagaStore<AtmDepositSaga, SR>,
+}
+
+#[async_trait::async_trait]
+impl<R, C, A, SR> HandleCommand for DepositMoneyCommandHandler<R, C, A, SR>
+where
+    R: BankAccountRepository<Error = InfraError>,
+    R::Transaction: LedgerTransaction,
+    C: CurrencyConversionService,
+    A: AtmRepository<Error = InfraError, Transaction = R::Transaction>,
+    SR: SagaRepository<Error = InfraError, Transaction = R::Transaction>,
+{
+    type Command = DepositMoneyCommand;
+    type Aggregate = BankAccount;
+    type Error = ApplicationError;
+
+    async fn handle_command(
+        &self,
+        command: Self::Command,
+    ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
+        use domain::events::bank_account_events::CustomerDepositedMoneyEvent;
+
+        let transaction = R::Transaction::begin(&self.pool).await?;
+
+        let DepositMoneyCommand {
+            account_id,
+            amount,
+            atm_id,
+        } = command;
+
+        // 停止中・撤去済みのAtmでは預け入れできない．Atmの通貨以外や紙幣で表せない金額も預け入れできない
+        let atm = self.atm_repo.find_by_id(atm_id, Some(&transaction)).await?;
+        atm.ensure_operational()?;
+        atm.ensure_accepts(amount)?;
+        let notes = NoteBreakdown::from_amount(amount)?;
+
+        // 編集した行全体を書き戻すため，同時に実行される他の取引の変更を上書きしないようにロックする
+        let mut bank_account = self
+            .repo
+            .find_by_id_for_update(account_id, &transaction)
+            .await?;
+        let (converted_amount, applied_rate) = self
+            .conversion_service
+            .convert(amount, bank_account.currency())
+            .await?;
+        bank_account.deposit_money(converted_amount)?;
+
+        let balance = bank_account.balance();
+        bank_account.domain_events_mut().push(
+            CustomerDepositedMoneyEvent {
+                account_id,
+                amount,
+                balance,
+                atm_id,
+                applied_rate,
+            }
+            .into(),
+        );
+
+        let events = bank_account.domain_events_mut().take();
+        self.repo.edit(bank_account, Some(&transaction)).await?;
+
+        // 入金された現金は額面の大きい紙幣から順にカセットに入れる．チャージはサガを進めるジョブが行う
+        self.saga_store
+            .start(
+                AtmDepositSaga::new(account_id, atm_id, notes),
+                Utc::now(),
+                None,
+                Some(&transaction),
+            )
+            .await?;
+
+        transaction.record_ledger(&events).await?;
+        transaction.commit().await?;
+
+        Ok(events)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// WithdrawMoneyCommand
+
+#[derive(new)]
+pub struct WithdrawMoneyCommandHandler<R, W, A>
+where
+    R: BankAccountRepository<Error = InfraError>,
+    W: WithdrawalEventRepository<Error = InfraError, Transaction = R::Transaction>,
+    A: AtmRepository<Error = InfraError, Transaction = R::Transaction>,
+{
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+    withdrawal_repo: W,
+    atm_repo: A,
+    atm_event_bus: Arc<AtmEventBus>,
+}
+
+#[async_trait::async_trait]
+impl<R, W, A> HandleCommand for WithdrawMoneyCommandHandler<R, W, A>
+where
+    R: BankAccountRepository<Error = InfraError>,
+    R::Transaction: LedgerTransaction,
+    W: WithdrawalEventRepository<Error = InfraError, Transaction = R::Transaction>,
+    A: AtmRepository<Error = InfraError, Transaction = R::Transaction>,
+{
+    type Command = WithdrawMoneyCommand;
+    type Aggregate = BankAccount;
+    type Error = ApplicationError;
+
+    async fn handle_command(
+        &self,
+        command: Self::Command,
+    ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
+        use domain::aggregates::bank_account::{daily_withdrawal_window, DailyWithdrawals};
+        use domain::events::bank_account_events::CustomerWithdrewCashEvent;
+
+        let transaction = R::Transaction::begin(&self.pool).await?;
+
+        let WithdrawMoneyCommand {
+            account_id,
+            amount,
+            atm_id,
+        } = command;
+
+        // 同じアカウントからの引き出しが同時に集計されないようにロックする
+        let mut bank_account = self
+            .repo
+            .find_by_id_for_update(account_id, &transaction)
+            .await?;
+        // 同じAtmでの引き出しの集計と現金の確保が同時に行われないようにロックする
+        let mut atm = self
+            .atm_repo
+            .find_by_id_for_update(atm_id, &transaction)
+            .await?;
+
+        let withdrawn_at = Utc::now();
+        let since = withdrawn_at - daily_withdrawal_window();
+        let daily_withdrawals = DailyWithdrawals::from_totals(
+            self.withdrawal_repo
+                .total_by_account(account_id, since, Some(&transaction))
+                .await?,
+            self.withdrawal_repo
+                .total_by_atm(atm_id, since, Some(&transaction))
+                .await?,
+            self.withdrawal_repo
+                .count_by_account(account_id, since, Some(&transaction))
+                .await?,
+            amount.currency(),
+        )?;
+
+        bank_account.withdraw_money(amount, daily_withdrawals)?;
+        // 口座から引き落とせる場合のみ現金を確保する．
+        // 停止中・現金不足・払い出せない金額の場合はここで失敗し，口座は変更されない
+        atm.withdraw(amount)?;
+
+        let balance = bank_account.balance();
+        let event = CustomerWithdrewCashEvent {
+            account_id,
+            amount,
+            balance,
+            atm_id,
+            withdrawn_at,
+        };
+        self.withdrawal_repo
+            .save(event.clone(), Some(&transaction))
+            .await?;
+        bank_account.domain_events_mut().push(event.into());
+        bank_account.charge_overdraft_fee()?;
+
+        let atm_events = atm.domain_events_mut().take();
+        self.atm_repo.edit(atm, Some(&transaction)).await?;
+
+        let events = bank_account.domain_events_mut().take();
+        self.repo.edit(bank_account, Some(&transaction)).await?;
+
+        transaction.record_ledger(&events).await?;
+        transaction.commit().await?;
+
+        for event in atm_events.into_iter() {
+            self.atm_event_bus.dispatch_event(event);
+        }
+
+        Ok(events)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// WriteCheckCommandHandler
+
+#[derive(new)]
+pub struct WriteCheckCommandHandler<
+    R: BankAccountRepository<Error = InfraError>,
+    U: UsedCheckNumberRepository<Error = InfraError, Transaction = R::Transaction>,
+    SR: SagaRepository<Error = InfraError, Transaction = R::Transaction>,
+> {
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+    used_check_number_repo: U,
+    saga_store: SagaStore<CheckClearingSaga, SR>,
+}
+
+#[async_trait::async_trait]
+impl<R, U, SR> HandleCommand for WriteCheckCommandHandler<R, U, SR>
+where
+    R: BankAccountRepository<Error = InfraError>,
+    R::Transaction: LedgerTransaction,
+    U: UsedCheckNumberRepository<Error = InfraError, Transaction = R::Transaction>,
+    SR: SagaRepository<Error = InfraError, Transaction = R::Transaction>,
+{
+    type Command = WriteCheckCommand;
+    type Aggregate = BankAccount;
+    type Error = ApplicationError;
+
+    async fn handle_command(
+        &self,
+        command: Self::Command,
+    ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
+        let transaction = R::Transaction::begin(&self.pool).await?;
+
+        let WriteCheckCommand {
+            account_id,
+            amount,
+            check_number,
+        } = command;
+
+        // 同じ小切手番号が同時に呈示されないようにロックする
+        let mut bank_account = self
+            .repo
+            .find_by_id_for_update(account_id, &transaction)
+            .await?;
+        let already_presented = self
+            .used_check_number_repo
+            .exists(account_id, &check_number, Some(&transaction))
+            .await?;
+        bank_account.write_check(amount, check_number.clone(), already_presented)?;
+
+        let now = Utc::now();
+        self.used_check_number_repo
+            .save(account_id, check_number.clone(), now, Some(&transaction))
+            .await?;
+
+        let events = bank_account.domain_events_mut().take();
+        self.repo.edit(bank_account, Some(&transaction)).await?;
+
+        // 決済サービスへの依頼はサガを進めるジョブが行う
+        self.saga_store
+            .start(
+                CheckClearingSaga::new(account_id, check_number, amount),
+                now,
+                Some(Duration::seconds(CONFIG.CHECK_CLEARING_SAGA_TIMEOUT_SECS)),
+                Some(&transaction),
+            )
+            .await?;
+
+        transaction.record_ledger(&events).await?;
+        transaction.commit().await?;
+
+        Ok(events)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// TransferMoneyCommandHandler
+
+/// 送金元と送金先の二つのアグリゲイトを一つのトランザクションで更新する．
+#[derive(new)]
+pub struct TransferMoneyCommandHandler<
+    R: BankAccountRepository<Error = InfraError>,
+    C: CurrencyConversionService,
+> {
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+    conversion_service: C,
+}
+
+#[async_trait::async_trait]
+impl<R, C> HandleCommand for TransferMoneyCommandHandler<R, C>
+where
+    R: BankAccountRepository<Error = InfraError>,
+    R::Transaction: LedgerTransaction,
+    C: CurrencyConversionService,
+{
+    type Command = TransferMoneyCommand;
+    type Aggregate = BankAccount;
+    type Error = ApplicationError;
+
+    async fn handle_command(
+        &self,
+        command: Self::Command,
+    ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
+        use domain::BankAccountError;
+
+        let TransferMoneyCommand {
+            from_account_id,
+            to_account_id,
+            amount,
+        } = command;
+
+        if from_account_id == to_account_id {
+            return Err(
+                DomainError::from(BankAccountError::SameAccountTransferError {
+                    account_id: from_account_id,
+                })
+                .into(),
+            );
+        }
+
+        let transaction = R::Transaction::begin(&self.pool).await?;
+
+        // デッドロックを避けるため，常にidの小さい順にロックを取得する
+        let (mut from_account, mut to_account) = if from_account_id < to_account_id {
+            let from_account = self
+                .repo
+                .find_by_id_for_update(from_account_id, &transaction)
+                .await?;
+            let to_account = self
+                .repo
+                .find_by_id_for_update(to_account_id, &transaction)
+                .await?;
+            (from_account, to_account)
+        } else {
+            let to_account = self
+                .repo
+                .find_by_id_for_update(to_account_id, &transaction)
+                .await?;
+            let from_account = self
+                .repo
+                .find_by_id_for_update(from_account_id, &transaction)
+                .await?;
+            (from_account, to_account)
+        };
+
+        let (converted_amount, applied_rate) = self
+            .conversion_service
+            .convert(amount, to_account.currency())
+            .await?;
+
+        from_account.transfer_out(to_account_id, amount)?;
+        to_account.transfer_in(from_account_id, converted_amount, applied_rate)?;
+
+        let mut events = from_account.domain_events_mut().take();
+        events.extend(to_account.domain_events_mut().take());
+
+        self.repo.edit(from_account, Some(&transaction)).await?;
+        self.repo.edit(to_account, Some(&transaction)).await?;
+
+        transaction.record_ledger(&events).await?;
+        transaction.commit().await?;
+
+        Ok(events)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// FreezeAccountCommandHandler
+
+#[derive(new)]
+pub struct FreezeAccountCommandHandler<R: BankAccountRepository<Error = InfraError>> {
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+}
+
+#[async_trait::async_trait]
+impl<R: BankAccountRepository<Error = InfraError>> HandleCommand
+    for FreezeAccountCommandHandler<R>
+{
+    type Command = FreezeAccountCommand;
+    type Aggregate = BankAccount;
+    type Error = ApplicationError;
+
+    async fn handle_command(
+        &self,
+        command: Self::Command,
+    ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
+        let transaction = R::Transaction::begin(&self.pool).await?;
+
+        let FreezeAccountCommand { account_id, reason } = command;
+
+        let mut bank_account = self
+            .repo
+            .find_by_id_for_update(account_id, &transaction)
+            .await?;
+        bank_account.freeze_account(reason)?;
+
+        let events = bank_account.domain_events_mut().take();
+        self.repo.edit(bank_account, Some(&transaction)).await?;
+
+        transaction.commit().await?;
+
+        Ok(events)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// UnfreezeAccountCommandHandler
+
+#[derive(new)]
+pub struct UnfreezeAccountCommandHandler<R: BankAccountRepository<Error = InfraError>> {
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+}
+
+#[async_trait::async_trait]
+impl<R: BankAccountRepository<Error = InfraError>> HandleCommand
+    for UnfreezeAccountCommandHandler<R>
+{
+    type Command = UnfreezeAccountCommand;
+    type Aggregate = BankAccount;
+    type Error = ApplicationError;
+
+    async fn handle_command(
+        &self,
+        command: Self::Command,
+    ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
+        let transaction = R::Transaction::begin(&self.pool).await?;
+
+        let UnfreezeAccountCommand { account_id } = command;
+
+        let mut bank_account = self
+            .repo
+            .find_by_id_for_update(account_id, &transaction)
+            .await?;
+        bank_account.unfreeze_account()?;
+
+        let events = bank_account.domain_events_mut().take();
+        self.repo.edit(bank_account, Some(&transaction)).await?;
+
+        transaction.commit().await?;
+
+        Ok(events)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// CloseAccountCommandHandler
+
+#[derive(new)]
+pub struct CloseAccountCommandHandler<R: BankAccountRepository<Error = InfraError>> {
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+}
+
+#[async_trait::async_trait]
+impl<R: BankAccountRepository<Error = InfraError>> HandleCommand for CloseAccountCommandHandler<R> {
+    type Command = CloseAccountCommand;
+    type Aggregate = BankAccount;
+    type Error = ApplicationError;
+
+    async fn handle_command(
+        &self,
+        command: Self::Command,
+    ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
+        let transaction = R::Transaction::begin(&self.pool).await?;
+
+        let CloseAccountCommand { account_id } = command;
+
+        let mut bank_account = self
+            .repo
+            .find_by_id_for_update(account_id, &transaction)
+            .await?;
+        bank_account.close_account()?;
+
+        let events = bank_account.domain_events_mut().take();
+        self.repo.edit(bank_account, Some(&transaction)).await?;
+
+        transaction.commit().await?;
+
+        Ok(events)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// SetOverdraftLimitCommandHandler
+
+#[derive(new)]
+pub struct SetOverdraftLimitCommandHandler<R: BankAccountRepository<Error = InfraError>> {
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+}
+
+#[async_trait::async_trait]
+impl<R: BankAccountRepository<Error = InfraError>> HandleCommand
+    for SetOverdraftLimitCommandHandler<R>
+{
+    type Command = SetOverdraftLimitCommand;
+    type Aggregate = BankAccount;
+    type Error = ApplicationError;
+
+    async fn handle_command(
+        &self,
+        command: Self::Command,
+    ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
+        let transaction = R::Transaction::begin(&self.pool).await?;
+
+        let SetOverdraftLimitCommand { account_id, limit } = command;
+
+        // 利用中の当座貸越と比較するため，引き出しと同時に実行されないようにロックする
+        let mut bank_account = self
+            .repo
+            .find_by_id_for_update(account_id, &transaction)
+            .await?;
+        bank_account.set_overdraft_limit(limit)?;
+
+        let events = bank_account.domain_events_mut().take();
+        self.repo.edit(bank_account, Some(&transaction)).await?;
+
+        transaction.commit().await?;
+
+        Ok(events)
+    }
+}
 
 // -------------------------------------------------------------------------------------------------
-// OpenAccountCommandHandler
+// AddOwnerCommandHandler
 
 #[derive(new)]
-pub struct OpenAccountCommandHandler<R: BankAccountRepository<Error = InfraError>> {
+pub struct AddOwnerCommandHandler<R: BankAccountRepository<Error = InfraError>> {
     repo: R,
     pool: <R::Transaction as Transaction>::Pool,
 }
 
 #[async_trait::async_trait]
-impl<R: BankAccountRepository<Error = InfraError>> HandleCommand for OpenAccountCommandHandler<R> {
-    type Command = OpenAccountCommand;
+impl<R: BankAccountRepository<Error = InfraError>> HandleCommand for AddOwnerCommandHandler<R> {
+    type Command = AddOwnerCommand;
     type Aggregate = BankAccount;
     type Error = ApplicationError;
 
@@ -38,17 +625,21 @@ impl<R: BankAccountRepository<Error = InfraError>> HandleCommand for OpenAccount
     ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
         let transaction = R::Transaction::begin(&self.pool).await?;
 
-        let OpenAccountCommand {
+        let AddOwnerCommand {
+            account_id,
             account_name,
             email_address,
+            role,
         } = command;
 
-        let mut bank_account = BankAccount::from_domains(email_address, account_name);
-        bank_account.open_account();
+        let mut bank_account = self
+            .repo
+            .find_by_id_for_update(account_id, &transaction)
+            .await?;
+        bank_account.add_owner(AccountOwner::new(account_name, email_address, role))?;
 
         let events = bank_account.domain_events_mut().take();
-
-        self.repo.save(bank_account, Some(&transaction)).await?;
+        self.repo.edit(bank_account, Some(&transaction)).await?;
 
         transaction.commit().await?;
 
@@ -57,17 +648,17 @@ impl<R: BankAccountRepository<Error = InfraError>> HandleCommand for OpenAccount
 }
 
 // -------------------------------------------------------------------------------------------------
-// DepositMoneyCommandHandler
+// RemoveOwnerCommandHandler
 
 #[derive(new)]
-pub struct DepositMoneyCommandHandler<R: BankAccountRepository<Error = InfraError>> {
+pub struct RemoveOwnerCommandHandler<R: BankAccountRepository<Error = InfraError>> {
     repo: R,
     pool: <R::Transaction as Transaction>::Pool,
 }
 
 #[async_trait::async_trait]
-impl<R: BankAccountRepository<Error = InfraError>> HandleCommand for DepositMoneyCommandHandler<R> {
-    type Command = DepositMoneyCommand;
+impl<R: BankAccountRepository<Error = InfraError>> HandleCommand for RemoveOwnerCommandHandler<R> {
+    type Command = RemoveOwnerCommand;
     type Aggregate = BankAccount;
     type Error = ApplicationError;
 
@@ -75,30 +666,61 @@ impl<R: BankAccountRepository<Error = InfraError>> HandleCommand for DepositMone
         &self,
         command: Self::Command,
     ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
-        use domain::events::bank_account_events::CustomerDepositedMoneyEvent;
-
         let transaction = R::Transaction::begin(&self.pool).await?;
 
-        let DepositMoneyCommand {
+        let RemoveOwnerCommand {
             account_id,
-            amount,
-            atm_id,
+            email_address,
         } = command;
 
-        let mut bank_account = self.repo.find_by_id(account_id, Some(&transaction)).await?;
-        if let Ok(_) = bank_account.deposit_money(amount) {
-            let balance = bank_account.balance();
+        let mut bank_account = self
+            .repo
+            .find_by_id_for_update(account_id, &transaction)
+            .await?;
+        bank_account.remove_owner(&email_address)?;
 
-            bank_account.domain_events_mut().push(
-                CustomerDepositedMoneyEvent {
-                    account_id,
-                    amount,
-                    balance,
-                    atm_id,
-                }
-                .into(),
-            );
-        }
+        let events = bank_account.domain_events_mut().take();
+        self.repo.edit(bank_account, Some(&transaction)).await?;
+
+        transaction.commit().await?;
+
+        Ok(events)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// RequestEmailChangeCommandHandler
+
+#[derive(new)]
+pub struct RequestEmailChangeCommandHandler<R: BankAccountRepository<Error = InfraError>> {
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+}
+
+#[async_trait::async_trait]
+impl<R: BankAccountRepository<Error = InfraError>> HandleCommand
+    for RequestEmailChangeCommandHandler<R>
+{
+    type Command = RequestEmailChangeCommand;
+    type Aggregate = BankAccount;
+    type Error = ApplicationError;
+
+    async fn handle_command(
+        &self,
+        command: Self::Command,
+    ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
+        let transaction = R::Transaction::begin(&self.pool).await?;
+
+        let RequestEmailChangeCommand {
+            account_id,
+            email_address,
+        } = command;
+
+        let mut bank_account = self
+            .repo
+            .find_by_id_for_update(account_id, &transaction)
+            .await?;
+        bank_account.request_email_change(email_address, Utc::now())?;
 
         let events = bank_account.domain_events_mut().take();
         self.repo.edit(bank_account, Some(&transaction)).await?;
@@ -110,19 +732,19 @@ impl<R: BankAccountRepository<Error = InfraError>> HandleCommand for DepositMone
 }
 
 // -------------------------------------------------------------------------------------------------
-// WithdrawMoneyCommand
+// ConfirmEmailChangeCommandHandler
 
 #[derive(new)]
-pub struct WithdrawMoneyCommandHandler<R: BankAccountRepository<Error = InfraError>> {
+pub struct ConfirmEmailChangeCommandHandler<R: BankAccountRepository<Error = InfraError>> {
     repo: R,
     pool: <R::Transaction as Transaction>::Pool,
 }
 
 #[async_trait::async_trait]
 impl<R: BankAccountRepository<Error = InfraError>> HandleCommand
-    for WithdrawMoneyCommandHandler<R>
+    for ConfirmEmailChangeCommandHandler<R>
 {
-    type Command = WithdrawMoneyCommand;
+    type Command = ConfirmEmailChangeCommand;
     type Aggregate = BankAccount;
     type Error = ApplicationError;
 
@@ -130,29 +752,80 @@ impl<R: BankAccountRepository<Error = InfraError>> HandleCommand
         &self,
         command: Self::Command,
     ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
-        use domain::events::bank_account_events::CustomerWithdrewCashEvent;
+        let transaction = R::Transaction::begin(&self.pool).await?;
+
+        let ConfirmEmailChangeCommand { account_id, token } = command;
+
+        // 同じトークンで二重に確定されないようにロックする
+        let mut bank_account = self
+            .repo
+            .find_by_id_for_update(account_id, &transaction)
+            .await?;
+        bank_account.confirm_email_change(&token, Utc::now())?;
+
+        let events = bank_account.domain_events_mut().take();
+        self.repo.edit(bank_account, Some(&transaction)).await?;
+
+        transaction.commit().await?;
+
+        Ok(events)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// ClearCheckCommandHandler
+
+#[derive(new)]
+pub struct ClearCheckCommandHandler<
+    R: BankAccountRepository<Error = InfraError>,
+    U: UsedCheckNumberRepository<Error = InfraError, Transaction = R::Transaction>,
+> {
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+    used_check_number_repo: U,
+}
+
+#[async_trait::async_trait]
+impl<R, U> HandleCommand for ClearCheckCommandHandler<R, U>
+where
+    R: BankAccountRepository<Error = InfraError>,
+    U: UsedCheckNumberRepository<Error = InfraError, Transaction = R::Transaction>,
+{
+    type Command = ClearCheckCommand;
+    type Aggregate = BankAccount;
+    type Error = ApplicationError;
 
+    async fn handle_command(
+        &self,
+        command: Self::Command,
+    ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
         let transaction = R::Transaction::begin(&self.pool).await?;
 
-        let WithdrawMoneyCommand {
+        let ClearCheckCommand {
             account_id,
+            check_number,
             amount,
-            atm_id,
         } = command;
 
-        let mut bank_account = self.repo.find_by_id(account_id, Some(&transaction)).await?;
-        if let Ok(_) = bank_account.withdraw_money(amount) {
-            let balance = bank_account.balance();
-
-            bank_account.domain_events_mut().push(
-                CustomerWithdrewCashEvent {
+        // 同じ小切手の決済と返金が同時に実行されないようにロックする
+        let mut bank_account = self
+            .repo
+            .find_by_id_for_update(account_id, &transaction)
+            .await?;
+        let settlement = self
+            .used_check_number_repo
+            .find_settlement(account_id, &check_number, Some(&transaction))
+            .await?;
+        bank_account.clear_check(check_number.clone(), amount, settlement)?;
+        if settlement.is_none() {
+            self.used_check_number_repo
+                .settle(
                     account_id,
-                    amount,
-                    balance,
-                    atm_id,
-                }
-                .into(),
-            );
+                    &check_number,
+                    CheckSettlement::Cleared,
+                    Some(&transaction),
+                )
+                .await?;
         }
 
         let events = bank_account.domain_events_mut().take();
@@ -165,17 +838,26 @@ impl<R: BankAccountRepository<Error = InfraError>> HandleCommand
 }
 
 // -------------------------------------------------------------------------------------------------
-// WriteCheckCommandHandler
+// RefundBouncedCheckCommandHandler
 
 #[derive(new)]
-pub struct WriteCheckCommandHandler<R: BankAccountRepository<Error = InfraError>> {
+pub struct RefundBouncedCheckCommandHandler<
+    R: BankAccountRepository<Error = InfraError>,
+    U: UsedCheckNumberRepository<Error = InfraError, Transaction = R::Transaction>,
+> {
     repo: R,
     pool: <R::Transaction as Transaction>::Pool,
+    used_check_number_repo: U,
 }
 
 #[async_trait::async_trait]
-impl<R: BankAccountRepository<Error = InfraError>> HandleCommand for WriteCheckCommandHandler<R> {
-    type Command = WriteCheckCommand;
+impl<R, U> HandleCommand for RefundBouncedCheckCommandHandler<R, U>
+where
+    R: BankAccountRepository<Error = InfraError>,
+    R::Transaction: LedgerTransaction,
+    U: UsedCheckNumberRepository<Error = InfraError, Transaction = R::Transaction>,
+{
+    type Command = RefundBouncedCheckCommand;
     type Aggregate = BankAccount;
     type Error = ApplicationError;
 
@@ -185,18 +867,39 @@ impl<R: BankAccountRepository<Error = InfraError>> HandleCommand for WriteCheckC
     ) -> Result<Vec<<Self::Aggregate as Aggregate>::Event>, Self::Error> {
         let transaction = R::Transaction::begin(&self.pool).await?;
 
-        let WriteCheckCommand {
+        let RefundBouncedCheckCommand {
             account_id,
-            amount,
             check_number,
+            amount,
+            reason,
         } = command;
 
-        let mut bank_account = self.repo.find_by_id(account_id, Some(&transaction)).await?;
-        bank_account.write_check(amount, check_number)?;
+        // 残高を変更するため，引き出しなどと同時に実行されないようにロックする
+        let mut bank_account = self
+            .repo
+            .find_by_id_for_update(account_id, &transaction)
+            .await?;
+        // 返金済みの小切手は再度返金しない
+        let settlement = self
+            .used_check_number_repo
+            .find_settlement(account_id, &check_number, Some(&transaction))
+            .await?;
+        bank_account.bounce_check(check_number.clone(), amount, reason, settlement)?;
+        if settlement.is_none() {
+            self.used_check_number_repo
+                .settle(
+                    account_id,
+                    &check_number,
+                    CheckSettlement::Bounced,
+                    Some(&transaction),
+                )
+                .await?;
+        }
 
         let events = bank_account.domain_events_mut().take();
         self.repo.edit(bank_account, Some(&transaction)).await?;
 
+        transaction.record_ledger(&events).await?;
         transaction.commit().await?;
 
         Ok(events)
@@ -236,10 +939,75 @@ pub struct BankAccountCommandHandler {
             Error = ApplicationError,
         >,
     >,
+    pub transfer_money_handler: Box<
+        dyn HandleCommand<
+            Command = TransferMoneyCommand,
+            Aggregate = BankAccount,
+            Error = ApplicationError,
+        >,
+    >,
+    pub freeze_account_handler: Box<
+        dyn HandleCommand<
+            Command = FreezeAccountCommand,
+            Aggregate = BankAccount,
+            Error = ApplicationError,
+        >,
+    >,
+    pub unfreeze_account_handler: Box<
+        dyn HandleCommand<
+            Command = UnfreezeAccountCommand,
+            Aggregate = BankAccount,
+            Error = ApplicationError,
+        >,
+    >,
+    pub close_account_handler: Box<
+        dyn HandleCommand<
+            Command = CloseAccountCommand,
+            Aggregate = BankAccount,
+            Error = ApplicationError,
+        >,
+    >,
+    pub set_overdraft_limit_handler: Box<
+        dyn HandleCommand<
+            Command = SetOverdraftLimitCommand,
+            Aggregate = BankAccount,
+            Error = ApplicationError,
+        >,
+    >,
+    pub add_owner_handler: Box<
+        dyn HandleCommand<
+            Command = AddOwnerCommand,
+            Aggregate = BankAccount,
+            Error = ApplicationError,
+        >,
+    >,
+    pub remove_owner_handler: Box<
+        dyn HandleCommand<
+            Command = RemoveOwnerCommand,
+            Aggregate = BankAccount,
+            Error = ApplicationError,
+        >,
+    >,
+    pub request_email_change_handler: Box<
+        dyn HandleCommand<
+            Command = RequestEmailChangeCommand,
+            Aggregate = BankAccount,
+            Error = ApplicationError,
+        >,
+    >,
+    pub confirm_email_change_handler: Box<
+        dyn HandleCommand<
+            Command = ConfirmEmailChangeCommand,
+            Aggregate = BankAccount,
+            Error = ApplicationError,
+        >,
+    >,
     /// BankAccountEventに関するイベントバス
     pub event_bus: BankAccountEventBus,
     /// リトライなどにより重複したコマンドじゃないかどうかを判定するキャッシュ．場合によってはリポジトリとする．
     pub command_id_cache: Mutex<LruCache<CommandId, ()>>,
+    /// ドメインのルールにより拒否されたコマンドの監査ログ
+    pub rejected_command_auditor: RejectedCommandAuditor,
 }
 
 impl BankAccountCommandHandler {
@@ -259,51 +1027,175 @@ impl ApiHandleCommand for BankAccountCommandHandler {
     type Command = BankAccountCommand;
 
     async fn handle_command(&self, command: Self::Command) -> Result<(), ApplicationError> {
-        let events = match command {
+        let command_id = command.command_id();
+        let command_name = command.command_name();
+        let account_id = command.account_id();
+
+        let res = match command {
             BankAccountCommand::OpenAccountCommand(cmd, id) => {
                 if !self.check_command_duplicate(id) {
-                    self.open_account_handler.handle_command(cmd).await?
+                    self.open_account_handler.handle_command(cmd).await
                 } else {
                     if self.open_account_handler.allow_duplicate() {
-                        self.open_account_handler.handle_command(cmd).await?
+                        self.open_account_handler.handle_command(cmd).await
                     } else {
-                        Vec::new()
+                        Ok(Vec::new())
                     }
                 }
             }
             BankAccountCommand::DepositMoneyCommand(cmd, id) => {
                 if !self.check_command_duplicate(id) {
-                    self.deposit_money_handler.handle_command(cmd).await?
+                    self.deposit_money_handler.handle_command(cmd).await
                 } else {
                     if self.deposit_money_handler.allow_duplicate() {
-                        self.deposit_money_handler.handle_command(cmd).await?
+                        self.deposit_money_handler.handle_command(cmd).await
                     } else {
-                        Vec::new()
+                        Ok(Vec::new())
                     }
                 }
             }
             BankAccountCommand::WithdrawMoneyCommand(cmd, id) => {
                 if !self.check_command_duplicate(id) {
-                    self.withdraw_money_handler.handle_command(cmd).await?
+                    self.withdraw_money_handler.handle_command(cmd).await
                 } else {
                     if self.withdraw_money_handler.allow_duplicate() {
-                        self.withdraw_money_handler.handle_command(cmd).await?
+                        self.withdraw_money_handler.handle_command(cmd).await
                     } else {
-                        Vec::new()
+                        Ok(Vec::new())
                     }
                 }
             }
             BankAccountCommand::WriteCheckCommand(cmd, id) => {
                 if !self.check_command_duplicate(id) {
-                    self.write_check_handler.handle_command(cmd).await?
+                    self.write_check_handler.handle_command(cmd).await
                 } else {
                     if self.write_check_handler.allow_duplicate() {
-                        self.write_check_handler.handle_command(cmd).await?
+                        self.write_check_handler.handle_command(cmd).await
+                    } else {
+                        Ok(Vec::new())
+                    }
+                }
+            }
+            BankAccountCommand::TransferMoneyCommand(cmd, id) => {
+                if !self.check_command_duplicate(id) {
+                    self.transfer_money_handler.handle_command(cmd).await
+                } else {
+                    if self.transfer_money_handler.allow_duplicate() {
+                        self.transfer_money_handler.handle_command(cmd).await
+                    } else {
+                        Ok(Vec::new())
+                    }
+                }
+            }
+            BankAccountCommand::FreezeAccountCommand(cmd, id) => {
+                if !self.check_command_duplicate(id) {
+                    self.freeze_account_handler.handle_command(cmd).await
+                } else {
+                    if self.freeze_account_handler.allow_duplicate() {
+                        self.freeze_account_handler.handle_command(cmd).await
+                    } else {
+                        Ok(Vec::new())
+                    }
+                }
+            }
+            BankAccountCommand::UnfreezeAccountCommand(cmd, id) => {
+                if !self.check_command_duplicate(id) {
+                    self.unfreeze_account_handler.handle_command(cmd).await
+                } else {
+                    if self.unfreeze_account_handler.allow_duplicate() {
+                        self.unfreeze_account_handler.handle_command(cmd).await
+                    } else {
+                        Ok(Vec::new())
+                    }
+                }
+            }
+            BankAccountCommand::CloseAccountCommand(cmd, id) => {
+                if !self.check_command_duplicate(id) {
+                    self.close_account_handler.handle_command(cmd).await
+                } else {
+                    if self.close_account_handler.allow_duplicate() {
+                        self.close_account_handler.handle_command(cmd).await
+                    } else {
+                        Ok(Vec::new())
+                    }
+                }
+            }
+            BankAccountCommand::SetOverdraftLimitCommand(cmd, id) => {
+                if !self.check_command_duplicate(id) {
+                    self.set_overdraft_limit_handler.handle_command(cmd).await
+                } else {
+                    if self.set_overdraft_limit_handler.allow_duplicate() {
+                        self.set_overdraft_limit_handler.handle_command(cmd).await
+                    } else {
+                        Ok(Vec::new())
+                    }
+                }
+            }
+            BankAccountCommand::AddOwnerCommand(cmd, id) => {
+                if !self.check_command_duplicate(id) {
+                    self.add_owner_handler.handle_command(cmd).await
+                } else {
+                    if self.add_owner_handler.allow_duplicate() {
+                        self.add_owner_handler.handle_command(cmd).await
                     } else {
-                        Vec::new()
+                        Ok(Vec::new())
                     }
                 }
             }
+            BankAccountCommand::RemoveOwnerCommand(cmd, id) => {
+                if !self.check_command_duplicate(id) {
+                    self.remove_owner_handler.handle_command(cmd).await
+                } else {
+                    if self.remove_owner_handler.allow_duplicate() {
+                        self.remove_owner_handler.handle_command(cmd).await
+                    } else {
+                        Ok(Vec::new())
+                    }
+                }
+            }
+            BankAccountCommand::RequestEmailChangeCommand(cmd, id) => {
+                if !self.check_command_duplicate(id) {
+                    self.request_email_change_handler.handle_command(cmd).await
+                } else {
+                    if self.request_email_change_handler.allow_duplicate() {
+                        self.request_email_change_handler.handle_command(cmd).await
+                    } else {
+                        Ok(Vec::new())
+                    }
+                }
+            }
+            BankAccountCommand::ConfirmEmailChangeCommand(cmd, id) => {
+                if !self.check_command_duplicate(id) {
+                    self.confirm_email_change_handler.handle_command(cmd).await
+                } else {
+                    if self.confirm_email_change_handler.allow_duplicate() {
+                        self.confirm_email_change_handler.handle_command(cmd).await
+                    } else {
+                        Ok(Vec::new())
+                    }
+                }
+            }
+        };
+
+        if res.is_err() {
+            // 失敗したコマンドはリトライできるように重複判定のキャッシュから取り除く
+            self.command_id_cache.lock().unwrap().pop(&command_id);
+        }
+
+        let events = match res {
+            Ok(events) => events,
+            Err(e @ ApplicationError::DomainError(_)) => {
+                // ドメインのルールにより拒否されたコマンドを記録し，エラーはそのまま呼び出し側に返す
+                if let Err(audit_e) = self
+                    .rejected_command_auditor
+                    .record(command_id, command_name, account_id.map(Into::into), &e)
+                    .await
+                {
+                    warn!("Failed to record rejected command {command_name}: {audit_e}");
+                }
+                return Err(e);
+            }
+            Err(e) => return Err(e),
         };
 
         // イベントのディスパッチ
@@ -314,3 +1206,178 @@ impl ApiHandleCommand for BankAccountCommandHandler {
         Ok(())
     }
 }
+
+// -------------------------------------------------------------------------------------------------
+// test
+
+#[cfg(test)]
+mod test {
+    use super::BankAccountCommandHandler;
+    use crate::audit::{RejectedCommandAuditor, RejectedCommandSink};
+    use crate::command_handlers::ApiHandleCommand;
+    use crate::event_handlers::bank_account_event_handlers::BankAccountEventBus;
+    use common::commands::bank_account_commands::{BankAccountCommand, WithdrawMoneyCommand};
+    use common::commands::CommandId;
+    use common::read_models::rejected_command;
+    use common::ApplicationError;
+    use domain::aggregates::bank_account::{AccountStatus, BankAccountId};
+    use domain::aggregates::{atm::AtmId, BankAccount};
+    use domain::events::bank_account_events::BankAccountEvent;
+    use domain::{BankAccountError, DomainError};
+
+    use ddd_cqrs_core::HandleCommand;
+    use event_bus::EventBus;
+
+    use lru::LruCache;
+    use std::marker::PhantomData;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// 決まった結果を返し，呼ばれた回数を数えるコマンドハンドラ
+    struct StubHandler<C> {
+        result: Result<Vec<BankAccountEvent>, ApplicationError>,
+        calls: Arc<AtomicUsize>,
+        command_type: PhantomData<fn(C)>,
+    }
+
+    impl<C> StubHandler<C> {
+        fn boxed(
+            result: Result<Vec<BankAccountEvent>, ApplicationError>,
+            calls: Arc<AtomicUsize>,
+        ) -> Box<Self> {
+            Box::new(Self {
+                result,
+                calls,
+                command_type: PhantomData,
+            })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl<C: Send + 'static> HandleCommand for StubHandler<C> {
+        type Command = C;
+        type Aggregate = BankAccount;
+        type Error = ApplicationError;
+
+        async fn handle_command(
+            &self,
+            _: Self::Command,
+        ) -> Result<Vec<BankAccountEvent>, Self::Error> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.result.clone()
+        }
+    }
+
+    /// 監査ログをメモリに記録する
+    #[derive(Clone, Default)]
+    struct MemorySink(Arc<Mutex<Vec<rejected_command::Model>>>);
+
+    #[async_trait::async_trait]
+    impl RejectedCommandSink for MemorySink {
+        async fn insert(&self, model: rejected_command::Model) -> Result<(), ApplicationError> {
+            self.0.lock().unwrap().push(model);
+            Ok(())
+        }
+    }
+
+    /// 引き出しのみ結果を指定し，その他は何もしないハンドラとしたコマンドハンドラ
+    fn command_handler(
+        withdraw_result: Result<Vec<BankAccountEvent>, ApplicationError>,
+        withdraw_calls: Arc<AtomicUsize>,
+        sink: MemorySink,
+    ) -> BankAccountCommandHandler {
+        fn ok<C>() -> Box<StubHandler<C>> {
+            StubHandler::boxed(Ok(Vec::new()), Arc::default())
+        }
+
+        BankAccountCommandHandler {
+            deposit_money_handler: ok(),
+            open_account_handler: ok(),
+            withdraw_money_handler: StubHandler::boxed(withdraw_result, withdraw_calls),
+            write_check_handler: ok(),
+            transfer_money_handler: ok(),
+            freeze_account_handler: ok(),
+            unfreeze_account_handler: ok(),
+            close_account_handler: ok(),
+            set_overdraft_limit_handler: ok(),
+            add_owner_handler: ok(),
+            remove_owner_handler: ok(),
+            request_email_change_handler: ok(),
+            confirm_email_change_handler: ok(),
+            event_bus: BankAccountEventBus::new(EventBus::new()),
+            command_id_cache: Mutex::new(LruCache::new(10.try_into().unwrap())),
+            rejected_command_auditor: RejectedCommandAuditor::with_sink(sink),
+        }
+    }
+
+    fn withdraw_command(id: CommandId) -> BankAccountCommand {
+        BankAccountCommand::WithdrawMoneyCommand(
+            WithdrawMoneyCommand {
+                account_id: BankAccountId::generate(),
+                amount: "1000 JPY".parse().unwrap(),
+                atm_id: AtmId::generate(),
+            },
+            id,
+        )
+    }
+
+    #[tokio::test]
+    async fn rejected_command_is_audited_and_retryable() {
+        let sink = MemorySink::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let error: ApplicationError = DomainError::from(BankAccountError::AccountNotOpenError {
+            status: AccountStatus::Frozen,
+        })
+        .into();
+        let handler = command_handler(Err(error), calls.clone(), sink.clone());
+
+        // ドメインのエラーはそのまま呼び出し側に返す
+        let id = CommandId::generate();
+        for _ in 0..2 {
+            let res = handler.handle_command(withdraw_command(id)).await;
+            assert!(matches!(
+                res,
+                Err(ApplicationError::DomainError(
+                    DomainError::BankAccountError(BankAccountError::AccountNotOpenError { .. })
+                ))
+            ));
+        }
+
+        // 失敗したコマンドは重複とみなさず再実行し，その度に監査ログに記録する
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let audited = sink.0.lock().unwrap();
+        assert_eq!(audited.len(), 2);
+        assert_eq!(audited[0].command_id, id);
+        assert_eq!(audited[0].command_name, "WithdrawMoneyCommand");
+        assert_eq!(audited[0].error_code, "ACCOUNT_NOT_OPEN");
+    }
+
+    #[tokio::test]
+    async fn infra_error_is_not_audited() {
+        let sink = MemorySink::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let error = ApplicationError::OtherInfraError("Connection refused.".to_string());
+        let handler = command_handler(Err(error), calls.clone(), sink.clone());
+
+        let id = CommandId::generate();
+        for _ in 0..2 {
+            let res = handler.handle_command(withdraw_command(id)).await;
+            assert!(matches!(res, Err(ApplicationError::OtherInfraError(_))));
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(sink.0.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn duplicate_command_is_skipped() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let handler = command_handler(Ok(Vec::new()), calls.clone(), MemorySink::default());
+
+        let id = CommandId::generate();
+        handler.handle_command(withdraw_command(id)).await.unwrap();
+        handler.handle_command(withdraw_command(id)).await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
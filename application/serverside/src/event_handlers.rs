@@ -1 +1,3 @@
+pub mod atm_event_handlers;
 pub mod bank_account_event_handlers;
+pub mod mail_templates;
@@ -0,0 +1,63 @@
+use super::mail_templates;
+use common::ApplicationError;
+use domain::aggregates::bank_account::EmailAddress;
+use domain::events::atm_events::{AtmCashLowEvent, AtmEvent};
+use infrastructure::mailer::Mailer;
+
+use event_bus::{EventBus, Subscribe, Task};
+
+use derive_new::new;
+use tracing::info;
+
+// -------------------------------------------------------------------------------------------------
+// SendAtmCashLowAlertHandler
+
+/// Atmの現金が少なくなったことを運用担当者にメールで知らせるイベントハンドラ
+#[derive(new)]
+pub struct SendAtmCashLowAlertHandler<M: Mailer> {
+    mailer: M,
+    /// 運用担当者のメールアドレス
+    to: EmailAddress,
+}
+
+#[async_trait::async_trait]
+impl<M: Mailer> Subscribe for SendAtmCashLowAlertHandler<M> {
+    type InputEvent = AtmCashLowEvent;
+    type Output = Result<(), ApplicationError>;
+    async fn handle_event<'event>(
+        &self,
+        event: &'event Self::InputEvent,
+    ) -> Result<(), ApplicationError> {
+        info!("SendAtmCashLowAlertHandler dispatched.");
+
+        self.mailer
+            .send(mail_templates::atm_cash_low_alert(self.to.clone(), event))
+            .await?;
+        Ok(())
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// AtmEventBus
+
+/// Atmに対するイベントバス
+#[derive(derive_new::new)]
+pub struct AtmEventBus {
+    event_bus: EventBus<Result<(), ApplicationError>>,
+}
+
+impl AtmEventBus {
+    pub fn dispatch_event(&self, event: AtmEvent) -> Vec<Task<Result<(), ApplicationError>>> {
+        use AtmEvent::*;
+
+        match event {
+            AtmRegisteredEvent(e) => self.event_bus.dispatch_event(e),
+            AtmCashChargedEvent(e) => self.event_bus.dispatch_event(e),
+            AtmCashDispensedEvent(e) => self.event_bus.dispatch_event(e),
+            AtmCashLowEvent(e) => self.event_bus.dispatch_event(e),
+            AtmStatusChangedEvent(e) => self.event_bus.dispatch_event(e),
+            AtmDecommissionedEvent(e) => self.event_bus.dispatch_event(e),
+            AtmRelocatedEvent(e) => self.event_bus.dispatch_event(e),
+        }
+    }
+}
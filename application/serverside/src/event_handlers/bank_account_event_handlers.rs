@@ -1,13 +1,15 @@
+use super::mail_templates;
 use common::ApplicationError;
 use domain::events::bank_account_events::{
-    AccountOpenedEvent, BankAccountEvent, CustomerDepositedMoneyEvent, CustomerWithdrewCashEvent,
-    CustomerWroteCheckEvent,
+    AccountOpenedEvent, BankAccountEvent, CustomerWithdrewCashEvent, EmailChangeRequestedEvent,
 };
-use domain::repositories::{AtmRepository, Transaction};
+use domain::repositories::BankAccountRepository;
+use infrastructure::mailer::Mailer;
 use infrastructure::InfraError;
 
 use event_bus::{EventBus, Subscribe, Task};
 
+use config::CONFIG;
 use derive_new::new;
 use tracing::info;
 
@@ -16,10 +18,12 @@ use tracing::info;
 
 /// アカウントの開設をメールで送信するイベントハンドラ
 #[derive(new)]
-pub struct SendOpenAccountMailHandler;
+pub struct SendOpenAccountMailHandler<M: Mailer> {
+    mailer: M,
+}
 
 #[async_trait::async_trait]
-impl Subscribe for SendOpenAccountMailHandler {
+impl<M: Mailer> Subscribe for SendOpenAccountMailHandler<M> {
     type InputEvent = AccountOpenedEvent;
     type Output = Result<(), ApplicationError>;
     async fn handle_event<'event>(
@@ -28,116 +32,73 @@ impl Subscribe for SendOpenAccountMailHandler {
     ) -> Result<(), ApplicationError> {
         info!("SendOpenAccountMainHandler dispatched.");
 
-        info!("Send email to {:?}", event.email_address);
+        self.mailer
+            .send(mail_templates::account_opened(event))
+            .await?;
         Ok(())
     }
 }
 
 // -------------------------------------------------------------------------------------------------
-// AtmDepositHandler
+// SendEmailChangeMailHandler
 
-/// Atmに現金を入れるイベントハンドラ
+/// メールアドレスの変更を確認するトークンを新しいメールアドレスに送信するイベントハンドラ
 #[derive(new)]
-pub struct AtmDepositHandler<AR: AtmRepository<Error = InfraError>> {
-    repo: AR,
-    pool: <AR::Transaction as Transaction>::Pool,
+pub struct SendEmailChangeMailHandler<M: Mailer> {
+    mailer: M,
 }
 
 #[async_trait::async_trait]
-impl<AR: AtmRepository<Error = InfraError>> Subscribe for AtmDepositHandler<AR> {
-    type InputEvent = CustomerDepositedMoneyEvent;
+impl<M: Mailer> Subscribe for SendEmailChangeMailHandler<M> {
+    type InputEvent = EmailChangeRequestedEvent;
     type Output = Result<(), ApplicationError>;
     async fn handle_event<'event>(
         &self,
         event: &'event Self::InputEvent,
     ) -> Result<(), ApplicationError> {
-        info!("AtmDepositHandler dispatched.");
-
-        let transaction = <AR::Transaction as Transaction>::begin(&self.pool).await?;
-
-        let CustomerDepositedMoneyEvent {
-            account_id: _,
-            amount,
-            balance: _,
-            atm_id,
-        } = event;
-
-        let mut atm = self.repo.find_by_id(*atm_id, Some(&transaction)).await?;
-
-        // 実際はもうアカウントのトランザクションを終了しているため，他の方法でリカバリーする
-        atm.charge_cash(*amount)?;
-
-        self.repo.edit(atm, Some(&transaction)).await?;
+        info!("SendEmailChangeMailHandler dispatched.");
 
+        self.mailer
+            .send(mail_templates::email_change_requested(event))
+            .await?;
         Ok(())
     }
 }
 
 // -------------------------------------------------------------------------------------------------
-// AtmWithdrawHandler
+// SendLargeWithdrawalAlertHandler
 
-/// Atmからお金を引き出すイベントハンドラ
+/// 高額の引き出しを主たる名義人にメールで知らせるイベントハンドラ
 #[derive(new)]
-pub struct AtmWithdrawHandler<AR: AtmRepository<Error = InfraError>> {
-    repo: AR,
-    pool: <AR::Transaction as Transaction>::Pool,
+pub struct SendLargeWithdrawalAlertHandler<R: BankAccountRepository<Error = InfraError>, M: Mailer>
+{
+    repo: R,
+    mailer: M,
 }
 
 #[async_trait::async_trait]
-impl<AR: AtmRepository<Error = InfraError>> Subscribe for AtmWithdrawHandler<AR> {
+impl<R: BankAccountRepository<Error = InfraError>, M: Mailer> Subscribe
+    for SendLargeWithdrawalAlertHandler<R, M>
+{
     type InputEvent = CustomerWithdrewCashEvent;
     type Output = Result<(), ApplicationError>;
     async fn handle_event<'event>(
         &self,
         event: &'event Self::InputEvent,
     ) -> Result<(), ApplicationError> {
-        info!("AtmWithdrawHandler dispatched.");
-
-        let transaction = <AR::Transaction as Transaction>::begin(&self.pool).await?;
-
-        let CustomerWithdrewCashEvent {
-            account_id: _,
-            amount,
-            balance: _,
-            atm_id,
-        } = event;
-
-        let mut atm = self.repo.find_by_id(*atm_id, Some(&transaction)).await?;
-
-        // 実際はもうアカウントのトランザクションを終了しているため，他の方法でリカバリーする
-        atm.withdraw(*amount)?;
-
-        self.repo.edit(atm, Some(&transaction)).await?;
-
-        Ok(())
-    }
-}
-
-// -------------------------------------------------------------------------------------------------
-// ExternalWroteCheckHandler
-
-/// 小切手利用の際に外部サービスを利用するイベントハンドラ
-#[derive(new)]
-pub struct ExternalWroteCheckHandler;
-
-#[async_trait::async_trait]
-impl Subscribe for ExternalWroteCheckHandler {
-    type InputEvent = CustomerWroteCheckEvent;
-    type Output = Result<(), ApplicationError>;
-    async fn handle_event<'event>(
-        &self,
-        event: &'event Self::InputEvent,
-    ) -> Result<(), ApplicationError> {
-        info!("ExternalWroteCheckHandler dispatched.");
+        if event.amount.amount() < CONFIG.LARGE_WITHDRAWAL_ALERT_THRESHOLD {
+            return Ok(());
+        }
+        info!("SendLargeWithdrawalAlertHandler dispatched.");
 
-        let CustomerWroteCheckEvent {
-            account_id: _,
-            check_number,
-            amount,
-            balance: _,
-        } = event;
+        let bank_account = self.repo.find_by_id(event.account_id, None).await?;
 
-        info!("Use external api:  check_number: {check_number}, amount: {amount}");
+        self.mailer
+            .send(mail_templates::large_withdrawal_alert(
+                bank_account.email_address().clone(),
+                event,
+            ))
+            .await?;
         Ok(())
     }
 }
@@ -160,9 +121,23 @@ impl BankAccountEventBus {
 
         match event {
             AccountOpenedEvent(e) => self.event_bus.dispatch_event(e),
+            AccountFrozenEvent(e) => self.event_bus.dispatch_event(e),
+            AccountUnfrozenEvent(e) => self.event_bus.dispatch_event(e),
+            AccountClosedEvent(e) => self.event_bus.dispatch_event(e),
             CustomerDepositedMoneyEvent(e) => self.event_bus.dispatch_event(e),
             CustomerWithdrewCashEvent(e) => self.event_bus.dispatch_event(e),
             CustomerWroteCheckEvent(e) => self.event_bus.dispatch_event(e),
+            CheckClearedEvent(e) => self.event_bus.dispatch_event(e),
+            CheckBouncedEvent(e) => self.event_bus.dispatch_event(e),
+            MoneyTransferredOutEvent(e) => self.event_bus.dispatch_event(e),
+            MoneyTransferredInEvent(e) => self.event_bus.dispatch_event(e),
+            OverdraftLimitSetEvent(e) => self.event_bus.dispatch_event(e),
+            OverdraftFeeChargedEvent(e) => self.event_bus.dispatch_event(e),
+            InterestAccruedEvent(e) => self.event_bus.dispatch_event(e),
+            OwnerAddedEvent(e) => self.event_bus.dispatch_event(e),
+            OwnerRemovedEvent(e) => self.event_bus.dispatch_event(e),
+            EmailChangeRequestedEvent(e) => self.event_bus.dispatch_event(e),
+            EmailAddressChangedEvent(e) => self.event_bus.dispatch_event(e),
         }
     }
 }
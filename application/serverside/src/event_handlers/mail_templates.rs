@@ -0,0 +1,100 @@
+use domain::aggregates::bank_account::EmailAddress;
+use domain::events::atm_events::AtmCashLowEvent;
+use domain::events::bank_account_events::{
+    AccountOpenedEvent, CustomerWithdrewCashEvent, EmailChangeRequestedEvent,
+};
+use infrastructure::mailer::Mail;
+
+/// アカウントの開設を知らせるメール
+pub fn account_opened(event: &AccountOpenedEvent) -> Mail {
+    let AccountOpenedEvent {
+        account_id,
+        email_address,
+    } = event;
+
+    Mail::new(
+        email_address.clone(),
+        "Your account has been opened".to_string(),
+        format!(
+            "Your bank account has been opened.\n\
+             \n\
+             Account ID: {account_id}\n",
+            account_id = account_id.to_uuid(),
+        ),
+    )
+}
+
+/// メールアドレスの変更を確認するトークンを送るメール．新しいメールアドレスに送信する
+pub fn email_change_requested(event: &EmailChangeRequestedEvent) -> Mail {
+    let EmailChangeRequestedEvent {
+        account_id,
+        email_address,
+        token,
+        expires_at,
+    } = event;
+
+    Mail::new(
+        email_address.clone(),
+        "Confirm your new email address".to_string(),
+        format!(
+            "A change of the contact email address was requested for account {account_id}.\n\
+             \n\
+             Verification token: {token}\n\
+             This token expires at {expires_at}.\n\
+             \n\
+             If you did not request this change, please ignore this email.\n",
+            account_id = account_id.to_uuid(),
+            expires_at = expires_at.to_rfc3339(),
+        ),
+    )
+}
+
+/// 高額の引き出しを知らせるメール
+pub fn large_withdrawal_alert(to: EmailAddress, event: &CustomerWithdrewCashEvent) -> Mail {
+    let CustomerWithdrewCashEvent {
+        account_id,
+        amount,
+        balance,
+        atm_id,
+        withdrawn_at,
+    } = event;
+
+    Mail::new(
+        to,
+        "Large withdrawal from your account".to_string(),
+        format!(
+            "A withdrawal of {amount} was made from account {account_id}.\n\
+             \n\
+             ATM: {atm_id}\n\
+             Withdrawn at: {withdrawn_at}\n\
+             Balance: {balance}\n\
+             \n\
+             If you did not make this withdrawal, please contact us immediately.\n",
+            account_id = account_id.to_uuid(),
+            atm_id = atm_id.to_uuid(),
+            withdrawn_at = withdrawn_at.to_rfc3339(),
+        ),
+    )
+}
+
+/// Atmの現金が少なくなったことを運用担当者に知らせるメール
+pub fn atm_cash_low_alert(to: EmailAddress, event: &AtmCashLowEvent) -> Mail {
+    let AtmCashLowEvent {
+        atm_id,
+        total_cash,
+        threshold,
+    } = event;
+
+    Mail::new(
+        to,
+        "ATM is running low on cash".to_string(),
+        format!(
+            "The cash in ATM {atm_id} has fallen below {threshold}.\n\
+             \n\
+             Remaining cash: {total_cash}\n\
+             \n\
+             Please schedule a cash replenishment.\n",
+            atm_id = atm_id.to_uuid(),
+        ),
+    )
+}
@@ -0,0 +1,3 @@
+pub mod atm_deposit_saga_job;
+pub mod check_clearing_saga_job;
+pub mod interest_accrual_job;
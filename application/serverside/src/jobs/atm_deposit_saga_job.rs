@@ -0,0 +1,52 @@
+use crate::sagas::atm_deposit_saga::AtmDepositSagaManager;
+
+use common::ApplicationError;
+use domain::repositories::{AtmRepository, SagaRepository};
+use infrastructure::InfraError;
+
+use chrono::{DateTime, Utc};
+use derive_new::new;
+use sea_orm::prelude::Uuid;
+use tracing::info;
+
+/// Atmへの入金のサガを進めるジョブの実行結果
+#[derive(Debug, Default)]
+pub struct AtmDepositSagaReport {
+    /// Atmにチャージしたサガ
+    pub charged: Vec<Uuid>,
+    /// チャージできなかったサガ．Atmが拒否した場合はFailedとして記録し，それ以外は時間をおいて再びチャージする
+    pub failed: Vec<(Uuid, ApplicationError)>,
+}
+
+/// 口座に入金された現金をAtmにチャージするジョブ．
+/// チャージとサガの終了を一つのトランザクションで保存するため，並行して何度実行してもよい
+#[derive(new)]
+pub struct AtmDepositSagaJob<
+    SR: SagaRepository<Error = InfraError>,
+    A: AtmRepository<Error = InfraError, Transaction = SR::Transaction>,
+> {
+    saga_manager: AtmDepositSagaManager<SR, A>,
+}
+
+impl<SR, A> AtmDepositSagaJob<SR, A>
+where
+    SR: SagaRepository<Error = InfraError>,
+    A: AtmRepository<Error = InfraError, Transaction = SR::Transaction>,
+{
+    /// nowまでに進める時刻を過ぎたサガのチャージを行う
+    pub async fn run(&self, now: DateTime<Utc>) -> Result<AtmDepositSagaReport, ApplicationError> {
+        let mut report = AtmDepositSagaReport::default();
+
+        for (saga_id, res) in self.saga_manager.run_due(now).await? {
+            match res {
+                Ok(_) => report.charged.push(saga_id),
+                Err(e) => {
+                    info!("Failed to charge AtmDepositSaga {saga_id}: {e}");
+                    report.failed.push((saga_id, e));
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,58 @@
+use crate::sagas::check_clearing_saga::CheckClearingSagaManager;
+
+use common::ApplicationError;
+use domain::repositories::SagaRepository;
+use infrastructure::check_clearing::CheckClearingService;
+use infrastructure::InfraError;
+
+use ddd_cqrs_core::SagaStatus;
+
+use chrono::{DateTime, Utc};
+use derive_new::new;
+use sea_orm::prelude::Uuid;
+use tracing::info;
+
+/// 小切手の決済のサガを進めるジョブの実行結果
+#[derive(Debug, Default)]
+pub struct CheckClearingSagaReport {
+    /// 決済されたサガ
+    pub cleared: Vec<Uuid>,
+    /// 不渡りとして返金したサガ
+    pub refunded: Vec<Uuid>,
+    /// 期限までに決済の結果が得られず，手動での確認を待っているサガ
+    pub suspended: Vec<Uuid>,
+    /// 進められなかったサガ．コマンドが拒否された場合はFailedとして記録し，それ以外は時間をおいて再び進める
+    pub failed: Vec<(Uuid, ApplicationError)>,
+}
+
+/// 小切手の決済のサガを進めるジョブ．発行した小切手の決済を依頼し，結果に応じて決済・返金を行う．
+/// 中断したサガも再び進める．サガを確保してから進めるため，並行して何度実行してもよい
+#[derive(new)]
+pub struct CheckClearingSagaJob<S: CheckClearingService, SR: SagaRepository<Error = InfraError>> {
+    saga_manager: CheckClearingSagaManager<S, SR>,
+}
+
+impl<S: CheckClearingService, SR: SagaRepository<Error = InfraError>> CheckClearingSagaJob<S, SR> {
+    /// nowまでに進める時刻を過ぎたサガを進める
+    pub async fn run(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<CheckClearingSagaReport, ApplicationError> {
+        let mut report = CheckClearingSagaReport::default();
+
+        for (saga_id, res) in self.saga_manager.run_due(now).await? {
+            match res {
+                Ok(SagaStatus::Completed) => report.cleared.push(saga_id),
+                Ok(SagaStatus::Compensated) => report.refunded.push(saga_id),
+                Ok(SagaStatus::Suspended) => report.suspended.push(saga_id),
+                Ok(_) => {}
+                Err(e) => {
+                    info!("Failed to advance CheckClearingSaga {saga_id}: {e}");
+                    report.failed.push((saga_id, e));
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
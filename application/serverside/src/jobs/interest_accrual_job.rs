@@ -0,0 +1,82 @@
+use ddd_cqrs_core::Aggregate;
+
+use crate::ledger::LedgerTransaction;
+use common::ApplicationError;
+use domain::aggregates::bank_account::BankAccountId;
+use domain::events::bank_account_events::{BankAccountEvent, InterestAccruedEvent};
+use domain::repositories::{BankAccountRepository, Transaction};
+use domain::DomainError;
+use infrastructure::InfraError;
+
+use chrono::NaiveDate;
+use derive_new::new;
+use tracing::info;
+
+/// 利息の計上ジョブの実行結果
+#[derive(Debug, Default)]
+pub struct InterestAccrualReport {
+    /// 計上した利息のイベント
+    pub accrued: Vec<InterestAccruedEvent>,
+    /// ドメインのルールにより計上しなかったアカウント
+    pub skipped: Vec<(BankAccountId, DomainError)>,
+}
+
+/// 月次の利息を計上するジョブ．全てのアカウントを走査し，アカウントごとのトランザクションで計上する．
+/// 計上済みの期間はアグリゲイト側で無視されるため，同じ期間に何度実行してもよい．
+#[derive(new)]
+pub struct InterestAccrualJob<R: BankAccountRepository<Error = InfraError>> {
+    repo: R,
+    pool: <R::Transaction as Transaction>::Pool,
+}
+
+impl<R> InterestAccrualJob<R>
+where
+    R: BankAccountRepository<Error = InfraError>,
+    R::Transaction: LedgerTransaction,
+{
+    /// as_ofを含む月の利息を全てのアカウントに計上する
+    pub async fn run(&self, as_of: NaiveDate) -> Result<InterestAccrualReport, ApplicationError> {
+        let mut report = InterestAccrualReport::default();
+
+        for account_id in self.repo.find_all_ids(None).await? {
+            match self.accrue(account_id, as_of).await {
+                Ok(events) => report
+                    .accrued
+                    .extend(events.into_iter().filter_map(|event| match event {
+                        BankAccountEvent::InterestAccruedEvent(e) => Some(e),
+                        _ => None,
+                    })),
+                Err(ApplicationError::DomainError(e)) => {
+                    info!("Skip interest accrual of {}: {e}", account_id.to_uuid());
+                    report.skipped.push((account_id, e));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(report)
+    }
+    /// 一つのアカウントに利息を計上する
+    async fn accrue(
+        &self,
+        account_id: BankAccountId,
+        as_of: NaiveDate,
+    ) -> Result<Vec<BankAccountEvent>, ApplicationError> {
+        let transaction = R::Transaction::begin(&self.pool).await?;
+
+        let mut bank_account = self
+            .repo
+            .find_by_id_for_update(account_id, &transaction)
+            .await?;
+        let rate = bank_account.annual_interest_rate();
+        bank_account.accrue_interest(rate, as_of)?;
+
+        let events = bank_account.domain_events_mut().take();
+        self.repo.edit(bank_account, Some(&transaction)).await?;
+
+        transaction.record_ledger(&events).await?;
+        transaction.commit().await?;
+
+        Ok(events)
+    }
+}
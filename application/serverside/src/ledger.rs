@@ -0,0 +1,41 @@
+use common::read_models::ledger_entry;
+use common::ApplicationError;
+use domain::events::bank_account_events::BankAccountEvent;
+use domain::repositories::Transaction;
+use infrastructure::transactions::DbTransaction;
+use infrastructure::InfraError;
+
+use chrono::Utc;
+use sea_orm::EntityTrait;
+
+/// BankAccountEventから口座の記帳を作成し，コマンドと同じトランザクションで保存する．
+/// イベントバスの購読者は並行に実行され順序が保たれないため，コマンドの結果のイベントをまとめて順に記帳する
+#[async_trait::async_trait]
+pub trait LedgerTransaction: Transaction<Error = InfraError> {
+    /// イベントのうち残高の変わるものを記帳する
+    async fn record_ledger(&self, events: &[BankAccountEvent]) -> Result<(), ApplicationError>;
+}
+
+#[async_trait::async_trait]
+impl LedgerTransaction for DbTransaction {
+    async fn record_ledger(&self, events: &[BankAccountEvent]) -> Result<(), ApplicationError> {
+        let recorded_at = Utc::now();
+        let entries = events
+            .iter()
+            .filter_map(|event| {
+                ledger_entry::ActiveModel::from_event(event, recorded_at).transpose()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        ledger_entry::Entity::insert_many(entries)
+            .exec(self.inner())
+            .await
+            .map_err(Into::<InfraError>::into)?;
+
+        Ok(())
+    }
+}
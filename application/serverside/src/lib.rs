@@ -1,4 +1,8 @@
 pub mod api_handlers;
+pub mod audit;
 pub mod command_handlers;
 pub mod event_handlers;
+pub mod jobs;
+pub mod ledger;
 pub mod query_handlers;
+pub mod sagas;
@@ -2,6 +2,7 @@ use common::query_statement::QueryStatement;
 use common::ApplicationError;
 use infrastructure::InfraError;
 
+use futures::{Stream, StreamExt};
 use sea_orm::{DatabaseConnection, FromQueryResult};
 use std::marker::PhantomData;
 
@@ -43,3 +44,25 @@ impl<T: FromQueryResult> QueryHandler<T> {
         Ok(res)
     }
 }
+
+impl<T: FromQueryResult + Send + 'static> QueryHandler<T> {
+    /// クエリの結果をストリームとして取得．全ての結果をメモリに保持しない．
+    pub fn handle_query_stream(
+        &self,
+        query_stmt: QueryStatement,
+    ) -> impl Stream<Item = Result<T, ApplicationError>> + Send + 'static {
+        // ストリームがコネクションを借用するため，クローンしたものをストリーム内部に移動する
+        let conn = self.conn.clone();
+
+        async_stream::try_stream! {
+            let mut stream = T::find_by_statement(query_stmt.statement())
+                .stream(&conn)
+                .await
+                .map_err(Into::<InfraError>::into)?;
+
+            while let Some(res) = stream.next().await {
+                yield res.map_err(Into::<InfraError>::into)?;
+            }
+        }
+    }
+}
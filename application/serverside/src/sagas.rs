@@ -0,0 +1,87 @@
+pub mod atm_deposit_saga;
+pub mod check_clearing_saga;
+
+use common::ApplicationError;
+use domain::repositories::SagaRepository;
+use infrastructure::InfraError;
+
+use ddd_cqrs_core::{Saga, SagaInstance};
+
+use chrono::{DateTime, Duration, Utc};
+use derive_new::new;
+use sea_orm::prelude::Uuid;
+use std::marker::PhantomData;
+use tracing::info;
+
+/// サガの状態の保存と取得．サガは保存した版を条件として保存し，
+/// 同じサガを複数の経路(サガを進めるジョブの並行した実行など)から同時に進めないようにする
+#[derive(new)]
+pub struct SagaStore<S: Saga, SR: SagaRepository<Error = InfraError>> {
+    saga_repo: SR,
+    #[new(default)]
+    saga_type: PhantomData<fn() -> S>,
+}
+
+impl<S: Saga, SR: SagaRepository<Error = InfraError>> SagaStore<S, SR> {
+    /// サガを開始して保存する．サガを開始したコマンドのトランザクションで保存し，
+    /// コマンドのみが反映されてサガが失われないようにする．保存したサガはジョブが進める
+    pub async fn start(
+        &self,
+        saga: S,
+        now: DateTime<Utc>,
+        timeout: Option<Duration>,
+        transaction: Option<&SR::Transaction>,
+    ) -> Result<SagaInstance<S>, ApplicationError> {
+        let instance = SagaInstance::start(
+            Uuid::new_v4(),
+            saga,
+            now,
+            timeout.map(|timeout| now + timeout),
+        );
+        self.saga_repo
+            .save(instance.to_record().map_err(InfraError::from)?, transaction)
+            .await?;
+
+        Ok(instance)
+    }
+    /// 保存されている最新のサガを取得する
+    pub async fn find_by_id(&self, id: Uuid) -> Result<SagaInstance<S>, ApplicationError> {
+        let record = self.saga_repo.find_by_id(id, None).await?;
+        Ok(SagaInstance::from_record(record).map_err(InfraError::from)?)
+    }
+    /// 次に進める時刻を過ぎた自動で進める状態のサガ．処理する前にsaveで確保する必要がある
+    pub async fn find_due(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<SagaInstance<S>>, ApplicationError> {
+        let records = self.saga_repo.find_due(S::SAGA_TYPE, now, None).await?;
+
+        Ok(records
+            .into_iter()
+            .map(SagaInstance::from_record)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(InfraError::from)?)
+    }
+    /// 読み込んだ時点の版のままの場合のみサガを次の版として保存する．保存した場合はtrue．
+    /// 他の経路が既にサガを進めていた場合はfalseを返し，このインスタンスを破棄しなければならない
+    pub async fn save(
+        &self,
+        instance: &mut SagaInstance<S>,
+        transaction: Option<&SR::Transaction>,
+    ) -> Result<bool, ApplicationError> {
+        let expected_version = instance.next_version();
+        let saved = self
+            .saga_repo
+            .update(
+                instance.to_record().map_err(InfraError::from)?,
+                expected_version,
+                transaction,
+            )
+            .await?;
+
+        if !saved {
+            info!("{} {} was already advanced.", S::SAGA_TYPE, instance.id());
+        }
+        Ok(saved)
+    }
+}
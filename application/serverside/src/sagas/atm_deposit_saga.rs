@@ -0,0 +1,153 @@
+use crate::event_handlers::atm_event_handlers::AtmEventBus;
+use common::commands::atm_commands::ChargeAtmCashCommand;
+use common::ApplicationError;
+use domain::aggregates::atm::{AtmId, NoteBreakdown};
+use domain::aggregates::bank_account::BankAccountId;
+use domain::repositories::{AtmRepository, SagaRepository, Transaction};
+use infrastructure::InfraError;
+
+use super::SagaStore;
+use config::CONFIG;
+use ddd_cqrs_core::{Aggregate, Saga, SagaInstance, SagaStatus, SagaStep};
+
+use chrono::{DateTime, Duration, Utc};
+use derive_new::new;
+use sea_orm::prelude::Uuid;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+// -------------------------------------------------------------------------------------------------
+// AtmDepositSaga
+
+/// 口座への入金からAtmのカセットへのチャージまでのサガ．
+/// 口座への入金のトランザクションで開始するため，入金は確定しており現金もAtmにある
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, new)]
+pub struct AtmDepositSaga {
+    pub account_id: BankAccountId,
+    pub atm_id: AtmId,
+    /// 入金された現金の内訳．額面の大きい紙幣から順に分けたもの
+    pub notes: NoteBreakdown,
+}
+
+/// AtmDepositSagaが受け取るイベント
+#[derive(Debug, Clone, PartialEq)]
+pub enum AtmDepositSagaEvent {
+    /// 口座への入金を確認した
+    Deposited,
+}
+
+impl AtmDepositSaga {
+    fn charge(&self) -> ChargeAtmCashCommand {
+        ChargeAtmCashCommand {
+            atm_id: self.atm_id,
+            notes: self.notes.clone(),
+        }
+    }
+}
+
+impl Saga for AtmDepositSaga {
+    type Event = AtmDepositSagaEvent;
+    type Command = ChargeAtmCashCommand;
+    const SAGA_TYPE: &'static str = "AtmDepositSaga";
+
+    fn handle_event(&mut self, event: &Self::Event) -> SagaStep<Self::Command> {
+        match event {
+            AtmDepositSagaEvent::Deposited => SagaStep::Complete(vec![self.charge()]),
+        }
+    }
+    fn on_timeout(&mut self) -> SagaStep<Self::Command> {
+        SagaStep::Complete(vec![self.charge()])
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// AtmDepositSagaManager
+
+/// 保存されたAtmDepositSagaを進める．チャージとサガの終了を一つのトランザクションで保存し，
+/// 同じサガを同時に進めた場合も一度だけチャージする
+#[derive(new)]
+pub struct AtmDepositSagaManager<
+    SR: SagaRepository<Error = InfraError>,
+    A: AtmRepository<Error = InfraError, Transaction = SR::Transaction>,
+> {
+    saga_store: SagaStore<AtmDepositSaga, SR>,
+    atm_repo: A,
+    pool: <SR::Transaction as Transaction>::Pool,
+    atm_event_bus: Arc<AtmEventBus>,
+}
+
+impl<SR, A> AtmDepositSagaManager<SR, A>
+where
+    SR: SagaRepository<Error = InfraError>,
+    A: AtmRepository<Error = InfraError, Transaction = SR::Transaction>,
+{
+    /// 次に進める時刻を過ぎたサガのチャージを行う．サガごとのidと，進めた後の進行状況またはエラーを返す．
+    /// 他の経路が先にチャージしたサガは含まない
+    pub async fn run_due(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<(Uuid, Result<SagaStatus, ApplicationError>)>, ApplicationError> {
+        let instances = self.saga_store.find_due(now).await?;
+
+        let mut results = Vec::with_capacity(instances.len());
+        for mut instance in instances.into_iter() {
+            let saga_id = instance.id();
+            instance.handle_event(&AtmDepositSagaEvent::Deposited);
+
+            match self.charge(instance.clone()).await {
+                Ok(Some(status)) => results.push((saga_id, Ok(status))),
+                Ok(None) => {}
+                Err(e) => {
+                    // Atmが拒否した場合はFailedとし，それ以外のエラーの場合は時間をおいて再びチャージする
+                    match e {
+                        ApplicationError::DomainError(_) => instance.fail(),
+                        _ => instance
+                            .schedule(now + Duration::seconds(CONFIG.SAGA_RETRY_INTERVAL_SECS)),
+                    }
+                    self.saga_store.save(&mut instance, None).await?;
+                    results.push((saga_id, Err(e)));
+                }
+            }
+        }
+
+        Ok(results)
+    }
+    /// サガのコマンドを実行し，終了したサガとともに保存する．他の経路が先に進めていた場合はNone
+    async fn charge(
+        &self,
+        mut instance: SagaInstance<AtmDepositSaga>,
+    ) -> Result<Option<SagaStatus>, ApplicationError> {
+        let transaction = SR::Transaction::begin(&self.pool).await?;
+
+        let mut events = Vec::new();
+        for command in instance.pending_commands().to_vec().into_iter() {
+            let ChargeAtmCashCommand { atm_id, notes } = command;
+
+            let mut atm = self
+                .atm_repo
+                .find_by_id_for_update(atm_id, &transaction)
+                .await?;
+            atm.charge_cash(notes)?;
+
+            events.extend(atm.domain_events_mut().take());
+            self.atm_repo.edit(atm, Some(&transaction)).await?;
+        }
+
+        instance.finish();
+        if !self
+            .saga_store
+            .save(&mut instance, Some(&transaction))
+            .await?
+        {
+            transaction.rollback().await?;
+            return Ok(None);
+        }
+        transaction.commit().await?;
+
+        for event in events.into_iter() {
+            self.atm_event_bus.dispatch_event(event);
+        }
+
+        Ok(Some(instance.status()))
+    }
+}
@@ -0,0 +1,521 @@
+use common::commands::bank_account_commands::{ClearCheckCommand, RefundBouncedCheckCommand};
+use common::ApplicationError;
+use domain::aggregates::bank_account::{BankAccountId, CheckNumber};
+use domain::aggregates::BankAccount;
+use domain::repositories::SagaRepository;
+use domain::Money;
+use infrastructure::check_clearing::{
+    CheckClearingRequest, CheckClearingResult, CheckClearingService,
+};
+use infrastructure::InfraError;
+
+use super::SagaStore;
+use config::CONFIG;
+use ddd_cqrs_core::{HandleCommand, Saga, SagaInstance, SagaStatus, SagaStep};
+
+use chrono::{DateTime, Duration, Utc};
+use derive_new::new;
+use sea_orm::prelude::Uuid;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+// -------------------------------------------------------------------------------------------------
+// CheckClearingSaga
+
+/// 小切手の発行から決済・不渡りの返金までのサガ．
+/// 期限までに決済サービスの結果が得られない場合は，決済されている可能性があるため返金せずに手動での確認を待つ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, new)]
+pub struct CheckClearingSaga {
+    pub account_id: BankAccountId,
+    pub check_number: CheckNumber,
+    pub amount: Money,
+}
+
+/// CheckClearingSagaが発行するコマンド．どちらも同じ小切手に対して何度実行してもよい
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CheckClearingSagaCommand {
+    ClearCheck(ClearCheckCommand),
+    RefundBouncedCheck(RefundBouncedCheckCommand),
+}
+
+impl CheckClearingSaga {
+    /// 決済サービスへのリクエスト．同じ小切手に対しては同じ冪等キーとなる
+    pub fn request(&self) -> CheckClearingRequest {
+        CheckClearingRequest::new(self.account_id, self.check_number.clone(), self.amount)
+    }
+    fn refund(&self, reason: String) -> CheckClearingSagaCommand {
+        CheckClearingSagaCommand::RefundBouncedCheck(RefundBouncedCheckCommand {
+            account_id: self.account_id,
+            check_number: self.check_number.clone(),
+            amount: self.amount,
+            reason,
+        })
+    }
+}
+
+impl Saga for CheckClearingSaga {
+    type Event = CheckClearingResult;
+    type Command = CheckClearingSagaCommand;
+    const SAGA_TYPE: &'static str = "CheckClearingSaga";
+
+    fn handle_event(&mut self, event: &Self::Event) -> SagaStep<Self::Command> {
+        match event {
+            CheckClearingResult::Cleared => {
+                SagaStep::Complete(vec![CheckClearingSagaCommand::ClearCheck(
+                    ClearCheckCommand {
+                        account_id: self.account_id,
+                        check_number: self.check_number.clone(),
+                        amount: self.amount,
+                    },
+                )])
+            }
+            CheckClearingResult::Bounced { reason } => {
+                SagaStep::Compensate(vec![self.refund(reason.clone())])
+            }
+        }
+    }
+    fn on_timeout(&mut self) -> SagaStep<Self::Command> {
+        SagaStep::Suspend
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// CheckClearingSagaManager
+
+/// 保存されたCheckClearingSagaを進める．決済サービスに結果を問い合わせ，サガが発行したコマンドを実行する
+#[derive(new)]
+pub struct CheckClearingSagaManager<S: CheckClearingService, SR: SagaRepository<Error = InfraError>>
+{
+    saga_store: SagaStore<CheckClearingSaga, SR>,
+    service: S,
+    clear_check_handler: Box<
+        dyn HandleCommand<
+            Command = ClearCheckCommand,
+            Aggregate = BankAccount,
+            Error = ApplicationError,
+        >,
+    >,
+    refund_bounced_check_handler: Box<
+        dyn HandleCommand<
+            Command = RefundBouncedCheckCommand,
+            Aggregate = BankAccount,
+            Error = ApplicationError,
+        >,
+    >,
+}
+
+impl<S: CheckClearingService, SR: SagaRepository<Error = InfraError>>
+    CheckClearingSagaManager<S, SR>
+{
+    /// 次に進める時刻を過ぎたサガを確保して進める．サガごとのidと，進めた後の進行状況またはエラーを返す．
+    /// 他の経路が先に確保したサガは含まない
+    pub async fn run_due(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<(Uuid, Result<SagaStatus, ApplicationError>)>, ApplicationError> {
+        let instances = self.saga_store.find_due(now).await?;
+
+        let mut results = Vec::with_capacity(instances.len());
+        for mut instance in instances.into_iter() {
+            // 中断した場合は確保した時間が過ぎてから再び進める
+            instance.schedule(now + Duration::seconds(CONFIG.SAGA_LEASE_SECS));
+            if !self.saga_store.save(&mut instance, None).await? {
+                continue;
+            }
+
+            let saga_id = instance.id();
+            match self.advance(instance, now).await {
+                Ok(Some(status)) => results.push((saga_id, Ok(status))),
+                Ok(None) => {}
+                Err(e) => results.push((saga_id, Err(e))),
+            }
+        }
+
+        Ok(results)
+    }
+    /// 確保したサガを進める．他の経路が先に進めていた場合はNone
+    async fn advance(
+        &self,
+        mut instance: SagaInstance<CheckClearingSaga>,
+        now: DateTime<Utc>,
+    ) -> Result<Option<SagaStatus>, ApplicationError> {
+        if !instance.is_executing() {
+            let request = instance.saga().request();
+            match self.service.clear_check(&request).await {
+                Ok(result) => {
+                    if let CheckClearingResult::Bounced { reason } = &result {
+                        info!("Check {} bounced: {reason}", request.check_number);
+                    }
+                    instance.handle_event(&result);
+                }
+                // 期限を過ぎても結果が得られない場合は，決済されている可能性があるため返金しない
+                Err(e) if instance.time_out(now) => {
+                    info!("CheckClearingSaga {} timed out: {e}", instance.id());
+                }
+                // 決済サービスを呼び出せない場合は時間をおいて再び問い合わせる
+                Err(e) => {
+                    instance.schedule(now + Duration::seconds(CONFIG.SAGA_RETRY_INTERVAL_SECS));
+                    self.saga_store.save(&mut instance, None).await?;
+                    return Err(e.into());
+                }
+            }
+
+            // 実行するコマンドを保存してから実行し，中断した場合も再び実行できるようにする
+            if !self.saga_store.save(&mut instance, None).await? {
+                return Ok(None);
+            }
+        }
+
+        if instance.is_executing() {
+            self.execute(&mut instance, now).await?;
+            if !self.saga_store.save(&mut instance, None).await? {
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(instance.status()))
+    }
+    /// 保存したコマンドを順に実行し，実行し終えた場合はサガを終了した状態にする．
+    /// コマンドが拒否された場合はFailedとし，それ以外のエラーの場合は時間をおいて再び実行する
+    async fn execute(
+        &self,
+        instance: &mut SagaInstance<CheckClearingSaga>,
+        now: DateTime<Utc>,
+    ) -> Result<(), ApplicationError> {
+        for command in instance.pending_commands().to_vec().into_iter() {
+            let res = match command {
+                CheckClearingSagaCommand::ClearCheck(command) => {
+                    self.clear_check_handler.handle_command(command).await
+                }
+                CheckClearingSagaCommand::RefundBouncedCheck(command) => {
+                    self.refund_bounced_check_handler
+                        .handle_command(command)
+                        .await
+                }
+            };
+
+            // 後続のイベントは現在購読者がいないため，ディスパッチしない．記帳はコマンドのトランザクションで行われる
+            if let Err(e) = res {
+                match e {
+                    ApplicationError::DomainError(_) => instance.fail(),
+                    _ => {
+                        instance.schedule(now + Duration::seconds(CONFIG.SAGA_RETRY_INTERVAL_SECS))
+                    }
+                }
+                self.saga_store.save(instance, None).await?;
+                return Err(e);
+            }
+        }
+
+        instance.finish();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CheckClearingSaga, CheckClearingSagaManager};
+    use crate::sagas::SagaStore;
+    use common::ApplicationError;
+    use domain::aggregates::bank_account::BankAccountId;
+    use domain::aggregates::BankAccount;
+    use domain::events::bank_account_events::BankAccountEvent;
+    use domain::repositories::SagaRepository;
+    use infrastructure::check_clearing::{
+        CheckClearingRequest, CheckClearingResult, CheckClearingService,
+    };
+    use infrastructure::transactions::MockTransaction;
+    use infrastructure::InfraError;
+
+    use config::CONFIG;
+    use ddd_cqrs_core::{HandleCommand, SagaRecord, SagaStatus};
+
+    use chrono::{DateTime, Duration, Utc};
+    use sea_orm::prelude::Uuid;
+    use std::collections::HashMap;
+    use std::marker::PhantomData;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// メモリ上のサガのリポジトリ．各操作の前に他のタスクに実行を譲り，操作自体は不可分に行う
+    #[derive(Default)]
+    struct MemorySagaRepository(Mutex<HashMap<Uuid, SagaRecord>>);
+
+    #[async_trait::async_trait]
+    impl SagaRepository for MemorySagaRepository {
+        type Error = InfraError;
+        type Transaction = MockTransaction;
+
+        async fn save<'t>(
+            &self,
+            record: SagaRecord,
+            _: Option<&'t Self::Transaction>,
+        ) -> Result<(), Self::Error> {
+            tokio::task::yield_now().await;
+            self.0.lock().unwrap().insert(record.id, record);
+            Ok(())
+        }
+        async fn update<'t>(
+            &self,
+            record: SagaRecord,
+            expected_version: i64,
+            _: Option<&'t Self::Transaction>,
+        ) -> Result<bool, Self::Error> {
+            tokio::task::yield_now().await;
+            let mut records = self.0.lock().unwrap();
+            match records.get_mut(&record.id) {
+                Some(saved) if saved.version == expected_version => {
+                    *saved = record;
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+        async fn find_by_id<'t>(
+            &self,
+            id: Uuid,
+            _: Option<&'t Self::Transaction>,
+        ) -> Result<SagaRecord, Self::Error> {
+            tokio::task::yield_now().await;
+            self.0
+                .lock()
+                .unwrap()
+                .get(&id)
+                .cloned()
+                .ok_or_else(|| InfraError::RecordNotFoundError(id.to_string()))
+        }
+        async fn find_due<'t>(
+            &self,
+            saga_type: &'static str,
+            now: DateTime<Utc>,
+            _: Option<&'t Self::Transaction>,
+        ) -> Result<Vec<SagaRecord>, Self::Error> {
+            tokio::task::yield_now().await;
+            Ok(self
+                .0
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|record| {
+                    record.saga_type == saga_type
+                        && record.status.is_active()
+                        && record.next_attempt_at <= now
+                })
+                .cloned()
+                .collect())
+        }
+    }
+
+    /// 指定した結果を返し，呼ばれた回数を数える決済サービス．結果が無い場合は通信の失敗とする
+    #[derive(Clone, Default)]
+    struct StubClearingService {
+        result: Arc<Mutex<Option<CheckClearingResult>>>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl CheckClearingService for StubClearingService {
+        async fn clear_check(
+            &self,
+            _: &CheckClearingRequest,
+        ) -> Result<CheckClearingResult, InfraError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            self.result
+                .lock()
+                .unwrap()
+                .clone()
+                .ok_or_else(|| InfraError::ExternalServiceError("Connection refused.".to_string()))
+        }
+    }
+
+    /// 呼ばれた回数を数えるコマンドハンドラ．最初のfailures回はインフラのエラーとする
+    struct CountingHandler<C> {
+        calls: Arc<AtomicUsize>,
+        failures: usize,
+        command_type: PhantomData<fn(C)>,
+    }
+
+    impl<C> CountingHandler<C> {
+        fn boxed(calls: Arc<AtomicUsize>, failures: usize) -> Box<Self> {
+            Box::new(Self {
+                calls,
+                failures,
+                command_type: PhantomData,
+            })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl<C: Send + 'static> HandleCommand for CountingHandler<C> {
+        type Command = C;
+        type Aggregate = BankAccount;
+        type Error = ApplicationError;
+
+        async fn handle_command(
+            &self,
+            _: Self::Command,
+        ) -> Result<Vec<BankAccountEvent>, Self::Error> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err(ApplicationError::OtherInfraError(
+                    "Connection refused.".to_string(),
+                ));
+            }
+            Ok(Vec::new())
+        }
+    }
+
+    struct Fixture {
+        saga_manager: CheckClearingSagaManager<StubClearingService, MemorySagaRepository>,
+        service: StubClearingService,
+        clear_calls: Arc<AtomicUsize>,
+        refund_calls: Arc<AtomicUsize>,
+    }
+
+    impl Fixture {
+        /// 返金の最初のrefund_failures回が失敗するサガのマネージャを作成する
+        fn new(refund_failures: usize) -> Self {
+            let service = StubClearingService::default();
+            let clear_calls = Arc::new(AtomicUsize::new(0));
+            let refund_calls = Arc::new(AtomicUsize::new(0));
+            let saga_manager = CheckClearingSagaManager::new(
+                SagaStore::new(MemorySagaRepository::default()),
+                service.clone(),
+                CountingHandler::boxed(clear_calls.clone(), 0),
+                CountingHandler::boxed(refund_calls.clone(), refund_failures),
+            );
+
+            Self {
+                saga_manager,
+                service,
+                clear_calls,
+                refund_calls,
+            }
+        }
+        /// 小切手の発行のトランザクションと同様にサガを開始する
+        async fn start(&self, now: DateTime<Utc>) -> Uuid {
+            let saga = CheckClearingSaga::new(
+                BankAccountId::generate(),
+                "0001".parse().unwrap(),
+                "300 JPY".parse().unwrap(),
+            );
+            self.saga_manager
+                .saga_store
+                .start(
+                    saga,
+                    now,
+                    Some(Duration::seconds(CONFIG.CHECK_CLEARING_SAGA_TIMEOUT_SECS)),
+                    None,
+                )
+                .await
+                .unwrap()
+                .id()
+        }
+        fn respond(&self, result: Option<CheckClearingResult>) {
+            *self.service.result.lock().unwrap() = result;
+        }
+        async fn status(&self, saga_id: Uuid) -> SagaStatus {
+            self.saga_manager
+                .saga_store
+                .find_by_id(saga_id)
+                .await
+                .unwrap()
+                .status()
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_runs_clear_once() {
+        let fixture = Fixture::new(0);
+        let now = Utc::now();
+        let saga_id = fixture.start(now).await;
+        fixture.respond(Some(CheckClearingResult::Cleared));
+
+        // 両方が同じサガを読み込んでから確保を競う
+        let (first, second) = tokio::join!(
+            fixture.saga_manager.run_due(now),
+            fixture.saga_manager.run_due(now)
+        );
+        let advanced = first.unwrap().len() + second.unwrap().len();
+
+        // 確保した一方のみが決済サービスに問い合わせ，決済する
+        assert_eq!(advanced, 1);
+        assert_eq!(fixture.service.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fixture.clear_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fixture.refund_calls.load(Ordering::SeqCst), 0);
+        assert_eq!(fixture.status(saga_id).await, SagaStatus::Completed);
+        assert!(fixture.saga_manager.run_due(now).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn timed_out_check_is_suspended() {
+        let fixture = Fixture::new(0);
+        let now = Utc::now();
+        let saga_id = fixture.start(now).await;
+
+        // 決済サービスを呼び出せない場合は実行中のまま時間をおいて再び問い合わせる
+        let results = fixture.saga_manager.run_due(now).await.unwrap();
+        assert!(matches!(results[..], [(id, Err(_))] if id == saga_id));
+        assert_eq!(fixture.status(saga_id).await, SagaStatus::Running);
+        assert!(fixture.saga_manager.run_due(now).await.unwrap().is_empty());
+
+        // 期限を過ぎても結果が得られない場合は，返金せずに手動での確認を待つ
+        let after_deadline = now + Duration::seconds(CONFIG.CHECK_CLEARING_SAGA_TIMEOUT_SECS + 1);
+        let results = fixture.saga_manager.run_due(after_deadline).await.unwrap();
+        assert!(matches!(results[..], [(id, Ok(SagaStatus::Suspended))] if id == saga_id));
+        assert_eq!(fixture.service.calls.load(Ordering::SeqCst), 2);
+
+        // 手動での確認を待っているサガは進めない
+        fixture.respond(Some(CheckClearingResult::Cleared));
+        assert!(fixture
+            .saga_manager
+            .run_due(after_deadline + Duration::days(1))
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(fixture.clear_calls.load(Ordering::SeqCst), 0);
+        assert_eq!(fixture.refund_calls.load(Ordering::SeqCst), 0);
+        assert_eq!(fixture.status(saga_id).await, SagaStatus::Suspended);
+    }
+
+    #[tokio::test]
+    async fn result_after_deadline_is_applied() {
+        let fixture = Fixture::new(0);
+        let now = Utc::now();
+        let saga_id = fixture.start(now).await;
+
+        // 期限を過ぎていても決済サービスから結果が得られた場合は，その結果に従う
+        fixture.respond(Some(CheckClearingResult::Cleared));
+        let after_deadline = now + Duration::seconds(CONFIG.CHECK_CLEARING_SAGA_TIMEOUT_SECS + 1);
+        let results = fixture.saga_manager.run_due(after_deadline).await.unwrap();
+        assert!(matches!(results[..], [(id, Ok(SagaStatus::Completed))] if id == saga_id));
+
+        assert_eq!(fixture.clear_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fixture.refund_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn interrupted_refund_is_retried() {
+        let fixture = Fixture::new(1);
+        let now = Utc::now();
+        let saga_id = fixture.start(now).await;
+        fixture.respond(Some(CheckClearingResult::Bounced {
+            reason: "Insufficient funds".to_string(),
+        }));
+
+        // 返金に失敗しても補償中のまま残し，返金したとは記録しない
+        let results = fixture.saga_manager.run_due(now).await.unwrap();
+        assert!(matches!(results[..], [(id, Err(_))] if id == saga_id));
+        assert_eq!(fixture.status(saga_id).await, SagaStatus::Compensating);
+
+        // 時間をおいて，決済サービスに問い合わせ直さずに保存した返金のコマンドを再び実行する
+        fixture.respond(None);
+        let retry_at = now + Duration::seconds(CONFIG.SAGA_RETRY_INTERVAL_SECS);
+        let results = fixture.saga_manager.run_due(retry_at).await.unwrap();
+        assert!(matches!(results[..], [(id, Ok(SagaStatus::Compensated))] if id == saga_id));
+
+        assert_eq!(fixture.service.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fixture.refund_calls.load(Ordering::SeqCst), 2);
+        assert_eq!(fixture.clear_calls.load(Ordering::SeqCst), 0);
+    }
+}
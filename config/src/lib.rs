@@ -1,6 +1,58 @@
+use rust_decimal::Decimal;
+
 #[allow(non_snake_case)]
 pub struct Config {
-    pub BALANCE_UPPER_LIM: f64,
+    pub BALANCE_UPPER_LIM: Decimal,
+    /// Standardの口座の引き出し限度額(集計期間あたり)
+    pub DAILY_WITHDRAWAL_LIMIT_STANDARD: Decimal,
+    /// Premiumの口座の引き出し限度額(集計期間あたり)
+    pub DAILY_WITHDRAWAL_LIMIT_PREMIUM: Decimal,
+    /// Atmごとの引き出し限度額(集計期間あたり)
+    pub ATM_DAILY_WITHDRAWAL_LIMIT: Decimal,
+    /// 引き出し限度額の集計期間(時間)
+    pub DAILY_WITHDRAWAL_WINDOW_HOURS: i64,
+    /// 当座貸越を利用した取引ごとの手数料
+    pub OVERDRAFT_FEE: Decimal,
+    /// Standardの口座の年利
+    pub INTEREST_RATE_STANDARD: Decimal,
+    /// Premiumの口座の年利
+    pub INTEREST_RATE_PREMIUM: Decimal,
+    /// 普通預金の引き出し回数の上限(集計期間あたり)
+    pub SAVINGS_WITHDRAWAL_COUNT_LIMIT: u64,
+    /// メールアドレスの変更を確認するトークンの有効期間(分)
+    pub EMAIL_CHANGE_TOKEN_TTL_MINUTES: i64,
+    /// この金額以上の引き出しをメールで知らせる
+    pub LARGE_WITHDRAWAL_ALERT_THRESHOLD: Decimal,
+    /// Atmの現金の総額がこの金額を下回った時に運用担当者に知らせる
+    pub ATM_CASH_LOW_THRESHOLD: Decimal,
+    /// Atmの運用担当者のメールアドレス
+    pub ATM_OPERATIONS_MAIL_ADDRESS: &'static str,
+    /// 送信するメールの送信元アドレス
+    pub MAIL_FROM_ADDRESS: &'static str,
+    /// 開発用にメールを書き出すディレクトリ
+    pub MAIL_DIR: &'static str,
+    /// SMTPでの1通の送信のタイムアウト(秒)
+    pub SMTP_TIMEOUT_SECS: u64,
+    /// 小切手決済サービスのURL
+    pub CHECK_CLEARING_URL: &'static str,
+    /// 小切手決済サービスへの1回のリクエストのタイムアウト(ミリ秒)
+    pub CHECK_CLEARING_TIMEOUT_MILLIS: u64,
+    /// 小切手決済サービスへのリトライの回数
+    pub CHECK_CLEARING_MAX_RETRIES: u32,
+    /// 小切手決済サービスへの最初のリトライまでの待ち時間(ミリ秒)
+    pub CHECK_CLEARING_RETRY_BACKOFF_MILLIS: u64,
+    /// 小切手決済サービスの呼び出しを遮断するまでの連続した失敗の回数
+    pub CHECK_CLEARING_FAILURE_THRESHOLD: u32,
+    /// 小切手決済サービスの呼び出しを遮断する時間(秒)
+    pub CHECK_CLEARING_COOLDOWN_SECS: u64,
+    /// 小切手の決済の結果を待つ期限(秒)．過ぎても結果が得られない場合は手動での確認を待つ
+    pub CHECK_CLEARING_SAGA_TIMEOUT_SECS: i64,
+    /// サガを進める経路がサガを確保する時間(秒)．過ぎても終了しない場合は中断したものとして再び進める
+    pub SAGA_LEASE_SECS: i64,
+    /// 決済サービスを呼び出せない場合などにサガを再び進めるまでの待ち時間(秒)
+    pub SAGA_RETRY_INTERVAL_SECS: i64,
+    /// test_apiでサガを進めるジョブを実行する間隔(ミリ秒)
+    pub SAGA_JOB_INTERVAL_MILLIS: u64,
     pub TEST_API_ADDR: &'static str,
     pub TEST_API_URL: &'static str,
 }
@@ -8,7 +60,32 @@ pub struct Config {
 impl Config {
     const fn init() -> Self {
         Self {
-            BALANCE_UPPER_LIM: 100_000_000.0,
+            BALANCE_UPPER_LIM: Decimal::from_parts(100_000_000, 0, 0, false, 0),
+            DAILY_WITHDRAWAL_LIMIT_STANDARD: Decimal::from_parts(500_000, 0, 0, false, 0),
+            DAILY_WITHDRAWAL_LIMIT_PREMIUM: Decimal::from_parts(2_000_000, 0, 0, false, 0),
+            ATM_DAILY_WITHDRAWAL_LIMIT: Decimal::from_parts(10_000_000, 0, 0, false, 0),
+            DAILY_WITHDRAWAL_WINDOW_HOURS: 24,
+            OVERDRAFT_FEE: Decimal::from_parts(300, 0, 0, false, 0),
+            INTEREST_RATE_STANDARD: Decimal::from_parts(1, 0, 0, false, 3),
+            INTEREST_RATE_PREMIUM: Decimal::from_parts(2, 0, 0, false, 3),
+            SAVINGS_WITHDRAWAL_COUNT_LIMIT: 3,
+            EMAIL_CHANGE_TOKEN_TTL_MINUTES: 30,
+            LARGE_WITHDRAWAL_ALERT_THRESHOLD: Decimal::from_parts(100_000, 0, 0, false, 0),
+            ATM_CASH_LOW_THRESHOLD: Decimal::from_parts(1_000_000, 0, 0, false, 0),
+            ATM_OPERATIONS_MAIL_ADDRESS: "atm-operations@example.com",
+            MAIL_FROM_ADDRESS: "noreply@example.com",
+            MAIL_DIR: "./mail",
+            SMTP_TIMEOUT_SECS: 10,
+            CHECK_CLEARING_URL: "http://127.0.0.1:8100",
+            CHECK_CLEARING_TIMEOUT_MILLIS: 3_000,
+            CHECK_CLEARING_MAX_RETRIES: 2,
+            CHECK_CLEARING_RETRY_BACKOFF_MILLIS: 200,
+            CHECK_CLEARING_FAILURE_THRESHOLD: 5,
+            CHECK_CLEARING_COOLDOWN_SECS: 30,
+            CHECK_CLEARING_SAGA_TIMEOUT_SECS: 3_600,
+            SAGA_LEASE_SECS: 60,
+            SAGA_RETRY_INTERVAL_SECS: 30,
+            SAGA_JOB_INTERVAL_MILLIS: 1_000,
             TEST_API_ADDR: "127.0.0.1:8000",
             TEST_API_URL: "http://127.0.0.1:8000",
         }
@@ -1,7 +1,9 @@
 mod aggregate;
 mod command;
 mod event;
+mod saga;
 
 pub use aggregate::Aggregate;
 pub use command::HandleCommand;
 pub use event::DomainEventList;
+pub use saga::{Saga, SagaInstance, SagaRecord, SagaRecordError, SagaStatus, SagaStep};
@@ -0,0 +1,424 @@
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+// -------------------------------------------------------------------------------------------------
+// SagaStatus
+
+/// サガの進行状況
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SagaStatus {
+    /// イベントを待っている
+    Running,
+    /// 正常に終了するためのコマンドを実行している
+    Completing,
+    /// 正常に終了した
+    Completed,
+    /// 補償コマンドを実行している
+    Compensating,
+    /// 補償コマンドにより取り消した
+    Compensated,
+    /// 結果を確定できないため，手動での確認を待っている
+    Suspended,
+    /// コマンドの実行に失敗したため，手動での対応が必要
+    Failed,
+}
+
+impl SagaStatus {
+    /// 自動で進める状態．中断した場合も再び進められる
+    pub const ACTIVE: [SagaStatus; 3] = [Self::Running, Self::Completing, Self::Compensating];
+
+    /// 終了した状態かどうか
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Completed | Self::Compensated | Self::Failed)
+    }
+    /// 自動で進める状態かどうか
+    pub fn is_active(&self) -> bool {
+        Self::ACTIVE.contains(self)
+    }
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "Running",
+            Self::Completing => "Completing",
+            Self::Completed => "Completed",
+            Self::Compensating => "Compensating",
+            Self::Compensated => "Compensated",
+            Self::Suspended => "Suspended",
+            Self::Failed => "Failed",
+        }
+    }
+}
+
+impl Display for SagaStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SagaStatus {
+    type Err = SagaRecordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Running" => Ok(Self::Running),
+            "Completing" => Ok(Self::Completing),
+            "Completed" => Ok(Self::Completed),
+            "Compensating" => Ok(Self::Compensating),
+            "Compensated" => Ok(Self::Compensated),
+            "Suspended" => Ok(Self::Suspended),
+            "Failed" => Ok(Self::Failed),
+            _ => Err(SagaRecordError::InvalidStatus(s.to_string())),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// SagaStep
+
+/// サガがイベントを処理した結果．次に実行するコマンドを持つ
+#[derive(Debug, Clone, PartialEq)]
+pub enum SagaStep<C> {
+    /// コマンドを実行し，次のイベントを待つ
+    Continue(Vec<C>),
+    /// コマンドを実行し，正常に終了する
+    Complete(Vec<C>),
+    /// 補償コマンドを実行し，それまでのステップを取り消す
+    Compensate(Vec<C>),
+    /// 結果を確定できないため，コマンドを実行せずに手動での確認を待つ
+    Suspend,
+}
+
+// -------------------------------------------------------------------------------------------------
+// Saga
+
+/// 複数のアグリゲイトにまたがる処理(サガ)が実装すべきトレイト．
+/// 状態の遷移のみを記述し，コマンドの実行と永続化はSagaInstanceを利用する側が行う
+pub trait Saga: Serialize + DeserializeOwned + Send + Sync {
+    type Event;
+    /// 発行するコマンド．実行前に中断しても再び実行できるように，サガとともに永続化する
+    type Command: Serialize + DeserializeOwned;
+    /// サガの種類．永続化した状態を区別するために利用する
+    const SAGA_TYPE: &'static str;
+
+    /// イベントを受け取って状態を進め，次に実行するコマンドを返す
+    fn handle_event(&mut self, event: &Self::Event) -> SagaStep<Self::Command>;
+    /// 期限までに終了しなかった場合に次に実行するコマンドを返す
+    fn on_timeout(&mut self) -> SagaStep<Self::Command>;
+}
+
+// -------------------------------------------------------------------------------------------------
+// SagaInstance
+
+/// 実行中のサガ．サガの状態に加えて進行状況と期限，実行を待っているコマンドを保持する．
+/// コマンドを実行する前に進行状況とコマンドを保存し，実行し終えてから終了した状態を保存する
+#[derive(Debug, Clone, PartialEq)]
+pub struct SagaInstance<S: Saga> {
+    id: Uuid,
+    status: SagaStatus,
+    /// 保存した版．保存するたびに一つ進め，複数の経路が同じサガを同時に進めないようにする
+    version: i64,
+    started_at: DateTime<Utc>,
+    deadline: Option<DateTime<Utc>>,
+    /// 次にサガを進める時刻．この時刻を過ぎても自動で進める状態のサガは中断したものとして再び進める
+    next_attempt_at: DateTime<Utc>,
+    saga: S,
+    pending_commands: Vec<S::Command>,
+}
+
+impl<S: Saga> SagaInstance<S> {
+    /// サガを開始する．deadlineを過ぎても終了しない場合はタイムアウトとしてサガの決めたステップに進める
+    pub fn start(
+        id: Uuid,
+        saga: S,
+        started_at: DateTime<Utc>,
+        deadline: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            id,
+            status: SagaStatus::Running,
+            version: 0,
+            started_at,
+            deadline,
+            next_attempt_at: started_at,
+            saga,
+            pending_commands: Vec::new(),
+        }
+    }
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+    pub fn status(&self) -> SagaStatus {
+        self.status
+    }
+    pub fn version(&self) -> i64 {
+        self.version
+    }
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+    pub fn deadline(&self) -> Option<DateTime<Utc>> {
+        self.deadline
+    }
+    pub fn next_attempt_at(&self) -> DateTime<Utc> {
+        self.next_attempt_at
+    }
+    pub fn saga(&self) -> &S {
+        &self.saga
+    }
+    /// 実行を待っているコマンド
+    pub fn pending_commands(&self) -> &[S::Command] {
+        &self.pending_commands
+    }
+    /// 発行したコマンドの実行を待っているかどうか
+    pub fn is_executing(&self) -> bool {
+        matches!(
+            self.status,
+            SagaStatus::Completing | SagaStatus::Compensating
+        ) || !self.pending_commands.is_empty()
+    }
+    /// イベントを処理して次に実行するコマンドを記録する．イベントを待っていない場合は何もせずfalseを返す
+    pub fn handle_event(&mut self, event: &S::Event) -> bool {
+        if self.status != SagaStatus::Running || self.is_executing() {
+            return false;
+        }
+
+        let step = self.saga.handle_event(event);
+        self.apply(step);
+        true
+    }
+    /// サガの決めたステップに応じて進行状況と実行するコマンドを変更する
+    fn apply(&mut self, step: SagaStep<S::Command>) {
+        match step {
+            SagaStep::Continue(commands) => self.pending_commands = commands,
+            SagaStep::Complete(commands) => {
+                self.status = SagaStatus::Completing;
+                self.pending_commands = commands;
+            }
+            SagaStep::Compensate(commands) => {
+                self.status = SagaStatus::Compensating;
+                self.pending_commands = commands;
+            }
+            SagaStep::Suspend => self.status = SagaStatus::Suspended,
+        }
+    }
+    /// 期限を過ぎているかどうか
+    pub fn is_timed_out(&self, now: DateTime<Utc>) -> bool {
+        self.status == SagaStatus::Running
+            && !self.is_executing()
+            && self.deadline.is_some_and(|deadline| deadline <= now)
+    }
+    /// 期限を過ぎている場合はサガの決めたステップに進める．進めた場合はtrue
+    pub fn time_out(&mut self, now: DateTime<Utc>) -> bool {
+        if !self.is_timed_out(now) {
+            return false;
+        }
+
+        let step = self.saga.on_timeout();
+        self.apply(step);
+        true
+    }
+    /// 記録したコマンドを実行し終えたことを記録し，終了するステップの場合は終了した状態にする
+    pub fn finish(&mut self) {
+        self.pending_commands.clear();
+        self.status = match self.status {
+            SagaStatus::Completing => SagaStatus::Completed,
+            SagaStatus::Compensating => SagaStatus::Compensated,
+            status => status,
+        };
+    }
+    /// コマンドの実行に失敗したことを記録する．実行できなかったコマンドは手動での対応のために残す
+    pub fn fail(&mut self) {
+        self.status = SagaStatus::Failed;
+    }
+    /// 次にサガを進める時刻を変更する
+    pub fn schedule(&mut self, next_attempt_at: DateTime<Utc>) {
+        self.next_attempt_at = next_attempt_at;
+    }
+    /// 自動で進める状態で，次に進める時刻を過ぎているかどうか
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.status.is_active() && self.next_attempt_at <= now
+    }
+    /// 版を一つ進め，進める前の版を返す．
+    /// 保存されている版が返した版と一致する場合のみ保存し，一致しない場合はこのインスタンスを破棄する
+    pub fn next_version(&mut self) -> i64 {
+        self.version += 1;
+        self.version - 1
+    }
+    /// 永続化用のレコードに変換する
+    pub fn to_record(&self) -> Result<SagaRecord, SagaRecordError> {
+        Ok(SagaRecord {
+            id: self.id,
+            saga_type: S::SAGA_TYPE.to_string(),
+            status: self.status,
+            version: self.version,
+            state: serde_json::to_value(&self.saga).map_err(SagaRecordError::State)?,
+            commands: serde_json::to_value(&self.pending_commands)
+                .map_err(SagaRecordError::State)?,
+            started_at: self.started_at,
+            deadline: self.deadline,
+            next_attempt_at: self.next_attempt_at,
+        })
+    }
+    /// 永続化したレコードから復元する
+    pub fn from_record(record: SagaRecord) -> Result<Self, SagaRecordError> {
+        let SagaRecord {
+            id,
+            saga_type,
+            status,
+            version,
+            state,
+            commands,
+            started_at,
+            deadline,
+            next_attempt_at,
+        } = record;
+
+        if saga_type != S::SAGA_TYPE {
+            return Err(SagaRecordError::SagaTypeMismatch {
+                expected: S::SAGA_TYPE,
+                found: saga_type,
+            });
+        }
+
+        Ok(Self {
+            id,
+            status,
+            version,
+            started_at,
+            deadline,
+            next_attempt_at,
+            saga: serde_json::from_value(state).map_err(SagaRecordError::State)?,
+            pending_commands: serde_json::from_value(commands).map_err(SagaRecordError::State)?,
+        })
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// SagaRecord
+
+/// サガの種類に依存しない永続化用のレコード．状態と実行を待っているコマンドはJSONとして保持する
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SagaRecord {
+    pub id: Uuid,
+    pub saga_type: String,
+    pub status: SagaStatus,
+    pub version: i64,
+    pub state: serde_json::Value,
+    pub commands: serde_json::Value,
+    pub started_at: DateTime<Utc>,
+    pub deadline: Option<DateTime<Utc>>,
+    pub next_attempt_at: DateTime<Utc>,
+}
+
+/// レコードとサガの変換時のエラー
+#[derive(Debug)]
+pub enum SagaRecordError {
+    SagaTypeMismatch {
+        expected: &'static str,
+        found: String,
+    },
+    InvalidStatus(String),
+    State(serde_json::Error),
+}
+
+impl Display for SagaRecordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SagaTypeMismatch { expected, found } => {
+                write!(f, "Saga type mismatch: expected {expected}, found {found}")
+            }
+            Self::InvalidStatus(status) => write!(f, "Invalid saga status: {status}"),
+            Self::State(e) => write!(f, "Invalid saga state: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SagaRecordError {}
+
+#[cfg(test)]
+mod test {
+    use super::{Saga, SagaInstance, SagaStatus, SagaStep};
+
+    use chrono::{Duration, TimeZone, Utc};
+    use serde::{Deserialize, Serialize};
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct TestSaga {
+        received: u32,
+    }
+
+    impl Saga for TestSaga {
+        type Event = bool;
+        type Command = String;
+        const SAGA_TYPE: &'static str = "TestSaga";
+
+        fn handle_event(&mut self, event: &bool) -> SagaStep<String> {
+            self.received += 1;
+            match (event, self.received) {
+                (true, 1) => SagaStep::Continue(vec!["next".to_string()]),
+                (true, _) => SagaStep::Complete(vec!["finish".to_string()]),
+                (false, _) => SagaStep::Compensate(vec!["undo".to_string()]),
+            }
+        }
+        fn on_timeout(&mut self) -> SagaStep<String> {
+            SagaStep::Suspend
+        }
+    }
+
+    #[test]
+    fn saga_transitions() {
+        let now = Utc.with_ymd_and_hms(2023, 8, 1, 0, 0, 0).unwrap();
+        let mut instance = SagaInstance::start(
+            Uuid::nil(),
+            TestSaga { received: 0 },
+            now,
+            Some(now + Duration::minutes(1)),
+        );
+        assert!(instance.is_due(now));
+
+        assert!(instance.handle_event(&true));
+        assert_eq!(instance.pending_commands(), ["next"]);
+        assert_eq!(instance.status(), SagaStatus::Running);
+        // コマンドを実行し終えるまでは次のイベントを処理せず，期限切れにもならない
+        assert!(!instance.handle_event(&true));
+        assert!(!instance.is_timed_out(now + Duration::minutes(1)));
+        instance.finish();
+        assert!(instance.pending_commands().is_empty());
+        assert_eq!(instance.status(), SagaStatus::Running);
+
+        assert!(instance.handle_event(&false));
+        assert_eq!(instance.status(), SagaStatus::Compensating);
+        assert_eq!(instance.next_version(), 0);
+
+        // レコードを経由しても実行を待っているコマンドと版は変わらない
+        let mut instance =
+            SagaInstance::<TestSaga>::from_record(instance.to_record().unwrap()).unwrap();
+        assert_eq!(instance.saga(), &TestSaga { received: 2 });
+        assert_eq!(instance.pending_commands(), ["undo"]);
+        assert_eq!(instance.version(), 1);
+        assert!(instance.is_due(now));
+
+        // 実行中でないサガはイベントを無視する
+        assert!(!instance.handle_event(&true));
+        instance.finish();
+        assert_eq!(instance.status(), SagaStatus::Compensated);
+        assert!(!instance.is_due(now));
+
+        let mut instance = SagaInstance::start(
+            Uuid::nil(),
+            TestSaga { received: 0 },
+            now,
+            Some(now + Duration::minutes(1)),
+        );
+        assert!(!instance.time_out(now));
+        assert!(instance.time_out(now + Duration::minutes(1)));
+        assert_eq!(instance.status(), SagaStatus::Suspended);
+        assert!(instance.pending_commands().is_empty());
+        assert!(!instance.is_due(now + Duration::minutes(1)));
+    }
+}
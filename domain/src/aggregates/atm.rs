@@ -1,11 +1,20 @@
 mod atm_location;
+mod atm_status;
+mod cassettes;
 
-use crate::error::{AtmError, DomainError};
-use crate::events::atm_events::AtmEvent;
+use crate::error::{AtmError, DomainError, MoneyError};
+use crate::events::atm_events::{self, AtmEvent};
 use crate::id::Id;
+use crate::money::{Currency, Money};
 
-pub use atm_location::AtmLocation;
+pub use atm_location::{AtmAddress, AtmLocation, GeoPoint};
+pub use atm_status::AtmStatus;
+pub use cassettes::{Cassettes, Denomination, NoteBreakdown};
 
+#[cfg(feature = "orm")]
+pub use cassettes::orm as cassette_orm;
+
+use config::CONFIG;
 use ddd_cqrs_core::{Aggregate, DomainEventList};
 
 use serde::{Deserialize, Serialize};
@@ -26,61 +35,221 @@ pub type AtmId = Id<AtmIdType>;
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct Atm {
     id: AtmId,
-    location: AtmLocation,
-    total_cash: f64,
+    /// 設置場所．住所と座標を登録する前から稼働していたAtmではNoneとなり，移設により登録するまで稼働できない
+    location: Option<AtmLocation>,
+    /// カセット内の紙幣の合計．クエリのためにカセットとは別に保持する
+    total_cash: Money,
+    /// 額面ごとのカセット
+    cassettes: Cassettes,
+    #[serde(default)]
+    status: AtmStatus,
     #[serde(skip)]
     events_list: DomainEventList<AtmEvent>,
 }
 
 impl Atm {
-    pub fn from_domains(location: AtmLocation, total_cash: f64) -> Self {
-        Atm {
+    /// 新しいAtmを登録する
+    pub fn from_domains(location: AtmLocation, cassettes: Cassettes) -> Result<Self, DomainError> {
+        let mut atm = Atm {
             id: AtmId::generate(),
-            location,
-            total_cash,
+            location: Some(location.clone()),
+            total_cash: cassettes.total()?,
+            cassettes,
+            status: AtmStatus::InService,
             events_list: DomainEventList::new(),
-        }
+        };
+
+        let event = atm_events::AtmRegisteredEvent {
+            atm_id: atm.id,
+            location,
+            notes: atm.cassettes.notes().clone(),
+            total_cash: atm.total_cash,
+        };
+        atm.domain_events_mut().push(event.into());
+        Ok(atm)
     }
-    pub fn from_primitives(location: String, total_cash: f64) -> Result<Self, DomainError> {
-        Ok(Atm {
-            id: AtmId::generate(),
-            location: AtmLocation::new(location),
-            total_cash,
-            events_list: DomainEventList::new(),
-        })
+    /// 現金の総額を額面の大きい紙幣から順にカセットに入れて登録する
+    pub fn from_primitives(location: AtmLocation, total_cash: Money) -> Result<Self, DomainError> {
+        let cassettes = Cassettes::new(
+            total_cash.currency(),
+            NoteBreakdown::from_amount(total_cash)?,
+        )?;
+        Self::from_domains(location, cassettes)
     }
     pub fn id(&self) -> AtmId {
         self.id
     }
-    pub fn location(&self) -> &AtmLocation {
-        &self.location
+    pub fn location(&self) -> Option<&AtmLocation> {
+        self.location.as_ref()
     }
-    pub fn total_cash(&self) -> f64 {
+    pub fn total_cash(&self) -> Money {
         self.total_cash
     }
+    pub fn cassettes(&self) -> &Cassettes {
+        &self.cassettes
+    }
+    /// Atmが扱う通貨
+    pub fn currency(&self) -> Currency {
+        self.cassettes.currency()
+    }
+    pub fn status(&self) -> AtmStatus {
+        self.status
+    }
+    /// 状態を遷移させる．遷移できない場合はエラー
+    fn transition_to(&mut self, to: AtmStatus) -> Result<(), DomainError> {
+        if self.status.can_transition_to(to) {
+            self.status = to;
+            Ok(())
+        } else {
+            Err(AtmError::InvalidStatusTransitionError {
+                from: self.status,
+                to,
+            }
+            .into())
+        }
+    }
+    /// 撤去済みのAtmは操作できない
+    fn ensure_not_decommissioned(&self) -> Result<(), DomainError> {
+        if self.status == AtmStatus::Decommissioned {
+            Err(AtmError::AtmDecommissionedError { atm_id: self.id }.into())
+        } else {
+            Ok(())
+        }
+    }
+    /// 預け入れ・引き出しができる状態かどうかを確認する
+    pub fn ensure_operational(&self) -> Result<(), DomainError> {
+        if self.status == AtmStatus::InService {
+            Ok(())
+        } else {
+            Err(AtmError::AtmNotOperationalError {
+                atm_id: self.id,
+                status: self.status,
+            }
+            .into())
+        }
+    }
+    /// 預け入れる現金がAtmの通貨かどうかを確認する．他の通貨の紙幣はカセットに入れられない
+    pub fn ensure_accepts(&self, amount: Money) -> Result<(), DomainError> {
+        if amount.currency() == self.currency() {
+            Ok(())
+        } else {
+            Err(MoneyError::CurrencyMismatchError {
+                expected: self.currency(),
+                actual: amount.currency(),
+            }
+            .into())
+        }
+    }
     // -------------------------------------------------------------------------------------------------
     // 以下がドメインロジック
 
-    /// Atmに現金をチャージ
-    pub fn charge_cash(&mut self, amount: f64) -> Result<(), DomainError> {
-        self.total_cash += amount;
+    /// Atmのカセットに紙幣をチャージ．停止中のAtmにもチャージできる
+    pub fn charge_cash(&mut self, notes: NoteBreakdown) -> Result<(), DomainError> {
+        self.ensure_not_decommissioned()?;
+        let amount = notes.total(self.currency())?;
+        self.cassettes.load(&notes)?;
+        self.total_cash = self.total_cash.checked_add(amount)?;
+
+        let event = atm_events::AtmCashChargedEvent {
+            atm_id: self.id,
+            amount,
+            notes,
+            total_cash: self.total_cash,
+        };
+        self.domain_events_mut().push(event.into());
         Ok(())
     }
-    /// Atmから現金を引き出す
-    pub fn withdraw(&mut self, amount: f64) -> Result<(), DomainError> {
-        if amount < self.total_cash {
-            self.total_cash -= amount;
-            Ok(())
-        } else {
-            Err(AtmError::CannotWithdrawError {
-                total_cash: self.total_cash,
-                withdraw_amount: amount,
+    /// Atmから現金を引き出す．カセット内の紙幣で払い出せる組み合わせを求め，その内訳を返す
+    pub fn withdraw(&mut self, amount: Money) -> Result<NoteBreakdown, DomainError> {
+        self.ensure_operational()?;
+        let total_cash = match self.total_cash.checked_sub(amount) {
+            Ok(total_cash) => total_cash,
+            Err(e @ MoneyError::CurrencyMismatchError { .. }) => return Err(e.into()),
+            Err(_) => {
+                return Err(AtmError::CannotWithdrawError {
+                    total_cash: self.total_cash,
+                    withdraw_amount: amount,
+                }
+                .into())
             }
-            .into())
+        };
+        let notes = self.cassettes.dispense(amount)?;
+
+        let threshold = atm_cash_low_threshold(self.currency())?;
+        let was_low = self.total_cash < threshold;
+        self.total_cash = total_cash;
+
+        let event = atm_events::AtmCashDispensedEvent {
+            atm_id: self.id,
+            amount,
+            notes: notes.clone(),
+            total_cash,
+        };
+        self.domain_events_mut().push(event.into());
+
+        if !was_low && total_cash < threshold {
+            let event = atm_events::AtmCashLowEvent {
+                atm_id: self.id,
+                total_cash,
+                threshold,
+            };
+            self.domain_events_mut().push(event.into());
         }
+        Ok(notes)
+    }
+    /// Atmを一時的に停止する．out_of_serviceがfalseの場合は稼働を再開する．
+    /// 場所が未登録のAtmは最寄りのAtmとして案内できないため，稼働を再開できない
+    pub fn set_out_of_service(&mut self, out_of_service: bool) -> Result<(), DomainError> {
+        let previous_status = self.status;
+        let status = if out_of_service {
+            AtmStatus::OutOfService
+        } else {
+            AtmStatus::InService
+        };
+        if status == AtmStatus::InService && self.location.is_none() {
+            return Err(AtmError::AtmLocationNotRegisteredError { atm_id: self.id }.into());
+        }
+        self.transition_to(status)?;
+
+        let event = atm_events::AtmStatusChangedEvent {
+            atm_id: self.id,
+            previous_status,
+            status,
+        };
+        self.domain_events_mut().push(event.into());
+        Ok(())
+    }
+    /// Atmを撤去する
+    pub fn decommission(&mut self) -> Result<(), DomainError> {
+        self.transition_to(AtmStatus::Decommissioned)?;
+
+        let event = atm_events::AtmDecommissionedEvent {
+            atm_id: self.id,
+            total_cash: self.total_cash,
+        };
+        self.domain_events_mut().push(event.into());
+        Ok(())
+    }
+    /// Atmを移設する
+    pub fn relocate(&mut self, location: AtmLocation) -> Result<(), DomainError> {
+        self.ensure_not_decommissioned()?;
+        let previous_location = self.location.replace(location.clone());
+
+        let event = atm_events::AtmRelocatedEvent {
+            atm_id: self.id,
+            previous_location,
+            location,
+        };
+        self.domain_events_mut().push(event.into());
+        Ok(())
     }
 }
 
+/// 運用担当者に知らせるAtmの現金の総額のしきい値
+pub fn atm_cash_low_threshold(currency: Currency) -> Result<Money, MoneyError> {
+    Money::new(CONFIG.ATM_CASH_LOW_THRESHOLD, currency)
+}
+
 impl Aggregate for Atm {
     type Event = AtmEvent;
     type IntoId = AtmId;
@@ -104,33 +273,99 @@ pub mod orm {
     use sea_orm::entity::prelude::*;
     use serde::Serialize;
 
-    /// Atmに対するORMモデル．
+    /// Atmに対するORMモデル．シリアライズはアグリゲイトを経由する．
     #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize)]
+    #[serde(into = "Atm")]
     #[sea_orm(table_name = "atm")]
     pub struct Model {
         #[sea_orm(primary_key, auto_increment = false, unique)]
         id: AtmId,
-        location: AtmLocation,
-        total_cash: f64,
+        /// 住所．AtmLocationの要素ごとに列を持つ．場所が未登録のAtmではNULL
+        postal_code: Option<String>,
+        region: Option<String>,
+        city: Option<String>,
+        street: Option<String>,
+        /// 座標．最寄りのAtmの検索に利用する．場所が未登録のAtmではNULL
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+        /// 現金の総額．通貨はcurrencyの列で保持する．
+        total_cash: Money,
+        /// Atmが扱う通貨
+        currency: Currency,
+        status: AtmStatus,
     }
 
     #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-    pub enum Relation {}
+    pub enum Relation {
+        /// 額面ごとのカセット
+        #[sea_orm(has_many = "super::cassettes::orm::Entity")]
+        Cassette,
+    }
+
+    impl Related<super::cassettes::orm::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Cassette.def()
+        }
+    }
 
     impl ActiveModelBehavior for ActiveModel {}
 
+    impl Model {
+        /// カセットの子テーブルの行と合わせてアグリゲイトに変換する
+        pub fn into_aggregate(
+            self,
+            cassettes: Vec<super::cassettes::orm::Model>,
+        ) -> Result<Atm, DomainError> {
+            let mut atm = Atm::from(self);
+            atm.cassettes = Cassettes::new(
+                atm.currency(),
+                NoteBreakdown::new(cassettes.into_iter().map(Into::into)),
+            )?;
+            Ok(atm)
+        }
+    }
+
     /// 双方のFromを実装することで，フィールド対応のバグを減らすことができる．
+    /// カセットは子テーブルに保持するため，ここでは変換しない．
     impl From<Model> for Atm {
         fn from(value: Model) -> Self {
             let Model {
                 id,
-                location,
+                postal_code,
+                region,
+                city,
+                street,
+                latitude,
+                longitude,
                 total_cash,
+                currency,
+                status,
             } = value;
+            // 保存時に検証済みのため，直接作成する
+            let location = match (postal_code, region, city, street, latitude, longitude) {
+                (
+                    Some(postal_code),
+                    Some(region),
+                    Some(city),
+                    Some(street),
+                    Some(latitude),
+                    Some(longitude),
+                ) => Some(AtmLocation::from_columns(
+                    postal_code,
+                    region,
+                    city,
+                    street,
+                    latitude,
+                    longitude,
+                )),
+                _ => None,
+            };
             Self {
                 id,
                 location,
-                total_cash,
+                total_cash: total_cash.with_currency(currency),
+                cassettes: Cassettes::empty(currency),
+                status,
                 events_list: Default::default(),
             }
         }
@@ -142,12 +377,23 @@ pub mod orm {
                 id,
                 location,
                 total_cash,
+                cassettes: _,
+                status,
                 events_list: _,
             } = value;
+            let address = location.as_ref().map(AtmLocation::address);
+            let coordinates = location.as_ref().map(AtmLocation::coordinates);
             Self {
                 id,
-                location,
+                postal_code: address.map(|address| address.postal_code().to_string()),
+                region: address.map(|address| address.region().to_string()),
+                city: address.map(|address| address.city().to_string()),
+                street: address.map(|address| address.street().to_string()),
+                latitude: coordinates.map(|coordinates| coordinates.latitude()),
+                longitude: coordinates.map(|coordinates| coordinates.longitude()),
+                currency: total_cash.currency(),
                 total_cash,
+                status,
             }
         }
     }
@@ -161,10 +407,15 @@ impl fake::Dummy<fake::Faker> for Atm {
     fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
         use fake::{Fake, Faker};
 
+        // カセットは子テーブルに保持するため，空とする
+        let currency: Currency = Faker.fake_with_rng(rng);
+
         Self {
             id: Faker.fake_with_rng(rng),
-            location: Faker.fake_with_rng(rng),
-            total_cash: Faker.fake_with_rng(rng),
+            location: Some(Faker.fake_with_rng(rng)),
+            total_cash: Money::zero(currency),
+            cassettes: Cassettes::empty(currency),
+            status: Faker.fake_with_rng(rng),
             events_list: DomainEventList::new(),
         }
     }
@@ -192,4 +443,152 @@ mod test {
             assert_eq!(atm, serde_json::from_str(&json_from_model).unwrap())
         }
     }
+
+    #[test]
+    fn cash_events() {
+        use super::{atm_cash_low_threshold, AtmLocation, Denomination, NoteBreakdown};
+        use crate::events::atm_events::{
+            AtmCashChargedEvent, AtmCashDispensedEvent, AtmCashLowEvent, AtmEvent,
+            AtmRegisteredEvent,
+        };
+        use crate::money::{Currency, Money};
+        use ddd_cqrs_core::Aggregate;
+        use fake::{Fake, Faker};
+
+        let jpy = |amount: &str| format!("{amount} JPY").parse::<Money>().unwrap();
+        let threshold = atm_cash_low_threshold(Currency::JPY).unwrap();
+        let initial_cash = threshold.checked_add(jpy("20000")).unwrap();
+
+        let ten_thousands = |count: u32| NoteBreakdown::new([(Denomination::new(10000), count)]);
+
+        let location = Faker.fake::<AtmLocation>();
+        let mut atm = Atm::from_primitives(location.clone(), initial_cash).unwrap();
+        let atm_id = atm.id();
+        assert_eq!(
+            atm.domain_events_mut().take(),
+            vec![AtmEvent::from(AtmRegisteredEvent {
+                atm_id,
+                location,
+                notes: ten_thousands(102),
+                total_cash: initial_cash,
+            })]
+        );
+
+        // しきい値以上のため，払い出しのイベントのみ
+        atm.withdraw(jpy("10000")).unwrap();
+        assert_eq!(
+            atm.domain_events_mut().take(),
+            vec![AtmEvent::from(AtmCashDispensedEvent {
+                atm_id,
+                amount: jpy("10000"),
+                notes: ten_thousands(1),
+                total_cash: threshold.checked_add(jpy("10000")).unwrap(),
+            })]
+        );
+
+        // しきい値を下回った時点で一度だけレイズする
+        atm.withdraw(jpy("20000")).unwrap();
+        let total_cash = threshold.checked_sub(jpy("10000")).unwrap();
+        assert_eq!(
+            atm.domain_events_mut().take(),
+            vec![
+                AtmEvent::from(AtmCashDispensedEvent {
+                    atm_id,
+                    amount: jpy("20000"),
+                    notes: ten_thousands(2),
+                    total_cash,
+                }),
+                AtmEvent::from(AtmCashLowEvent {
+                    atm_id,
+                    total_cash,
+                    threshold,
+                }),
+            ]
+        );
+        atm.withdraw(jpy("10000")).unwrap();
+        assert_eq!(atm.domain_events_mut().take().len(), 1);
+
+        let notes = NoteBreakdown::new([(Denomination::new(1000), 50)]);
+        atm.charge_cash(notes.clone()).unwrap();
+        assert_eq!(
+            atm.domain_events_mut().take(),
+            vec![AtmEvent::from(AtmCashChargedEvent {
+                atm_id,
+                amount: jpy("50000"),
+                notes,
+                total_cash: atm.total_cash(),
+            })]
+        );
+
+        // Atmの通貨以外の現金は預け入れできない
+        atm.ensure_accepts(jpy("1000")).unwrap();
+        assert!(atm
+            .ensure_accepts("10 USD".parse::<Money>().unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn status_rules() {
+        use super::{AtmLocation, AtmStatus, NoteBreakdown};
+        use crate::error::{AtmError, DomainError};
+        use crate::money::Money;
+        use fake::{Fake, Faker};
+
+        let jpy = |amount: &str| format!("{amount} JPY").parse::<Money>().unwrap();
+
+        let mut atm = Atm::from_primitives(Faker.fake(), jpy("100000")).unwrap();
+
+        // 停止中は引き出しできないが，チャージはできる
+        atm.set_out_of_service(true).unwrap();
+        assert!(matches!(
+            atm.withdraw(jpy("10000")),
+            Err(DomainError::AtmError(AtmError::AtmNotOperationalError {
+                status: AtmStatus::OutOfService,
+                ..
+            }))
+        ));
+        atm.charge_cash(NoteBreakdown::from_amount(jpy("1000")).unwrap())
+            .unwrap();
+        assert!(matches!(
+            atm.set_out_of_service(true),
+            Err(DomainError::AtmError(
+                AtmError::InvalidStatusTransitionError { .. }
+            ))
+        ));
+
+        atm.set_out_of_service(false).unwrap();
+        atm.withdraw(jpy("10000")).unwrap();
+        let location = Faker.fake::<AtmLocation>();
+        atm.relocate(location.clone()).unwrap();
+        assert_eq!(atm.location(), Some(&location));
+
+        // 場所が未登録のAtmは移設により場所を登録するまで稼働を再開できない
+        atm.set_out_of_service(true).unwrap();
+        atm.location = None;
+        assert!(matches!(
+            atm.set_out_of_service(false),
+            Err(DomainError::AtmError(
+                AtmError::AtmLocationNotRegisteredError { .. }
+            ))
+        ));
+        atm.relocate(location.clone()).unwrap();
+        atm.set_out_of_service(false).unwrap();
+
+        // 撤去後は何もできない
+        atm.decommission().unwrap();
+        assert!(atm.ensure_operational().is_err());
+        assert!(matches!(
+            atm.charge_cash(NoteBreakdown::from_amount(jpy("1000")).unwrap()),
+            Err(DomainError::AtmError(
+                AtmError::AtmDecommissionedError { .. }
+            ))
+        ));
+        assert!(matches!(
+            atm.relocate(Faker.fake()),
+            Err(DomainError::AtmError(
+                AtmError::AtmDecommissionedError { .. }
+            ))
+        ));
+        assert!(atm.set_out_of_service(false).is_err());
+    }
 }
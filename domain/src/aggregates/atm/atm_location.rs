@@ -1,40 +1,276 @@
-use std::borrow::Cow;
+use crate::error::{AtmError, DomainError};
 
 use serde::{Deserialize, Serialize};
+use std::fmt::Display;
 
-/// ATMのある場所を示すエンティティ
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(transparent)]
-#[cfg_attr(feature = "orm", derive(sea_orm_newtype::DeriveNewType))]
-#[cfg_attr(feature = "orm", sea_orm_newtype(from_into = "String"))] // transparentでもよい
-pub struct AtmLocation(String);
+/// 住所の各要素の最大の文字数
+const MAX_ADDRESS_PART_CHARS: usize = 100;
+/// 郵便番号の文字数の範囲
+const POSTAL_CODE_CHARS: std::ops::RangeInclusive<usize> = 3..=10;
+/// 地球の平均半径(km)
+const EARTH_RADIUS_KM: f64 = 6371.0;
 
-impl AtmLocation {
-    pub fn new<'a, S: Into<Cow<'a, str>>>(location: S) -> Self {
-        let s: Cow<'a, str> = location.into();
-        Self(s.into_owned())
+// -------------------------------------------------------------------------------------------------
+// AtmAddress
+
+/// Atmの設置場所の住所
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "AtmAddressPrimitives")]
+pub struct AtmAddress {
+    /// 郵便番号
+    postal_code: String,
+    /// 都道府県・州
+    region: String,
+    /// 市区町村
+    city: String,
+    /// 町名・番地・建物名
+    street: String,
+}
+
+/// デシリアライズ時に検証するための型
+#[derive(Deserialize)]
+struct AtmAddressPrimitives {
+    postal_code: String,
+    region: String,
+    city: String,
+    street: String,
+}
+
+impl TryFrom<AtmAddressPrimitives> for AtmAddress {
+    type Error = DomainError;
+    fn try_from(value: AtmAddressPrimitives) -> Result<Self, Self::Error> {
+        let AtmAddressPrimitives {
+            postal_code,
+            region,
+            city,
+            street,
+        } = value;
+        Self::new(postal_code, region, city, street)
     }
-    pub fn as_str(&self) -> &str {
-        &self.0
+}
+
+/// 住所の要素を検証する．前後の空白は取り除く
+fn validate_address_part(field: &'static str, value: String) -> Result<String, DomainError> {
+    let value = value.trim();
+    let reason = if value.is_empty() {
+        Some("must not be empty")
+    } else if value.chars().count() > MAX_ADDRESS_PART_CHARS {
+        Some("is too long")
+    } else if value.chars().any(char::is_control) {
+        Some("must not contain control characters")
+    } else {
+        None
+    };
+
+    match reason {
+        Some(reason) => Err(AtmError::InvalidAddressError {
+            field: field.to_string(),
+            reason: reason.to_string(),
+        }
+        .into()),
+        None => Ok(value.to_string()),
     }
 }
 
-impl From<String> for AtmLocation {
-    fn from(value: String) -> Self {
-        AtmLocation(value)
+impl AtmAddress {
+    pub fn new(
+        postal_code: String,
+        region: String,
+        city: String,
+        street: String,
+    ) -> Result<Self, DomainError> {
+        let postal_code = validate_address_part("postal_code", postal_code)?;
+        if !POSTAL_CODE_CHARS.contains(&postal_code.len())
+            || !postal_code
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == ' ')
+        {
+            return Err(AtmError::InvalidAddressError {
+                field: "postal_code".to_string(),
+                reason: "must be 3 to 10 letters, digits or hyphens".to_string(),
+            }
+            .into());
+        }
+
+        Ok(Self {
+            postal_code,
+            region: validate_address_part("region", region)?,
+            city: validate_address_part("city", city)?,
+            street: validate_address_part("street", street)?,
+        })
+    }
+    pub fn postal_code(&self) -> &str {
+        &self.postal_code
+    }
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+    pub fn city(&self) -> &str {
+        &self.city
+    }
+    pub fn street(&self) -> &str {
+        &self.street
     }
 }
 
-impl From<AtmLocation> for String {
-    fn from(value: AtmLocation) -> Self {
-        value.0
+impl Display for AtmAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.postal_code, self.region, self.city, self.street
+        )
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// GeoPoint
+
+/// 緯度・経度(度)で表す地点
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "GeoPointPrimitives")]
+pub struct GeoPoint {
+    latitude: f64,
+    longitude: f64,
+}
+
+/// デシリアライズ時に検証するための型
+#[derive(Deserialize)]
+struct GeoPointPrimitives {
+    latitude: f64,
+    longitude: f64,
+}
+
+impl TryFrom<GeoPointPrimitives> for GeoPoint {
+    type Error = DomainError;
+    fn try_from(value: GeoPointPrimitives) -> Result<Self, Self::Error> {
+        Self::new(value.latitude, value.longitude)
     }
 }
 
+impl GeoPoint {
+    /// 緯度は-90から90，経度は-180から180の範囲
+    pub fn new(latitude: f64, longitude: f64) -> Result<Self, DomainError> {
+        if (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude) {
+            Ok(Self {
+                latitude,
+                longitude,
+            })
+        } else {
+            Err(AtmError::InvalidCoordinatesError {
+                latitude,
+                longitude,
+            }
+            .into())
+        }
+    }
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+    /// 大円距離(km)．haversine公式を用いる
+    pub fn distance_km(&self, other: &GeoPoint) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.longitude - self.longitude).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// AtmLocation
+
+/// ATMのある場所を示すエンティティ．住所と座標を持つ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AtmLocation {
+    address: AtmAddress,
+    coordinates: GeoPoint,
+}
+
+impl AtmLocation {
+    pub fn new(address: AtmAddress, coordinates: GeoPoint) -> Self {
+        Self {
+            address,
+            coordinates,
+        }
+    }
+    pub fn address(&self) -> &AtmAddress {
+        &self.address
+    }
+    pub fn coordinates(&self) -> GeoPoint {
+        self.coordinates
+    }
+    /// 検証済みの列から直接作成する
+    #[cfg(feature = "orm")]
+    pub(crate) fn from_columns(
+        postal_code: String,
+        region: String,
+        city: String,
+        street: String,
+        latitude: f64,
+        longitude: f64,
+    ) -> Self {
+        Self {
+            address: AtmAddress {
+                postal_code,
+                region,
+                city,
+                street,
+            },
+            coordinates: GeoPoint {
+                latitude,
+                longitude,
+            },
+        }
+    }
+}
+
+/// JSONの列に保存する場合の値．シリアライズと同じ形式とする．
+/// 最寄りのAtmの検索に利用するため，AtmのModelでは要素ごとの列に保持する
 #[cfg(feature = "orm")]
 impl From<&AtmLocation> for sea_orm::Value {
     fn from(value: &AtmLocation) -> Self {
-        value.as_str().into()
+        use sea_orm::JsonValue;
+
+        let address = value.address();
+        let coordinates = value.coordinates();
+        let json: JsonValue = [
+            (
+                "address",
+                [
+                    ("postal_code", address.postal_code()),
+                    ("region", address.region()),
+                    ("city", address.city()),
+                    ("street", address.street()),
+                ]
+                .into_iter()
+                .collect::<JsonValue>(),
+            ),
+            (
+                "coordinates",
+                [
+                    ("latitude", coordinates.latitude()),
+                    ("longitude", coordinates.longitude()),
+                ]
+                .into_iter()
+                .collect::<JsonValue>(),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        sea_orm::Value::Json(Some(Box::new(json)))
+    }
+}
+
+#[cfg(feature = "orm")]
+impl From<AtmLocation> for sea_orm::Value {
+    fn from(value: AtmLocation) -> Self {
+        (&value).into()
     }
 }
 
@@ -42,12 +278,37 @@ impl From<&AtmLocation> for sea_orm::Value {
 // impl Dummy
 
 #[cfg(any(test, feature = "fake"))]
-impl fake::Dummy<fake::Faker> for AtmLocation {
+impl fake::Dummy<fake::Faker> for AtmAddress {
     fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
+        use fake::faker::address::en::{CityName, StateName, StreetName, ZipCode};
         use fake::Fake;
 
-        let location: String = fake::faker::address::en::CityName().fake_with_rng(rng);
-        location.into()
+        AtmAddress::new(
+            ZipCode().fake_with_rng(rng),
+            StateName().fake_with_rng(rng),
+            CityName().fake_with_rng(rng),
+            StreetName().fake_with_rng(rng),
+        )
+        .unwrap()
+    }
+}
+
+#[cfg(any(test, feature = "fake"))]
+impl fake::Dummy<fake::Faker> for GeoPoint {
+    fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
+        // JSONを経由しても値が変わらないように，小数点以下6桁(約10cm)とする
+        let latitude = rng.gen_range(-90_000_000..=90_000_000) as f64 / 1e6;
+        let longitude = rng.gen_range(-180_000_000..=180_000_000) as f64 / 1e6;
+        GeoPoint::new(latitude, longitude).unwrap()
+    }
+}
+
+#[cfg(any(test, feature = "fake"))]
+impl fake::Dummy<fake::Faker> for AtmLocation {
+    fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
+        use fake::{Fake, Faker};
+
+        AtmLocation::new(Faker.fake_with_rng(rng), Faker.fake_with_rng(rng))
     }
 }
 
@@ -56,7 +317,54 @@ impl fake::Dummy<fake::Faker> for AtmLocation {
 
 #[cfg(test)]
 mod test {
-    use super::AtmLocation;
+    use super::{AtmAddress, AtmLocation, GeoPoint};
+
+    #[test]
+    fn validate_location() {
+        let address = AtmAddress::new(
+            " 100-0005 ".to_string(),
+            "東京都".to_string(),
+            "千代田区".to_string(),
+            "丸の内1-9-1".to_string(),
+        )
+        .unwrap();
+        assert_eq!(address.postal_code(), "100-0005");
+
+        assert!(AtmAddress::new(
+            "100-0005".to_string(),
+            " ".to_string(),
+            "千代田区".to_string(),
+            "丸の内1-9-1".to_string(),
+        )
+        .is_err());
+        assert!(AtmAddress::new(
+            "〒100".to_string(),
+            "東京都".to_string(),
+            "千代田区".to_string(),
+            "丸の内1-9-1".to_string(),
+        )
+        .is_err());
+
+        assert!(GeoPoint::new(90.5, 0.0).is_err());
+        assert!(GeoPoint::new(0.0, f64::NAN).is_err());
+
+        // デシリアライズでも検証する
+        let location = AtmLocation::new(address, GeoPoint::new(35.681, 139.767).unwrap());
+        let json = serde_json::to_string(&location).unwrap();
+        assert_eq!(location, serde_json::from_str(&json).unwrap());
+        assert!(serde_json::from_str::<AtmLocation>(&json.replace("35.681", "135.681")).is_err());
+    }
+
+    #[test]
+    fn distance() {
+        let tokyo = GeoPoint::new(35.681, 139.767).unwrap();
+        let osaka = GeoPoint::new(34.702, 135.496).unwrap();
+
+        // 東京駅から大阪駅まで約400km
+        let distance = tokyo.distance_km(&osaka);
+        assert!((400.0..410.0).contains(&distance), "{distance}");
+        assert_eq!(tokyo.distance_km(&tokyo), 0.0);
+    }
 
     #[cfg(feature = "orm")]
     mod orm_test {
@@ -69,6 +377,10 @@ mod test {
         fn ref_into_value_eq_into_value() {
             let atm_location = Faker.fake::<AtmLocation>();
 
+            assert_eq!(
+                Into::<Value>::into(&atm_location),
+                Value::Json(Some(Box::new(serde_json::to_value(&atm_location).unwrap())))
+            );
             assert_eq!(
                 Into::<Value>::into(&atm_location),
                 Into::<Value>::into(atm_location)
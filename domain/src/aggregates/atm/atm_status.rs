@@ -0,0 +1,99 @@
+use crate::error::DomainError;
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Atmの稼働状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "orm", derive(sea_orm_newtype::DeriveNewType))]
+#[cfg_attr(feature = "orm", sea_orm_newtype(try_from_into = "String"))]
+pub enum AtmStatus {
+    /// 稼働中
+    #[default]
+    InService,
+    /// 一時的に停止中．預け入れ・引き出しはできないが，現金のチャージはできる
+    OutOfService,
+    /// 撤去済み．以降は状態を変更できない
+    Decommissioned,
+}
+
+impl AtmStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AtmStatus::InService => "InService",
+            AtmStatus::OutOfService => "OutOfService",
+            AtmStatus::Decommissioned => "Decommissioned",
+        }
+    }
+    /// 現在の状態からtoへ遷移できるかどうか
+    pub fn can_transition_to(&self, to: AtmStatus) -> bool {
+        use AtmStatus::*;
+
+        matches!(
+            (self, to),
+            (InService, OutOfService)
+                | (OutOfService, InService)
+                | (InService, Decommissioned)
+                | (OutOfService, Decommissioned)
+        )
+    }
+}
+
+impl Display for AtmStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AtmStatus {
+    type Err = DomainError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "InService" => Ok(AtmStatus::InService),
+            "OutOfService" => Ok(AtmStatus::OutOfService),
+            "Decommissioned" => Ok(AtmStatus::Decommissioned),
+            _ => Err(DomainError::DomainParseError(format!(
+                "Unknown atm status: {s}"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<String> for AtmStatus {
+    type Error = DomainError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<AtmStatus> for String {
+    fn from(value: AtmStatus) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+#[cfg(feature = "orm")]
+impl From<&AtmStatus> for sea_orm::Value {
+    fn from(value: &AtmStatus) -> Self {
+        value.as_str().into()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// impl Dummy
+
+#[cfg(any(test, feature = "fake"))]
+impl fake::Dummy<fake::Faker> for AtmStatus {
+    fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
+        use rand::seq::SliceRandom;
+
+        *[
+            AtmStatus::InService,
+            AtmStatus::OutOfService,
+            AtmStatus::Decommissioned,
+        ]
+        .choose(rng)
+        .unwrap()
+    }
+}
@@ -0,0 +1,520 @@
+use crate::error::{AtmError, DomainError, MoneyError};
+use crate::money::{Currency, Money};
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+// -------------------------------------------------------------------------------------------------
+// Denomination
+
+/// 紙幣の額面．通貨の主単位で表す
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Denomination(u32);
+
+const JPY_DENOMINATIONS: [Denomination; 4] = [
+    Denomination(10000),
+    Denomination(5000),
+    Denomination(2000),
+    Denomination(1000),
+];
+
+const USD_DENOMINATIONS: [Denomination; 6] = [
+    Denomination(100),
+    Denomination(50),
+    Denomination(20),
+    Denomination(10),
+    Denomination(5),
+    Denomination(1),
+];
+
+const EUR_DENOMINATIONS: [Denomination; 7] = [
+    Denomination(500),
+    Denomination(200),
+    Denomination(100),
+    Denomination(50),
+    Denomination(20),
+    Denomination(10),
+    Denomination(5),
+];
+
+impl Denomination {
+    pub fn new(value: u32) -> Self {
+        Self(value)
+    }
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+    /// 通貨で扱う紙幣の額面(降順)
+    pub fn all(currency: Currency) -> &'static [Denomination] {
+        match currency {
+            Currency::JPY => &JPY_DENOMINATIONS,
+            Currency::USD => &USD_DENOMINATIONS,
+            Currency::EUR => &EUR_DENOMINATIONS,
+        }
+    }
+    /// 通貨で扱う額面かどうか
+    pub fn is_valid_for(&self, currency: Currency) -> bool {
+        Self::all(currency).contains(self)
+    }
+    /// count枚分の金額
+    fn times(&self, count: u32, currency: Currency) -> Result<Money, MoneyError> {
+        let amount = Decimal::from(self.0)
+            .checked_mul(Decimal::from(count))
+            .ok_or(MoneyError::OverflowError)?;
+        Money::new(amount, currency)
+    }
+}
+
+impl Display for Denomination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// NoteBreakdown
+
+/// 額面ごとの紙幣の枚数．枚数が0の額面は保持しない
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(
+    from = "BTreeMap<Denomination, u32>",
+    into = "BTreeMap<Denomination, u32>"
+)]
+pub struct NoteBreakdown(BTreeMap<Denomination, u32>);
+
+impl NoteBreakdown {
+    /// 同じ額面が複数ある場合は枚数を合計する
+    pub fn new<I: IntoIterator<Item = (Denomination, u32)>>(notes: I) -> Self {
+        let mut breakdown = Self::default();
+        for (denomination, count) in notes {
+            breakdown.add(denomination, count);
+        }
+        breakdown
+    }
+    /// 金額を額面の大きい紙幣から順に分ける．入金された現金の内訳の推定に利用する．
+    /// 各通貨の額面は貪欲法で最適となる組み合わせである
+    pub fn from_amount(amount: Money) -> Result<Self, DomainError> {
+        let currency = amount.currency();
+        let mut remaining = whole_units(amount)?;
+        let mut counts = Vec::new();
+        for denomination in Denomination::all(currency) {
+            counts.push(remaining / denomination.0 as u64);
+            remaining %= denomination.0 as u64;
+        }
+
+        if remaining == 0 {
+            Self::from_counts(currency, &counts, amount)
+        } else {
+            Err(AtmError::UnrepresentableAmountError { amount }.into())
+        }
+    }
+    fn from_counts(currency: Currency, counts: &[u64], amount: Money) -> Result<Self, DomainError> {
+        let mut breakdown = Self::default();
+        for (denomination, count) in Denomination::all(currency).iter().zip(counts) {
+            let count = u32::try_from(*count)
+                .map_err(|_| AtmError::UnrepresentableAmountError { amount })?;
+            breakdown.add(*denomination, count);
+        }
+        Ok(breakdown)
+    }
+    fn add(&mut self, denomination: Denomination, count: u32) {
+        if count > 0 {
+            let current = self.0.entry(denomination).or_default();
+            *current = current.saturating_add(count);
+        }
+    }
+    /// 指定した額面の枚数
+    pub fn count(&self, denomination: Denomination) -> u32 {
+        self.0.get(&denomination).copied().unwrap_or_default()
+    }
+    /// 額面の大きい順に額面と枚数を返す
+    pub fn iter(&self) -> impl Iterator<Item = (Denomination, u32)> + '_ {
+        self.0.iter().rev().map(|(d, c)| (*d, *c))
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// 紙幣の合計金額
+    pub fn total(&self, currency: Currency) -> Result<Money, MoneyError> {
+        self.iter()
+            .try_fold(Money::zero(currency), |total, (d, c)| {
+                total.checked_add(d.times(c, currency)?)
+            })
+    }
+}
+
+impl From<BTreeMap<Denomination, u32>> for NoteBreakdown {
+    fn from(value: BTreeMap<Denomination, u32>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<NoteBreakdown> for BTreeMap<Denomination, u32> {
+    fn from(value: NoteBreakdown) -> Self {
+        value.0
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Cassettes
+
+/// Atmの額面ごとのカセット．払い出しに利用する紙幣を保持する
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cassettes {
+    currency: Currency,
+    notes: NoteBreakdown,
+}
+
+impl Cassettes {
+    /// 通貨で扱わない額面を含む場合はエラー
+    pub fn new(currency: Currency, notes: NoteBreakdown) -> Result<Self, DomainError> {
+        ensure_denominations(currency, &notes)?;
+        Ok(Self { currency, notes })
+    }
+    pub fn empty(currency: Currency) -> Self {
+        Self {
+            currency,
+            notes: NoteBreakdown::default(),
+        }
+    }
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+    pub fn notes(&self) -> &NoteBreakdown {
+        &self.notes
+    }
+    /// カセット内の紙幣の合計金額
+    pub fn total(&self) -> Result<Money, MoneyError> {
+        self.notes.total(self.currency)
+    }
+    /// 紙幣をカセットに補充する
+    pub fn load(&mut self, notes: &NoteBreakdown) -> Result<(), DomainError> {
+        ensure_denominations(self.currency, notes)?;
+        for (denomination, count) in notes.iter() {
+            self.notes.add(denomination, count);
+        }
+        Ok(())
+    }
+    /// カセット内の紙幣で金額を払い出す組み合わせを求める．カセットは変更しない
+    pub fn breakdown(&self, amount: Money) -> Result<NoteBreakdown, DomainError> {
+        if amount.currency() != self.currency {
+            return Err(MoneyError::CurrencyMismatchError {
+                expected: self.currency,
+                actual: amount.currency(),
+            }
+            .into());
+        }
+        let units = whole_units(amount)?;
+        let denominations: Vec<(u64, u64)> = Denomination::all(self.currency)
+            .iter()
+            .map(|denomination| {
+                (
+                    denomination.0 as u64,
+                    self.notes.count(*denomination) as u64,
+                )
+            })
+            .collect();
+
+        match find_combination(&denominations, units) {
+            Some(counts) => NoteBreakdown::from_counts(self.currency, &counts, amount),
+            None => Err(AtmError::CannotDispenseError { amount }.into()),
+        }
+    }
+    /// 金額を払い出し，払い出した紙幣の内訳を返す
+    pub fn dispense(&mut self, amount: Money) -> Result<NoteBreakdown, DomainError> {
+        let notes = self.breakdown(amount)?;
+        for (denomination, count) in notes.iter() {
+            let current = self.notes.0.entry(denomination).or_default();
+            *current -= count; // breakdownはカセット内の枚数を超えない
+            if *current == 0 {
+                self.notes.0.remove(&denomination);
+            }
+        }
+        Ok(notes)
+    }
+}
+
+/// 通貨で扱わない額面が含まれていないか確認する
+fn ensure_denominations(currency: Currency, notes: &NoteBreakdown) -> Result<(), DomainError> {
+    match notes.iter().find(|(d, _)| !d.is_valid_for(currency)) {
+        Some((denomination, _)) => Err(AtmError::InvalidDenominationError {
+            denomination,
+            currency,
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// 紙幣で払い出せる主単位の整数の金額に変換する
+fn whole_units(amount: Money) -> Result<u64, DomainError> {
+    amount
+        .amount()
+        .fract()
+        .is_zero()
+        .then(|| amount.amount().to_u64())
+        .flatten()
+        .ok_or_else(|| AtmError::UnrepresentableAmountError { amount }.into())
+}
+
+/// 払い出しの組み合わせを求める金額の上限．金額は利用する額面の最大公約数を単位として数える
+const MAX_COMBINATION_UNITS: u64 = 100_000;
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// 額面(降順)と利用できる枚数の組から，合計がamountとなる各額面の枚数を求める．
+/// 額面の最大公約数で割り切れない金額は払い出せないため，動的計画法の前に除外する．
+/// 大きい額面を優先した組み合わせを返す．
+fn find_combination(denominations: &[(u64, u64)], amount: u64) -> Option<Vec<u64>> {
+    let unit = denominations
+        .iter()
+        .filter(|(_, available)| *available > 0)
+        .fold(0, |unit, (value, _)| gcd(unit, *value));
+    if unit == 0 {
+        return (amount == 0).then(|| vec![0; denominations.len()]);
+    }
+    if !amount.is_multiple_of(unit) {
+        return None;
+    }
+    let capacity = denominations
+        .iter()
+        .fold(0u64, |sum, (v, c)| sum.saturating_add(v.saturating_mul(*c)));
+    if amount > capacity || amount / unit > MAX_COMBINATION_UNITS {
+        return None;
+    }
+
+    let target = (amount / unit) as usize;
+    // 枚数の無い額面は単位で割り切れない場合があるが，利用しないため0とする
+    let values: Vec<usize> = denominations
+        .iter()
+        .map(|(value, available)| match available {
+            0 => 0,
+            _ => (value / unit) as usize,
+        })
+        .collect();
+
+    // reachable[i][r]: i番目以降の(小さい)額面でrを払い出せるか
+    let mut reachable = vec![vec![false; target + 1]; denominations.len() + 1];
+    reachable[denominations.len()][0] = true;
+    for (i, &(_, available)) in denominations.iter().enumerate().rev() {
+        let value = values[i];
+        // used[r]: rを払い出すのに必要なi番目の額面の最小の枚数
+        let mut used = vec![0u64; target + 1];
+        for r in 0..=target {
+            if reachable[i + 1][r] {
+                reachable[i][r] = true;
+            } else if value > 0
+                && r >= value
+                && reachable[i][r - value]
+                && used[r - value] < available
+            {
+                reachable[i][r] = true;
+                used[r] = used[r - value] + 1;
+            }
+        }
+    }
+    if !reachable[0][target] {
+        return None;
+    }
+
+    // 大きい額面から，残りを払い出せる範囲で最大の枚数を選ぶ
+    let mut remaining = target;
+    let mut counts = Vec::with_capacity(denominations.len());
+    for (i, &(_, available)) in denominations.iter().enumerate() {
+        let value = values[i];
+        let max_count = match value {
+            0 => 0,
+            value => available.min((remaining / value) as u64),
+        };
+        let count = (0..=max_count)
+            .rev()
+            .find(|count| reachable[i + 1][remaining - *count as usize * value])?;
+        remaining -= count as usize * value;
+        counts.push(count);
+    }
+    Some(counts)
+}
+
+// -------------------------------------------------------------------------------------------------
+// sea_orm用Model
+
+#[cfg(feature = "orm")]
+pub mod orm {
+    use super::Denomination;
+    use crate::aggregates::atm::{self, AtmId};
+
+    use sea_orm::entity::prelude::*;
+    use serde::Serialize;
+
+    /// Atmの額面ごとのカセットの子テーブル．枚数が0の額面は保持しない
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize)]
+    #[sea_orm(table_name = "atm_cassette")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        #[sea_orm(indexed)]
+        pub atm_id: AtmId,
+        /// 紙幣の額面(通貨の主単位)
+        pub denomination: i64,
+        /// 紙幣の枚数
+        pub count: i64,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(
+            belongs_to = "atm::orm::Entity",
+            from = "Column::AtmId",
+            to = "atm::orm::Column::Id",
+            on_delete = "Cascade"
+        )]
+        Atm,
+    }
+
+    impl Related<atm::orm::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Atm.def()
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    impl Model {
+        /// 額面と枚数からモデルを作成する．idは新しく生成する
+        pub fn from_notes(atm_id: AtmId, denomination: Denomination, count: u32) -> Self {
+            Self {
+                id: Uuid::new_v4(),
+                atm_id,
+                denomination: denomination.value().into(),
+                count: count.into(),
+            }
+        }
+    }
+
+    /// 保存時に検証済みのため，範囲外の値は0とする
+    impl From<Model> for (Denomination, u32) {
+        fn from(value: Model) -> Self {
+            (
+                Denomination::new(u32::try_from(value.denomination).unwrap_or_default()),
+                u32::try_from(value.count).unwrap_or_default(),
+            )
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// impl Dummy
+
+#[cfg(any(test, feature = "fake"))]
+impl fake::Dummy<fake::Faker> for NoteBreakdown {
+    fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
+        use fake::Fake;
+
+        NoteBreakdown::new(
+            Denomination::all(crate::money::Currency::JPY)
+                .iter()
+                .map(|denomination| (*denomination, (0..100).fake_with_rng::<u32, R>(rng))),
+        )
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// test
+
+#[cfg(test)]
+mod test {
+    use super::{Cassettes, Denomination, NoteBreakdown};
+    use crate::error::{AtmError, DomainError};
+    use crate::money::{Currency, Money};
+
+    fn notes(notes: &[(u32, u32)]) -> NoteBreakdown {
+        NoteBreakdown::new(notes.iter().map(|(d, c)| (Denomination::new(*d), *c)))
+    }
+
+    #[test]
+    fn dispense_with_backtracking() {
+        let usd = |amount: &str| format!("{amount} USD").parse::<Money>().unwrap();
+
+        // 貪欲法では50ドル札を使ってしまい払い出せない
+        let mut cassettes = Cassettes::new(Currency::USD, notes(&[(50, 1), (20, 5)])).unwrap();
+        assert_eq!(cassettes.dispense(usd("60")).unwrap(), notes(&[(20, 3)]));
+        assert_eq!(cassettes.notes(), &notes(&[(50, 1), (20, 2)]));
+
+        // 大きい額面を優先する
+        assert_eq!(
+            cassettes.dispense(usd("90")).unwrap(),
+            notes(&[(50, 1), (20, 2)])
+        );
+        assert!(cassettes.notes().is_empty());
+
+        assert!(matches!(
+            cassettes.dispense(usd("20")),
+            Err(DomainError::AtmError(AtmError::CannotDispenseError { .. }))
+        ));
+    }
+
+    #[test]
+    fn undispensable_amount() {
+        let usd = |amount: &str| format!("{amount} USD").parse::<Money>().unwrap();
+        let eur = |amount: &str| format!("{amount} EUR").parse::<Money>().unwrap();
+
+        // 満杯のカセットでも額面の最大公約数で割り切れない金額は払い出せない
+        let full: Vec<(u32, u32)> = [500, 200, 100, 50, 20, 10]
+            .into_iter()
+            .map(|d| (d, 10_000))
+            .collect();
+        let cassettes = Cassettes::new(Currency::EUR, notes(&full)).unwrap();
+        assert!(matches!(
+            cassettes.breakdown(eur("8888885")),
+            Err(DomainError::AtmError(AtmError::CannotDispenseError { .. }))
+        ));
+        assert_eq!(
+            cassettes.breakdown(eur("880")).unwrap(),
+            notes(&[(500, 1), (200, 1), (100, 1), (50, 1), (20, 1), (10, 1)])
+        );
+
+        // 割り切れても組み合わせが無い金額は払い出せない
+        let cassettes =
+            Cassettes::new(Currency::USD, notes(&[(50, 10_000), (20, 10_000)])).unwrap();
+        assert!(matches!(
+            cassettes.breakdown(usd("30")),
+            Err(DomainError::AtmError(AtmError::CannotDispenseError { .. }))
+        ));
+        assert_eq!(
+            cassettes.breakdown(usd("110")).unwrap(),
+            notes(&[(50, 1), (20, 3)])
+        );
+    }
+
+    #[test]
+    fn invalid_notes() {
+        let jpy = |amount: &str| format!("{amount} JPY").parse::<Money>().unwrap();
+
+        assert!(matches!(
+            Cassettes::new(Currency::JPY, notes(&[(500, 1)])),
+            Err(DomainError::AtmError(
+                AtmError::InvalidDenominationError { .. }
+            ))
+        ));
+        assert!(matches!(
+            NoteBreakdown::from_amount(jpy("1500")),
+            Err(DomainError::AtmError(
+                AtmError::UnrepresentableAmountError { .. }
+            ))
+        ));
+        assert_eq!(
+            NoteBreakdown::from_amount(jpy("18000")).unwrap(),
+            notes(&[(10000, 1), (5000, 1), (2000, 1), (1000, 1)])
+        );
+    }
+}
@@ -1,14 +1,39 @@
+mod account_status;
+mod account_tier;
+mod account_type;
+mod check_number;
 mod email_address;
+mod email_change;
+mod interest;
 mod name;
+mod owner;
+mod withdrawal_limit;
 
 pub use self::email_address::EmailAddress;
-use crate::error::{BankAccountError, DomainError};
+use crate::error::{BankAccountError, DomainError, MoneyError};
 use crate::events::bank_account_events::{self, BankAccountEvent};
 use crate::id::Id;
+use crate::money::{Balance, Currency, ExchangeRate, Money};
+pub use account_status::AccountStatus;
+pub use account_tier::AccountTier;
+pub use account_type::AccountType;
+#[cfg(feature = "orm")]
+pub use check_number::orm as used_check_number_orm;
+pub use check_number::{CheckNumber, CheckSettlement};
 use config::CONFIG;
 use ddd_cqrs_core::{Aggregate, DomainEventList};
+pub use email_change::{email_change_token_ttl, EmailChangeToken, PendingEmailChange};
+pub use interest::InterestPeriod;
 pub use name::AccountName;
+#[cfg(feature = "orm")]
+pub use owner::orm as owner_orm;
+pub use owner::{AccountOwner, OwnerRole};
+pub use withdrawal_limit::{
+    atm_daily_withdrawal_limit, daily_withdrawal_window, DailyLimitScope, DailyWithdrawals,
+};
 
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::{Decimal, RoundingStrategy};
 use serde::{Deserialize, Serialize};
 
 // -------------------------------------------------------------------------------------------------
@@ -27,27 +52,53 @@ pub type BankAccountId = Id<BankAccountIdType>;
 pub struct BankAccount {
     /// id
     id: BankAccountId,
-    /// 口座が有効かどうか
-    opened: bool,
-    /// 残高
-    balance: f64,
+    /// 口座の状態
+    status: AccountStatus,
+    /// 口座のランク
+    tier: AccountTier,
+    /// 口座の種類
+    account_type: AccountType,
+    /// 残高．当座貸越を利用中は負となる
+    balance: Balance,
+    /// 当座貸越の限度額．0の場合は当座貸越を利用できない
+    overdraft_limit: Money,
+    /// 最後に利息を計上した期間
+    last_interest_period: Option<InterestPeriod>,
     /// メールアドレス
     email_address: EmailAddress,
     /// 口座名
     account_name: AccountName,
+    /// 共同名義人．メールアドレスと口座名は主たる名義人のもの
+    #[serde(default)]
+    joint_owners: Vec<AccountOwner>,
+    /// 確認待ちのメールアドレスの変更
+    #[serde(default)]
+    pending_email_change: Option<PendingEmailChange>,
     /// イベントのリスト
     #[serde(skip)]
     events_list: DomainEventList<BankAccountEvent>,
 }
 
 impl BankAccount {
-    pub fn from_domains(email_address: EmailAddress, account_name: AccountName) -> Self {
+    pub fn from_domains(
+        email_address: EmailAddress,
+        account_name: AccountName,
+        currency: Currency,
+        tier: AccountTier,
+        account_type: AccountType,
+    ) -> Self {
         BankAccount {
             id: BankAccountId::generate(),
-            opened: false,
-            balance: 0_f64,
+            status: AccountStatus::Pending,
+            tier,
+            account_type,
+            balance: Balance::zero(currency),
+            overdraft_limit: Money::zero(currency),
+            last_interest_period: None,
             email_address,
             account_name,
+            joint_owners: Vec::new(),
+            pending_email_change: None,
             events_list: DomainEventList::new(),
         }
     }
@@ -55,93 +106,646 @@ impl BankAccount {
         email_address: String,
         first_name: String,
         last_name: String,
+        currency: Currency,
+        tier: AccountTier,
+        account_type: AccountType,
     ) -> Result<Self, DomainError> {
         Ok(BankAccount {
             id: BankAccountId::generate(),
-            opened: false,
-            balance: 0_f64,
+            status: AccountStatus::Pending,
+            tier,
+            account_type,
+            balance: Balance::zero(currency),
+            overdraft_limit: Money::zero(currency),
+            last_interest_period: None,
             email_address: email_address.try_into()?,
             account_name: AccountName::from_primitives(first_name, last_name)?,
+            joint_owners: Vec::new(),
+            pending_email_change: None,
             events_list: DomainEventList::new(),
         })
     }
     pub fn id(&self) -> BankAccountId {
         self.id
     }
+    pub fn status(&self) -> AccountStatus {
+        self.status
+    }
+    pub fn tier(&self) -> AccountTier {
+        self.tier
+    }
+    pub fn account_type(&self) -> AccountType {
+        self.account_type
+    }
+    /// 口座に適用する年利．利息がつかない種類の口座では0
+    pub fn annual_interest_rate(&self) -> Decimal {
+        if self.account_type.accrues_interest() {
+            self.tier.annual_interest_rate()
+        } else {
+            Decimal::ZERO
+        }
+    }
+    /// 口座が利用可能かどうか
     pub fn opened(&self) -> bool {
-        self.opened
+        self.status == AccountStatus::Open
     }
-    pub fn balance(&self) -> f64 {
+    pub fn balance(&self) -> Balance {
         self.balance
     }
+    pub fn overdraft_limit(&self) -> Money {
+        self.overdraft_limit
+    }
+    pub fn last_interest_period(&self) -> Option<InterestPeriod> {
+        self.last_interest_period
+    }
+    /// 口座の通貨
+    pub fn currency(&self) -> Currency {
+        self.balance.currency()
+    }
     pub fn email_address(&self) -> &EmailAddress {
         &self.email_address
     }
     pub fn account_name(&self) -> &AccountName {
         &self.account_name
     }
+    pub fn joint_owners(&self) -> &[AccountOwner] {
+        &self.joint_owners
+    }
+    pub fn pending_email_change(&self) -> Option<&PendingEmailChange> {
+        self.pending_email_change.as_ref()
+    }
+    /// 主たる名義人を含む全ての名義人
+    pub fn owners(&self) -> Vec<AccountOwner> {
+        let primary = AccountOwner::new(
+            self.account_name.clone(),
+            self.email_address.clone(),
+            OwnerRole::Primary,
+        );
+        std::iter::once(primary)
+            .chain(self.joint_owners.iter().cloned())
+            .collect()
+    }
+    /// メールアドレスの人物が名義人かどうか
+    pub fn is_owned_by(&self, email_address: &EmailAddress) -> bool {
+        &self.email_address == email_address
+            || self
+                .joint_owners
+                .iter()
+                .any(|owner| owner.email_address() == email_address)
+    }
 
     // -------------------------------------------------------------------------------------------------
     // 以下はドメインロジック
 
+    /// 状態を遷移させる
+    fn transition_to(&mut self, to: AccountStatus) -> Result<(), DomainError> {
+        if self.status.can_transition_to(to) {
+            self.status = to;
+            Ok(())
+        } else {
+            Err(BankAccountError::InvalidStatusTransitionError {
+                from: self.status,
+                to,
+            }
+            .into())
+        }
+    }
+    /// 当座貸越を考慮した引き落とし後の残高．限度額を超える場合はNoneを返す．
+    /// 当座貸越を利用できない場合は，残高が0以下となる引き落としはできない．
+    fn balance_after_debit(&self, amount: Money) -> Result<Option<Balance>, MoneyError> {
+        let balance = self.balance.checked_sub(amount)?;
+
+        if self.overdraft_limit.is_zero() {
+            return Ok(balance.is_positive().then_some(balance));
+        }
+        if !balance.is_negative() {
+            return Ok(Some(balance));
+        }
+        // 手数料を含めても限度額を超えないこと
+        let charged = balance.checked_sub(overdraft_fee(self.currency())?)?;
+        Ok((!charged.checked_add(self.overdraft_limit)?.is_negative()).then_some(balance))
+    }
+    /// 取引を行える状態かどうかを確認する
+    fn ensure_open(&self) -> Result<(), DomainError> {
+        if self.status == AccountStatus::Open {
+            Ok(())
+        } else {
+            Err(BankAccountError::AccountNotOpenError {
+                status: self.status,
+            }
+            .into())
+        }
+    }
     /// アカウントの利用を可能にする．
-    pub fn open_account(&mut self) {
+    pub fn open_account(&mut self) -> Result<(), DomainError> {
+        self.transition_to(AccountStatus::Open)?;
+
         let event = bank_account_events::AccountOpenedEvent {
             account_id: self.id,
             email_address: self.email_address.clone(),
         };
+        self.domain_events_mut().push(event.into());
+        Ok(())
+    }
+    /// アカウントを凍結する．凍結中は全ての取引ができない
+    pub fn freeze_account(&mut self, reason: String) -> Result<(), DomainError> {
+        self.transition_to(AccountStatus::Frozen)?;
+
+        let event = bank_account_events::AccountFrozenEvent {
+            account_id: self.id,
+            reason,
+        };
+        self.domain_events_mut().push(event.into());
+        Ok(())
+    }
+    /// アカウントの凍結を解除する
+    pub fn unfreeze_account(&mut self) -> Result<(), DomainError> {
+        self.transition_to(AccountStatus::Open)?;
+
+        let event = bank_account_events::AccountUnfrozenEvent {
+            account_id: self.id,
+        };
+        self.domain_events_mut().push(event.into());
+        Ok(())
+    }
+    /// アカウントを解約する．残高が0でなければならない
+    pub fn close_account(&mut self) -> Result<(), DomainError> {
+        if !self.balance.is_zero() {
+            return Err(BankAccountError::CloseWithBalanceError {
+                balance: self.balance,
+            }
+            .into());
+        }
+        self.transition_to(AccountStatus::Closed)?;
+
+        let event = bank_account_events::AccountClosedEvent {
+            account_id: self.id,
+        };
+        self.domain_events_mut().push(event.into());
+        Ok(())
+    }
+    /// メールアドレスの変更を要求する．新しいメールアドレスに確認のトークンを送信する．
+    /// 確認待ちの変更がある場合は置き換える
+    pub fn request_email_change(
+        &mut self,
+        email_address: EmailAddress,
+        now: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        self.ensure_open()?;
+        if self.is_owned_by(&email_address) {
+            return Err(BankAccountError::OwnerAlreadyExistsError { email_address }.into());
+        }
 
-        self.opened = true;
+        let pending = PendingEmailChange::new(
+            email_address,
+            EmailChangeToken::generate(),
+            now + email_change_token_ttl(),
+        );
+
+        let event = bank_account_events::EmailChangeRequestedEvent {
+            account_id: self.id,
+            email_address: pending.email_address().clone(),
+            token: pending.token().clone(),
+            expires_at: pending.expires_at(),
+        };
+        self.pending_email_change = Some(pending);
         self.domain_events_mut().push(event.into());
+        Ok(())
+    }
+    /// 有効期限内のトークンでメールアドレスの変更を確定する
+    pub fn confirm_email_change(
+        &mut self,
+        token: &EmailChangeToken,
+        now: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        self.ensure_open()?;
+        let Some(pending) = self.pending_email_change.as_ref() else {
+            return Err(BankAccountError::NoPendingEmailChangeError.into());
+        };
+        if pending.token() != token {
+            return Err(BankAccountError::InvalidEmailChangeTokenError.into());
+        }
+        if pending.is_expired(now) {
+            return Err(BankAccountError::EmailChangeTokenExpiredError {
+                expires_at: pending.expires_at(),
+            }
+            .into());
+        }
+        // 確認待ちの間に名義人として追加された場合
+        if self.is_owned_by(pending.email_address()) {
+            return Err(BankAccountError::OwnerAlreadyExistsError {
+                email_address: pending.email_address().clone(),
+            }
+            .into());
+        }
+
+        let email_address = self
+            .pending_email_change
+            .take()
+            .map(|pending| pending.into_parts().0)
+            .expect("pending email change exists");
+        let previous_email_address =
+            std::mem::replace(&mut self.email_address, email_address.clone());
+
+        let event = bank_account_events::EmailAddressChangedEvent {
+            account_id: self.id,
+            previous_email_address,
+            email_address,
+        };
+        self.domain_events_mut().push(event.into());
+        Ok(())
+    }
+    /// 名義人を追加する．主たる名義人を追加した場合は，既存の主たる名義人は共同名義人となる
+    pub fn add_owner(&mut self, owner: AccountOwner) -> Result<(), DomainError> {
+        self.ensure_open()?;
+        if self.is_owned_by(owner.email_address()) {
+            return Err(BankAccountError::OwnerAlreadyExistsError {
+                email_address: owner.email_address().clone(),
+            }
+            .into());
+        }
+
+        match owner.role() {
+            OwnerRole::Primary => {
+                let previous = AccountOwner::new(
+                    std::mem::replace(&mut self.account_name, owner.account_name().clone()),
+                    std::mem::replace(&mut self.email_address, owner.email_address().clone()),
+                    OwnerRole::Secondary,
+                );
+                self.joint_owners.push(previous);
+            }
+            OwnerRole::Secondary => self.joint_owners.push(owner.clone()),
+        }
+
+        let event = bank_account_events::OwnerAddedEvent {
+            account_id: self.id,
+            owner,
+        };
+        self.domain_events_mut().push(event.into());
+        Ok(())
+    }
+    /// 共同名義人を削除する．主たる名義人は削除できない
+    pub fn remove_owner(&mut self, email_address: &EmailAddress) -> Result<(), DomainError> {
+        self.ensure_open()?;
+        if &self.email_address == email_address {
+            return Err(BankAccountError::RemovePrimaryOwnerError {
+                email_address: email_address.clone(),
+            }
+            .into());
+        }
+        let Some(position) = self
+            .joint_owners
+            .iter()
+            .position(|owner| owner.email_address() == email_address)
+        else {
+            return Err(BankAccountError::OwnerNotFoundError {
+                email_address: email_address.clone(),
+            }
+            .into());
+        };
+        self.joint_owners.remove(position);
+
+        let event = bank_account_events::OwnerRemovedEvent {
+            account_id: self.id,
+            email_address: email_address.clone(),
+        };
+        self.domain_events_mut().push(event.into());
+        Ok(())
+    }
+    /// 当座貸越の限度額を設定する．0を設定すると当座貸越を利用できなくなる
+    pub fn set_overdraft_limit(&mut self, limit: Money) -> Result<(), DomainError> {
+        self.ensure_open()?;
+        // 既に利用中の当座貸越を下回る限度額は設定できない
+        if self.balance.checked_add(limit)?.is_negative() {
+            return Err(BankAccountError::OverdraftLimitBelowBalanceError {
+                limit,
+                balance: self.balance,
+            }
+            .into());
+        }
+
+        let event = bank_account_events::OverdraftLimitSetEvent {
+            account_id: self.id,
+            previous_limit: self.overdraft_limit,
+            limit,
+        };
+        self.overdraft_limit = limit;
+        self.domain_events_mut().push(event.into());
+        Ok(())
+    }
+    /// 残高が負の場合に当座貸越の手数料を引き落とす．引き出し・小切手のイベントの後に呼ぶ
+    pub fn charge_overdraft_fee(&mut self) -> Result<(), DomainError> {
+        if !self.balance.is_negative() {
+            return Ok(());
+        }
+        let fee = overdraft_fee(self.currency())?;
+        self.balance = self.balance.checked_sub(fee)?;
+
+        let event = bank_account_events::OverdraftFeeChargedEvent {
+            account_id: self.id,
+            fee,
+            balance: self.balance,
+        };
+        self.domain_events_mut().push(event.into());
+        Ok(())
+    }
+    /// as_ofを含む月の利息を計上する．rateは年利で，残高が正の場合のみ月割りの利息を切り捨てて預金する．
+    /// 既に計上済みの期間の場合は何もしない
+    pub fn accrue_interest(&mut self, rate: Decimal, as_of: NaiveDate) -> Result<(), DomainError> {
+        if rate.is_sign_negative() {
+            return Err(BankAccountError::InvalidInterestRateError { rate }.into());
+        }
+        let period = InterestPeriod::from_date(as_of);
+        if self.last_interest_period.is_some_and(|last| last >= period) {
+            return Ok(());
+        }
+        // 凍結中の口座にも利息は計上する
+        if !matches!(self.status, AccountStatus::Open | AccountStatus::Frozen) {
+            return Err(BankAccountError::AccountNotOpenError {
+                status: self.status,
+            }
+            .into());
+        }
+
+        let interest = if self.balance.is_positive() {
+            let amount = self
+                .balance
+                .amount()
+                .checked_mul(rate)
+                .and_then(|amount| amount.checked_div(Decimal::from(12)))
+                .ok_or(MoneyError::OverflowError)?
+                .round_dp_with_strategy(self.currency().minor_units(), RoundingStrategy::ToZero);
+            Money::new(amount, self.currency())?
+        } else {
+            Money::zero(self.currency())
+        };
+
+        // 預け入れと同じく，残高の上限を超える利息は計上しない
+        let limit = Money::new(CONFIG.BALANCE_UPPER_LIM, self.currency())?;
+        let exceed_balance = self.balance.checked_add(interest)?;
+        if !interest.is_zero() && exceed_balance > limit.into() {
+            return Err(BankAccountError::DepositExceedLimitError {
+                limit,
+                amount: interest,
+                exceed_balance,
+            }
+            .into());
+        }
+
+        self.last_interest_period = Some(period);
+        if interest.is_zero() {
+            return Ok(());
+        }
+        self.balance = exceed_balance;
+
+        let event = bank_account_events::InterestAccruedEvent {
+            account_id: self.id,
+            period,
+            rate,
+            interest,
+            balance: self.balance,
+        };
+        self.domain_events_mut().push(event.into());
+        Ok(())
     }
     /// 預金を行う
-    pub fn deposit_money(&mut self, amount: f64) -> Result<(), DomainError> {
-        if self.balance + amount > CONFIG.BALANCE_UPPER_LIM {
+    pub fn deposit_money(&mut self, amount: Money) -> Result<(), DomainError> {
+        self.ensure_open()?;
+        let limit = Money::new(CONFIG.BALANCE_UPPER_LIM, self.balance.currency())?;
+        let exceed_balance = self.balance.checked_add(amount)?;
+        if exceed_balance > limit.into() {
             Err(BankAccountError::DepositExceedLimitError {
-                limit: CONFIG.BALANCE_UPPER_LIM,
+                limit,
                 amount,
-                exceed_balance: self.balance + amount,
+                exceed_balance,
             }
             .into())
         } else {
-            self.balance += amount;
+            self.balance = exceed_balance;
             Ok(())
         }
     }
-    /// 引き出しを行う
-    pub fn withdraw_money(&mut self, amount: f64) -> Result<(), DomainError> {
-        if self.balance - amount > 0.0 {
-            self.balance -= amount;
-            Ok(())
-        } else {
-            Err(BankAccountError::WithdrawExceedBalanceError {
+    /// 引き出しを行う．集計期間内の引き出しの総額が口座・Atmの限度額を超える場合はエラーとなる
+    pub fn withdraw_money(
+        &mut self,
+        amount: Money,
+        daily_withdrawals: DailyWithdrawals,
+    ) -> Result<(), DomainError> {
+        self.ensure_open()?;
+
+        let DailyWithdrawals {
+            by_account,
+            at_atm,
+            count_by_account,
+        } = daily_withdrawals;
+        if let Some(limit) = self.account_type.withdrawal_count_limit() {
+            if count_by_account >= limit {
+                return Err(BankAccountError::WithdrawalCountExceeded {
+                    limit,
+                    count: count_by_account,
+                }
+                .into());
+            }
+        }
+        for (scope, limit, withdrawn) in [
+            (
+                DailyLimitScope::Account,
+                self.tier.daily_withdrawal_limit(amount.currency())?,
+                by_account,
+            ),
+            (
+                DailyLimitScope::Atm,
+                atm_daily_withdrawal_limit(amount.currency())?,
+                at_atm,
+            ),
+        ] {
+            if withdrawn.checked_add(amount)? > limit {
+                return Err(BankAccountError::DailyLimitExceeded {
+                    scope,
+                    limit,
+                    withdrawn,
+                    amount,
+                }
+                .into());
+            }
+        }
+
+        match self.balance_after_debit(amount) {
+            Ok(Some(balance)) => {
+                self.balance = balance;
+                Ok(())
+            }
+            Err(e @ MoneyError::CurrencyMismatchError { .. }) => Err(e.into()),
+            _ => Err(BankAccountError::WithdrawExceedBalanceError {
                 amount,
                 balance: self.balance,
             }
-            .into())
+            .into()),
         }
     }
     /// 小切手を利用する
-    pub fn write_check(&mut self, amount: f64, check_number: String) -> Result<(), DomainError> {
-        if self.balance - amount > 0.0 {
-            let event = bank_account_events::CustomerWroteCheckEvent {
-                account_id: self.id,
+    pub fn write_check(
+        &mut self,
+        amount: Money,
+        check_number: CheckNumber,
+        already_presented: bool,
+    ) -> Result<(), DomainError> {
+        self.ensure_open()?;
+        if !self.account_type.allows_check() {
+            return Err(BankAccountError::CheckNotAllowedError {
+                account_type: self.account_type,
+            }
+            .into());
+        }
+        // 同じ小切手の二重の呈示
+        if already_presented {
+            return Err(BankAccountError::DuplicateCheck { check_number }.into());
+        }
+        match self.balance_after_debit(amount) {
+            Ok(Some(balance)) => {
+                let event = bank_account_events::CustomerWroteCheckEvent {
+                    account_id: self.id,
+                    amount,
+                    check_number,
+                    balance,
+                };
+
+                self.balance = balance;
+                self.domain_events_mut().push(event.into());
+                self.charge_overdraft_fee()
+            }
+            Err(e @ MoneyError::CurrencyMismatchError { .. }) => Err(e.into()),
+            _ => Err(BankAccountError::CheckExceedBalanceError {
                 amount,
-                check_number,
                 balance: self.balance,
-            };
+            }
+            .into()),
+        }
+    }
+    /// 発行した小切手が決済されたことを記録する．settlementは記録済みの決済の結果で，
+    /// 既に決済済みの場合は何もせず，返金済みの場合はエラーとする
+    pub fn clear_check(
+        &mut self,
+        check_number: CheckNumber,
+        amount: Money,
+        settlement: Option<CheckSettlement>,
+    ) -> Result<(), DomainError> {
+        match settlement {
+            Some(CheckSettlement::Cleared) => return Ok(()),
+            Some(settlement @ CheckSettlement::Bounced) => {
+                return Err(BankAccountError::CheckAlreadySettledError {
+                    check_number,
+                    settlement,
+                }
+                .into())
+            }
+            None => {}
+        }
+        let event = bank_account_events::CheckClearedEvent {
+            account_id: self.id,
+            check_number,
+            amount,
+        };
+        self.domain_events_mut().push(event.into());
+        Ok(())
+    }
+    /// 発行した小切手が不渡りとなった場合に，引き落とした金額を返金する．
+    /// 補償のための処理であるため，口座の状態や残高の上限によらず返金する．
+    /// 同じ小切手を二度返金しないように，返金済みの場合は何もせず，決済済みの場合はエラーとする
+    pub fn bounce_check(
+        &mut self,
+        check_number: CheckNumber,
+        amount: Money,
+        reason: String,
+        settlement: Option<CheckSettlement>,
+    ) -> Result<(), DomainError> {
+        match settlement {
+            Some(CheckSettlement::Bounced) => return Ok(()),
+            Some(settlement @ CheckSettlement::Cleared) => {
+                return Err(BankAccountError::CheckAlreadySettledError {
+                    check_number,
+                    settlement,
+                }
+                .into())
+            }
+            None => {}
+        }
+        self.balance = self.balance.checked_add(amount)?;
 
-            self.balance -= amount;
-            self.domain_events_mut().push(event.into());
-            Ok(())
-        } else {
-            Err(BankAccountError::CheckExceedBalanceError {
+        let event = bank_account_events::CheckBouncedEvent {
+            account_id: self.id,
+            check_number,
+            amount,
+            reason,
+            balance: self.balance,
+        };
+        self.domain_events_mut().push(event.into());
+        Ok(())
+    }
+    /// 他のアカウントへ送金する(引き落とし側)
+    pub fn transfer_out(
+        &mut self,
+        to_account_id: BankAccountId,
+        amount: Money,
+    ) -> Result<(), DomainError> {
+        if to_account_id == self.id {
+            return Err(BankAccountError::SameAccountTransferError {
+                account_id: self.id,
+            }
+            .into());
+        }
+        self.ensure_open()?;
+        // 送金には当座貸越を利用できない
+        match self.balance.checked_sub(amount) {
+            Ok(balance) if !balance.is_negative() => {
+                self.balance = balance;
+                let event = bank_account_events::MoneyTransferredOutEvent {
+                    account_id: self.id,
+                    to_account_id,
+                    amount,
+                    balance,
+                };
+                self.domain_events_mut().push(event.into());
+                Ok(())
+            }
+            Err(e @ MoneyError::CurrencyMismatchError { .. }) => Err(e.into()),
+            _ => Err(BankAccountError::TransferExceedBalanceError {
                 amount,
                 balance: self.balance,
             }
-            .into())
+            .into()),
         }
     }
+    /// 他のアカウントからの送金を受ける(入金側)．amountは換算後の金額
+    pub fn transfer_in(
+        &mut self,
+        from_account_id: BankAccountId,
+        amount: Money,
+        applied_rate: ExchangeRate,
+    ) -> Result<(), DomainError> {
+        if from_account_id == self.id {
+            return Err(BankAccountError::SameAccountTransferError {
+                account_id: self.id,
+            }
+            .into());
+        }
+        self.deposit_money(amount)?;
+
+        let event = bank_account_events::MoneyTransferredInEvent {
+            account_id: self.id,
+            from_account_id,
+            amount,
+            balance: self.balance,
+            applied_rate,
+        };
+        self.domain_events_mut().push(event.into());
+        Ok(())
+    }
+}
+
+/// 当座貸越を利用した取引ごとの手数料
+fn overdraft_fee(currency: Currency) -> Result<Money, MoneyError> {
+    Money::new(CONFIG.OVERDRAFT_FEE, currency)
 }
 
 impl Aggregate for BankAccount {
@@ -167,45 +771,117 @@ pub mod orm {
     use sea_orm::entity::prelude::*;
     use serde::Serialize;
 
-    /// BankAccountに対するORMモデル．
+    /// BankAccountに対するORMモデル．シリアライズはアグリゲイトを経由する．
     #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize)]
+    #[serde(into = "BankAccount")]
     #[sea_orm(table_name = "bank_account")]
     pub struct Model {
         /// id
         #[sea_orm(primary_key, auto_increment = false, unique)]
         id: BankAccountId,
-        /// 口座が有効かどうか
-        opened: bool,
-        /// 残高
-        balance: f64,
+        /// 口座の状態
+        status: AccountStatus,
+        /// 口座のランク
+        tier: AccountTier,
+        /// 口座の種類
+        account_type: AccountType,
+        /// 残高．通貨はcurrencyの列で保持する．
+        balance: Balance,
+        /// 当座貸越の限度額．通貨はcurrencyの列で保持する．
+        overdraft_limit: Money,
+        /// 最後に利息を計上した期間
+        last_interest_period: Option<InterestPeriod>,
+        /// 口座の通貨
+        currency: Currency,
         /// メールアドレス
         email_address: EmailAddress,
-        /// 口座名
-        account_name: AccountName,
+        /// 口座名の名
+        first_name: String,
+        /// 口座名の姓
+        last_name: String,
+        /// 確認待ちの変更後のメールアドレス
+        pending_email_address: Option<EmailAddress>,
+        /// メールアドレスの変更を確認するトークン
+        email_change_token: Option<EmailChangeToken>,
+        /// トークンの有効期限
+        email_change_expires_at: Option<DateTimeUtc>,
     }
 
     #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-    pub enum Relation {}
+    pub enum Relation {
+        /// 共同名義人
+        #[sea_orm(has_many = "super::owner::orm::Entity")]
+        Owner,
+    }
+
+    impl Related<super::owner::orm::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Owner.def()
+        }
+    }
 
     impl ActiveModelBehavior for ActiveModel {}
 
+    impl Model {
+        /// 共同名義人の子テーブルの行と合わせてアグリゲイトに変換する
+        pub fn into_aggregate(self, owners: Vec<super::owner::orm::Model>) -> BankAccount {
+            BankAccount::from(self).with_owner_models(owners)
+        }
+    }
+
+    impl BankAccount {
+        /// 共同名義人の子テーブルの行を設定する．クエリで別に取得した共同名義人を合わせるために利用する
+        pub fn with_owner_models(mut self, owners: Vec<super::owner::orm::Model>) -> Self {
+            self.joint_owners = owners.into_iter().map(Into::into).collect();
+            self
+        }
+    }
+
     /// 双方のFromを実装することで，フィールド対応のバグを減らすことができる．
+    /// 共同名義人は子テーブルに保持するため，ここでは変換しない．
     impl From<Model> for BankAccount {
         fn from(value: Model) -> Self {
             let Model {
                 id,
-                opened,
+                status,
+                tier,
+                account_type,
                 balance,
+                overdraft_limit,
+                last_interest_period,
+                currency,
                 email_address,
-                account_name,
+                first_name,
+                last_name,
+                pending_email_address,
+                email_change_token,
+                email_change_expires_at,
             } = value;
 
+            // 3つの列が全て揃っている場合のみ確認待ちとする
+            let pending_email_change = match (
+                pending_email_address,
+                email_change_token,
+                email_change_expires_at,
+            ) {
+                (Some(email_address), Some(token), Some(expires_at)) => {
+                    Some(PendingEmailChange::new(email_address, token, expires_at))
+                }
+                _ => None,
+            };
+
             Self {
                 id,
-                opened,
-                balance,
+                status,
+                tier,
+                account_type,
+                balance: balance.with_currency(currency),
+                overdraft_limit: overdraft_limit.with_currency(currency),
+                last_interest_period,
                 email_address,
-                account_name,
+                account_name: AccountName::from_columns(first_name, last_name),
+                joint_owners: Vec::new(),
+                pending_email_change,
                 events_list: Default::default(),
             }
         }
@@ -215,19 +891,42 @@ pub mod orm {
         fn from(value: BankAccount) -> Self {
             let BankAccount {
                 id,
-                opened,
+                status,
+                tier,
+                account_type,
                 balance,
+                overdraft_limit,
+                last_interest_period,
                 email_address,
                 account_name,
+                joint_owners: _,
+                pending_email_change,
                 events_list: _,
             } = value;
 
+            let (pending_email_address, email_change_token, email_change_expires_at) =
+                match pending_email_change.map(PendingEmailChange::into_parts) {
+                    Some((email_address, token, expires_at)) => {
+                        (Some(email_address), Some(token), Some(expires_at))
+                    }
+                    None => (None, None, None),
+                };
+
             Self {
                 id,
-                opened,
+                status,
+                tier,
+                account_type,
+                currency: balance.currency(),
                 balance,
+                overdraft_limit,
+                last_interest_period,
                 email_address,
-                account_name,
+                first_name: account_name.first_name().to_string(),
+                last_name: account_name.last_name().to_string(),
+                pending_email_address,
+                email_change_token,
+                email_change_expires_at,
             }
         }
     }
@@ -241,12 +940,20 @@ impl fake::Dummy<fake::Faker> for BankAccount {
     fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
         use fake::{Fake, Faker};
 
+        let balance: Balance = Faker.fake_with_rng(rng);
+
         Self {
             id: Faker.fake_with_rng(rng),
-            opened: Faker.fake_with_rng(rng),
-            balance: (0.0..CONFIG.BALANCE_UPPER_LIM).fake_with_rng(rng),
+            status: Faker.fake_with_rng(rng),
+            tier: Faker.fake_with_rng(rng),
+            account_type: Faker.fake_with_rng(rng),
+            balance,
+            overdraft_limit: Money::zero(balance.currency()),
+            last_interest_period: None,
             email_address: Faker.fake_with_rng(rng),
             account_name: Faker.fake_with_rng(rng),
+            joint_owners: Vec::new(),
+            pending_email_change: None,
             events_list: DomainEventList::new(),
         }
     }
@@ -277,4 +984,627 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn transfer_between_accounts() {
+        use super::{AccountTier, AccountType};
+        use crate::error::{BankAccountError, DomainError};
+        use crate::money::{Currency, ExchangeRate, Money};
+        use ddd_cqrs_core::Aggregate;
+
+        let jpy = |amount: &str| format!("{amount} JPY").parse::<Money>().unwrap();
+
+        let mut from = BankAccount::from_primitives(
+            "from@example.com".to_string(),
+            "Taro".to_string(),
+            "Yamada".to_string(),
+            Currency::JPY,
+            AccountTier::Standard,
+            AccountType::Checking,
+        )
+        .unwrap();
+        let mut to = BankAccount::from_primitives(
+            "to@example.com".to_string(),
+            "Hanako".to_string(),
+            "Sato".to_string(),
+            Currency::JPY,
+            AccountTier::Standard,
+            AccountType::Checking,
+        )
+        .unwrap();
+        from.open_account().unwrap();
+        to.open_account().unwrap();
+        from.domain_events_mut().take();
+        to.domain_events_mut().take();
+
+        from.deposit_money(jpy("1000")).unwrap();
+
+        from.transfer_out(to.id(), jpy("1000")).unwrap();
+        to.transfer_in(
+            from.id(),
+            jpy("1000"),
+            ExchangeRate::identity(Currency::JPY),
+        )
+        .unwrap();
+
+        assert!(from.balance().is_zero());
+        assert_eq!(to.balance(), jpy("1000").into());
+        assert_eq!(from.domain_events_mut().take().len(), 1);
+        assert_eq!(to.domain_events_mut().take().len(), 1);
+
+        assert!(matches!(
+            from.transfer_out(to.id(), jpy("1")),
+            Err(DomainError::BankAccountError(
+                BankAccountError::TransferExceedBalanceError { .. }
+            ))
+        ));
+        assert!(matches!(
+            to.transfer_out(to.id(), jpy("1")),
+            Err(DomainError::BankAccountError(
+                BankAccountError::SameAccountTransferError { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn account_lifecycle() {
+        use super::{AccountStatus, AccountTier, AccountType, DailyWithdrawals};
+        use crate::error::{BankAccountError, DomainError};
+        use crate::money::{Currency, Money};
+
+        let jpy = |amount: &str| format!("{amount} JPY").parse::<Money>().unwrap();
+
+        let mut account = BankAccount::from_primitives(
+            "lifecycle@example.com".to_string(),
+            "Taro".to_string(),
+            "Yamada".to_string(),
+            Currency::JPY,
+            AccountTier::Standard,
+            AccountType::Checking,
+        )
+        .unwrap();
+
+        // 開設前は取引できない
+        assert!(matches!(
+            account.deposit_money(jpy("100")),
+            Err(DomainError::BankAccountError(
+                BankAccountError::AccountNotOpenError {
+                    status: AccountStatus::Pending
+                }
+            ))
+        ));
+
+        account.open_account().unwrap();
+        account.deposit_money(jpy("100")).unwrap();
+
+        account.freeze_account("compliance".to_string()).unwrap();
+        assert!(matches!(
+            account.withdraw_money(jpy("10"), DailyWithdrawals::none(Currency::JPY)),
+            Err(DomainError::BankAccountError(
+                BankAccountError::AccountNotOpenError {
+                    status: AccountStatus::Frozen
+                }
+            ))
+        ));
+        assert!(account.close_account().is_err());
+
+        account.unfreeze_account().unwrap();
+        assert!(matches!(
+            account.close_account(),
+            Err(DomainError::BankAccountError(
+                BankAccountError::CloseWithBalanceError { .. }
+            ))
+        ));
+
+        account
+            .write_check(jpy("50"), "0001".parse().unwrap(), false)
+            .unwrap();
+        account
+            .transfer_out(super::BankAccountId::generate(), jpy("50"))
+            .unwrap();
+        account.close_account().unwrap();
+        assert_eq!(account.status(), AccountStatus::Closed);
+
+        assert!(matches!(
+            account.open_account(),
+            Err(DomainError::BankAccountError(
+                BankAccountError::InvalidStatusTransitionError { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn daily_withdrawal_limit() {
+        use super::{AccountTier, AccountType, DailyLimitScope, DailyWithdrawals};
+        use crate::error::{BankAccountError, DomainError};
+        use crate::money::{Currency, Money};
+        use config::CONFIG;
+
+        let jpy = |amount: &str| format!("{amount} JPY").parse::<Money>().unwrap();
+        let limit = Money::new(CONFIG.DAILY_WITHDRAWAL_LIMIT_STANDARD, Currency::JPY).unwrap();
+
+        let mut account = BankAccount::from_primitives(
+            "limit@example.com".to_string(),
+            "Taro".to_string(),
+            "Yamada".to_string(),
+            Currency::JPY,
+            AccountTier::Standard,
+            AccountType::Checking,
+        )
+        .unwrap();
+        account.open_account().unwrap();
+        account.deposit_money(limit).unwrap();
+        account.deposit_money(jpy("100")).unwrap();
+
+        // 限度額ちょうどまでは引き出せる
+        account
+            .withdraw_money(
+                jpy("100"),
+                DailyWithdrawals {
+                    by_account: limit.checked_sub(jpy("100")).unwrap(),
+                    at_atm: Money::zero(Currency::JPY),
+                    count_by_account: 0,
+                },
+            )
+            .unwrap();
+
+        assert!(matches!(
+            account.withdraw_money(
+                jpy("1"),
+                DailyWithdrawals {
+                    by_account: limit,
+                    at_atm: Money::zero(Currency::JPY),
+                    count_by_account: 0,
+                }
+            ),
+            Err(DomainError::BankAccountError(
+                BankAccountError::DailyLimitExceeded {
+                    scope: DailyLimitScope::Account,
+                    ..
+                }
+            ))
+        ));
+
+        let atm_limit = Money::new(CONFIG.ATM_DAILY_WITHDRAWAL_LIMIT, Currency::JPY).unwrap();
+        assert!(matches!(
+            account.withdraw_money(
+                jpy("1"),
+                DailyWithdrawals {
+                    by_account: Money::zero(Currency::JPY),
+                    at_atm: atm_limit,
+                    count_by_account: 0,
+                }
+            ),
+            Err(DomainError::BankAccountError(
+                BankAccountError::DailyLimitExceeded {
+                    scope: DailyLimitScope::Atm,
+                    ..
+                }
+            ))
+        ));
+    }
+
+    #[test]
+    fn overdraft() {
+        use super::{AccountTier, AccountType, DailyWithdrawals};
+        use crate::error::{BankAccountError, DomainError};
+        use crate::events::bank_account_events::BankAccountEvent;
+        use crate::money::{Balance, Currency, Money};
+        use config::CONFIG;
+        use ddd_cqrs_core::Aggregate;
+
+        let jpy = |amount: &str| format!("{amount} JPY").parse::<Money>().unwrap();
+        let fee = Money::new(CONFIG.OVERDRAFT_FEE, Currency::JPY).unwrap();
+
+        let mut account = BankAccount::from_primitives(
+            "overdraft@example.com".to_string(),
+            "Taro".to_string(),
+            "Yamada".to_string(),
+            Currency::JPY,
+            AccountTier::Standard,
+            AccountType::Checking,
+        )
+        .unwrap();
+        account.open_account().unwrap();
+        account.deposit_money(jpy("1000")).unwrap();
+
+        // 当座貸越が無い場合は残高を超えて引き出せない
+        assert!(matches!(
+            account.withdraw_money(jpy("1000"), DailyWithdrawals::none(Currency::JPY)),
+            Err(DomainError::BankAccountError(
+                BankAccountError::WithdrawExceedBalanceError { .. }
+            ))
+        ));
+
+        account.set_overdraft_limit(jpy("10000")).unwrap();
+        account.domain_events_mut().take();
+
+        account
+            .withdraw_money(jpy("5000"), DailyWithdrawals::none(Currency::JPY))
+            .unwrap();
+        account.charge_overdraft_fee().unwrap();
+        assert_eq!(
+            account.balance(),
+            "-4000 JPY"
+                .parse::<Balance>()
+                .unwrap()
+                .checked_sub(fee)
+                .unwrap()
+        );
+        assert!(matches!(
+            account.domain_events_mut().take().as_slice(),
+            [BankAccountEvent::OverdraftFeeChargedEvent(_)]
+        ));
+
+        // 手数料を含めて限度額を超える場合はエラー
+        assert!(matches!(
+            account.write_check(jpy("6000"), "0001".parse().unwrap(), false),
+            Err(DomainError::BankAccountError(
+                BankAccountError::CheckExceedBalanceError { .. }
+            ))
+        ));
+        // 利用中の当座貸越を下回る限度額は設定できない
+        assert!(matches!(
+            account.set_overdraft_limit(jpy("1000")),
+            Err(DomainError::BankAccountError(
+                BankAccountError::OverdraftLimitBelowBalanceError { .. }
+            ))
+        ));
+        // 当座貸越を利用中は解約できない
+        assert!(account.close_account().is_err());
+    }
+
+    #[test]
+    fn accrue_interest_once_per_period() {
+        use super::{AccountTier, AccountType, InterestPeriod};
+        use crate::error::{BankAccountError, DomainError};
+        use crate::money::{Currency, Money};
+        use chrono::NaiveDate;
+        use ddd_cqrs_core::Aggregate;
+        use rust_decimal::Decimal;
+
+        let jpy = |amount: &str| format!("{amount} JPY").parse::<Money>().unwrap();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        // 年利1.2%
+        let rate = Decimal::new(12, 3);
+
+        let mut account = BankAccount::from_primitives(
+            "interest@example.com".to_string(),
+            "Taro".to_string(),
+            "Yamada".to_string(),
+            Currency::JPY,
+            AccountTier::Standard,
+            AccountType::Savings,
+        )
+        .unwrap();
+        account.open_account().unwrap();
+        account.deposit_money(jpy("100050")).unwrap();
+        account.domain_events_mut().take();
+
+        // 100050 * 0.012 / 12 = 100.05 -> 100
+        account.accrue_interest(rate, date(2023, 8, 31)).unwrap();
+        assert_eq!(account.balance(), jpy("100150").into());
+        assert_eq!(
+            account.last_interest_period(),
+            Some(InterestPeriod::new(2023, 8).unwrap())
+        );
+        assert_eq!(account.domain_events_mut().take().len(), 1);
+
+        // 同じ期間・過去の期間には計上しない
+        account.accrue_interest(rate, date(2023, 8, 1)).unwrap();
+        account.accrue_interest(rate, date(2023, 7, 31)).unwrap();
+        assert_eq!(account.balance(), jpy("100150").into());
+        assert!(account.domain_events_mut().take().is_empty());
+
+        account.accrue_interest(rate, date(2023, 9, 30)).unwrap();
+        assert_eq!(account.balance(), jpy("100250").into());
+
+        assert!(matches!(
+            account.accrue_interest(Decimal::new(-1, 3), date(2023, 10, 31)),
+            Err(DomainError::BankAccountError(
+                BankAccountError::InvalidInterestRateError { .. }
+            ))
+        ));
+
+        // 残高の上限を超える利息は計上せず，期間も計上済みとしない
+        account.deposit_money(jpy("99890000")).unwrap();
+        assert!(matches!(
+            account.accrue_interest(rate, date(2023, 10, 31)),
+            Err(DomainError::BankAccountError(
+                BankAccountError::DepositExceedLimitError { .. }
+            ))
+        ));
+        assert_eq!(account.balance(), jpy("99990250").into());
+        assert_eq!(
+            account.last_interest_period(),
+            Some(InterestPeriod::new(2023, 9).unwrap())
+        );
+    }
+
+    #[test]
+    fn savings_account_rules() {
+        use super::{AccountTier, AccountType, DailyWithdrawals};
+        use crate::error::{BankAccountError, DomainError};
+        use crate::money::{Currency, Money};
+        use config::CONFIG;
+        use rust_decimal::Decimal;
+
+        let jpy = |amount: &str| format!("{amount} JPY").parse::<Money>().unwrap();
+
+        let mut savings = BankAccount::from_primitives(
+            "savings@example.com".to_string(),
+            "Taro".to_string(),
+            "Yamada".to_string(),
+            Currency::JPY,
+            AccountTier::Standard,
+            AccountType::Savings,
+        )
+        .unwrap();
+        savings.open_account().unwrap();
+        savings.deposit_money(jpy("1000")).unwrap();
+
+        assert!(matches!(
+            savings.write_check(jpy("100"), "0001".parse().unwrap(), false),
+            Err(DomainError::BankAccountError(
+                BankAccountError::CheckNotAllowedError {
+                    account_type: AccountType::Savings
+                }
+            ))
+        ));
+
+        let mut daily_withdrawals = DailyWithdrawals::none(Currency::JPY);
+        daily_withdrawals.count_by_account = CONFIG.SAVINGS_WITHDRAWAL_COUNT_LIMIT - 1;
+        savings
+            .withdraw_money(jpy("100"), daily_withdrawals)
+            .unwrap();
+
+        daily_withdrawals.count_by_account = CONFIG.SAVINGS_WITHDRAWAL_COUNT_LIMIT;
+        assert!(matches!(
+            savings.withdraw_money(jpy("100"), daily_withdrawals),
+            Err(DomainError::BankAccountError(
+                BankAccountError::WithdrawalCountExceeded { .. }
+            ))
+        ));
+        assert_eq!(
+            savings.annual_interest_rate(),
+            AccountTier::Standard.annual_interest_rate()
+        );
+
+        let checking = BankAccount::from_primitives(
+            "checking@example.com".to_string(),
+            "Taro".to_string(),
+            "Yamada".to_string(),
+            Currency::JPY,
+            AccountTier::Standard,
+            AccountType::Checking,
+        )
+        .unwrap();
+        assert_eq!(checking.annual_interest_rate(), Decimal::ZERO);
+    }
+
+    #[test]
+    fn joint_owners() {
+        use super::{AccountName, AccountOwner, AccountTier, AccountType, EmailAddress, OwnerRole};
+        use crate::error::{BankAccountError, DomainError};
+        use crate::money::Currency;
+
+        let email = |s: &str| EmailAddress::try_from(s.to_string()).unwrap();
+        let owner = |s: &str, role: OwnerRole| {
+            AccountOwner::new(
+                AccountName::from_primitives("Hanako".to_string(), "Yamada".to_string()).unwrap(),
+                email(s),
+                role,
+            )
+        };
+
+        let mut account = BankAccount::from_primitives(
+            "taro@example.com".to_string(),
+            "Taro".to_string(),
+            "Yamada".to_string(),
+            Currency::JPY,
+            AccountTier::Standard,
+            AccountType::Checking,
+        )
+        .unwrap();
+        account.open_account().unwrap();
+
+        account
+            .add_owner(owner("hanako@example.com", OwnerRole::Secondary))
+            .unwrap();
+        assert!(account.is_owned_by(&email("hanako@example.com")));
+        assert_eq!(account.owners().len(), 2);
+        assert!(matches!(
+            account.add_owner(owner("hanako@example.com", OwnerRole::Secondary)),
+            Err(DomainError::BankAccountError(
+                BankAccountError::OwnerAlreadyExistsError { .. }
+            ))
+        ));
+        assert!(matches!(
+            account.remove_owner(&email("taro@example.com")),
+            Err(DomainError::BankAccountError(
+                BankAccountError::RemovePrimaryOwnerError { .. }
+            ))
+        ));
+
+        // 主名義人を追加すると，以前の主名義人は共同名義人になる
+        account
+            .add_owner(owner("jiro@example.com", OwnerRole::Primary))
+            .unwrap();
+        assert_eq!(account.email_address(), &email("jiro@example.com"));
+        account.remove_owner(&email("taro@example.com")).unwrap();
+        account.remove_owner(&email("hanako@example.com")).unwrap();
+        assert!(account.joint_owners().is_empty());
+        assert!(matches!(
+            account.remove_owner(&email("hanako@example.com")),
+            Err(DomainError::BankAccountError(
+                BankAccountError::OwnerNotFoundError { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn email_change() {
+        use super::{
+            email_change_token_ttl, AccountTier, AccountType, EmailAddress, EmailChangeToken,
+        };
+        use crate::error::{BankAccountError, DomainError};
+        use crate::events::bank_account_events::BankAccountEvent;
+        use crate::money::Currency;
+        use chrono::{TimeZone, Utc};
+        use ddd_cqrs_core::Aggregate;
+
+        let email = |s: &str| EmailAddress::try_from(s.to_string()).unwrap();
+        let now = Utc.with_ymd_and_hms(2023, 10, 1, 12, 0, 0).unwrap();
+
+        let mut account = BankAccount::from_primitives(
+            "taro@example.com".to_string(),
+            "Taro".to_string(),
+            "Yamada".to_string(),
+            Currency::JPY,
+            AccountTier::Standard,
+            AccountType::Checking,
+        )
+        .unwrap();
+        account.open_account().unwrap();
+
+        assert!(matches!(
+            account.confirm_email_change(&EmailChangeToken::generate(), now),
+            Err(DomainError::BankAccountError(
+                BankAccountError::NoPendingEmailChangeError
+            ))
+        ));
+
+        account
+            .request_email_change(email("new@example.com"), now)
+            .unwrap();
+        let token = match account.domain_events_mut().take().pop() {
+            Some(BankAccountEvent::EmailChangeRequestedEvent(e)) => e.token,
+            other => panic!("unexpected event: {other:?}"),
+        };
+
+        assert!(matches!(
+            account.confirm_email_change(&EmailChangeToken::generate(), now),
+            Err(DomainError::BankAccountError(
+                BankAccountError::InvalidEmailChangeTokenError
+            ))
+        ));
+        assert!(matches!(
+            account.confirm_email_change(&token, now + email_change_token_ttl()),
+            Err(DomainError::BankAccountError(
+                BankAccountError::EmailChangeTokenExpiredError { .. }
+            ))
+        ));
+        assert_eq!(account.email_address(), &email("taro@example.com"));
+
+        account.confirm_email_change(&token, now).unwrap();
+        assert_eq!(account.email_address(), &email("new@example.com"));
+        assert!(account.pending_email_change().is_none());
+    }
+
+    #[test]
+    fn bounced_check_refund() {
+        use super::{AccountTier, AccountType, CheckSettlement};
+        use crate::error::{BankAccountError, DomainError};
+        use crate::money::{Currency, Money};
+        use ddd_cqrs_core::Aggregate;
+
+        let jpy = |amount: &str| format!("{amount} JPY").parse::<Money>().unwrap();
+
+        let mut account = BankAccount::from_primitives(
+            "taro@example.com".to_string(),
+            "Taro".to_string(),
+            "Yamada".to_string(),
+            Currency::JPY,
+            AccountTier::Standard,
+            AccountType::Checking,
+        )
+        .unwrap();
+        account.open_account().unwrap();
+        account.deposit_money(jpy("1000")).unwrap();
+        account
+            .write_check(jpy("300"), "0001".parse().unwrap(), false)
+            .unwrap();
+        assert_eq!(account.balance(), jpy("700").into());
+
+        account
+            .bounce_check(
+                "0001".parse().unwrap(),
+                jpy("300"),
+                "Refer to drawer".to_string(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(account.balance(), jpy("1000").into());
+
+        // 返金済みの小切手は再度返金しない
+        account.domain_events_mut().take();
+        account
+            .bounce_check(
+                "0001".parse().unwrap(),
+                jpy("300"),
+                "Refer to drawer".to_string(),
+                Some(CheckSettlement::Bounced),
+            )
+            .unwrap();
+        assert_eq!(account.balance(), jpy("1000").into());
+        assert!(account.domain_events_mut().take().is_empty());
+
+        // 返金済みの小切手は決済できず，決済済みの小切手は返金できない
+        assert!(matches!(
+            account.clear_check(
+                "0001".parse().unwrap(),
+                jpy("300"),
+                Some(CheckSettlement::Bounced)
+            ),
+            Err(DomainError::BankAccountError(
+                BankAccountError::CheckAlreadySettledError { .. }
+            ))
+        ));
+        assert!(matches!(
+            account.bounce_check(
+                "0002".parse().unwrap(),
+                jpy("300"),
+                "Refer to drawer".to_string(),
+                Some(CheckSettlement::Cleared),
+            ),
+            Err(DomainError::BankAccountError(
+                BankAccountError::CheckAlreadySettledError { .. }
+            ))
+        ));
+        assert_eq!(account.balance(), jpy("1000").into());
+    }
+
+    #[test]
+    fn duplicate_check() {
+        use super::{AccountTier, AccountType, CheckNumber};
+        use crate::error::{BankAccountError, DomainError};
+        use crate::money::{Currency, Money};
+
+        let jpy = |amount: &str| format!("{amount} JPY").parse::<Money>().unwrap();
+
+        assert!("12a4".parse::<CheckNumber>().is_err());
+        assert!("123".parse::<CheckNumber>().is_err());
+
+        let mut account = BankAccount::from_primitives(
+            "taro@example.com".to_string(),
+            "Taro".to_string(),
+            "Yamada".to_string(),
+            Currency::JPY,
+            AccountTier::Standard,
+            AccountType::Checking,
+        )
+        .unwrap();
+        account.open_account().unwrap();
+        account.deposit_money(jpy("1000")).unwrap();
+
+        assert!(matches!(
+            account.write_check(jpy("100"), "0001".parse().unwrap(), true),
+            Err(DomainError::BankAccountError(
+                BankAccountError::DuplicateCheck { .. }
+            ))
+        ));
+        account
+            .write_check(jpy("100"), "0002".parse().unwrap(), false)
+            .unwrap();
+    }
 }
@@ -0,0 +1,101 @@
+use crate::error::DomainError;
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// 口座のライフサイクルを表す状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "orm", derive(sea_orm_newtype::DeriveNewType))]
+#[cfg_attr(feature = "orm", sea_orm_newtype(try_from_into = "String"))]
+pub enum AccountStatus {
+    /// 開設手続き中
+    #[default]
+    Pending,
+    /// 利用可能
+    Open,
+    /// 凍結中．全ての取引ができない
+    Frozen,
+    /// 解約済み．以降は状態を変更できない
+    Closed,
+}
+
+impl AccountStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountStatus::Pending => "Pending",
+            AccountStatus::Open => "Open",
+            AccountStatus::Frozen => "Frozen",
+            AccountStatus::Closed => "Closed",
+        }
+    }
+    /// 現在の状態からtoへ遷移できるかどうか
+    pub fn can_transition_to(&self, to: AccountStatus) -> bool {
+        use AccountStatus::*;
+
+        matches!(
+            (self, to),
+            (Pending, Open) | (Open, Frozen) | (Frozen, Open) | (Open, Closed)
+        )
+    }
+}
+
+impl Display for AccountStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AccountStatus {
+    type Err = DomainError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Pending" => Ok(AccountStatus::Pending),
+            "Open" => Ok(AccountStatus::Open),
+            "Frozen" => Ok(AccountStatus::Frozen),
+            "Closed" => Ok(AccountStatus::Closed),
+            _ => Err(DomainError::DomainParseError(format!(
+                "Unknown account status: {s}"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<String> for AccountStatus {
+    type Error = DomainError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<AccountStatus> for String {
+    fn from(value: AccountStatus) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+#[cfg(feature = "orm")]
+impl From<&AccountStatus> for sea_orm::Value {
+    fn from(value: &AccountStatus) -> Self {
+        value.as_str().into()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// impl Dummy
+
+#[cfg(any(test, feature = "fake"))]
+impl fake::Dummy<fake::Faker> for AccountStatus {
+    fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
+        use rand::seq::SliceRandom;
+
+        *[
+            AccountStatus::Pending,
+            AccountStatus::Open,
+            AccountStatus::Frozen,
+            AccountStatus::Closed,
+        ]
+        .choose(rng)
+        .unwrap()
+    }
+}
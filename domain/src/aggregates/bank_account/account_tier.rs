@@ -0,0 +1,95 @@
+use crate::error::{DomainError, MoneyError};
+use crate::money::{Currency, Money};
+
+use config::CONFIG;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// 口座のランク．引き出し限度額などが異なる
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "orm", derive(sea_orm_newtype::DeriveNewType))]
+#[cfg_attr(feature = "orm", sea_orm_newtype(try_from_into = "String"))]
+pub enum AccountTier {
+    #[default]
+    Standard,
+    Premium,
+}
+
+impl AccountTier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountTier::Standard => "Standard",
+            AccountTier::Premium => "Premium",
+        }
+    }
+    /// 集計期間あたりの引き出し限度額
+    pub fn daily_withdrawal_limit(&self, currency: Currency) -> Result<Money, MoneyError> {
+        let limit = match self {
+            AccountTier::Standard => CONFIG.DAILY_WITHDRAWAL_LIMIT_STANDARD,
+            AccountTier::Premium => CONFIG.DAILY_WITHDRAWAL_LIMIT_PREMIUM,
+        };
+        Money::new(limit, currency)
+    }
+    /// 利息の年利
+    pub fn annual_interest_rate(&self) -> Decimal {
+        match self {
+            AccountTier::Standard => CONFIG.INTEREST_RATE_STANDARD,
+            AccountTier::Premium => CONFIG.INTEREST_RATE_PREMIUM,
+        }
+    }
+}
+
+impl Display for AccountTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AccountTier {
+    type Err = DomainError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Standard" => Ok(AccountTier::Standard),
+            "Premium" => Ok(AccountTier::Premium),
+            _ => Err(DomainError::DomainParseError(format!(
+                "Unknown account tier: {s}"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<String> for AccountTier {
+    type Error = DomainError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<AccountTier> for String {
+    fn from(value: AccountTier) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+#[cfg(feature = "orm")]
+impl From<&AccountTier> for sea_orm::Value {
+    fn from(value: &AccountTier) -> Self {
+        value.as_str().into()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// impl Dummy
+
+#[cfg(any(test, feature = "fake"))]
+impl fake::Dummy<fake::Faker> for AccountTier {
+    fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
+        if rng.gen_bool(0.5) {
+            AccountTier::Standard
+        } else {
+            AccountTier::Premium
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::error::DomainError;
+
+use config::CONFIG;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// 口座の種類．利用できる取引や利息の有無が異なる
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "orm", derive(sea_orm_newtype::DeriveNewType))]
+#[cfg_attr(feature = "orm", sea_orm_newtype(try_from_into = "String"))]
+pub enum AccountType {
+    /// 当座預金
+    #[default]
+    Checking,
+    /// 普通預金．小切手は利用できず，引き出しの回数に上限があるが利息がつく
+    Savings,
+    /// 法人口座
+    Business,
+}
+
+impl AccountType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountType::Checking => "Checking",
+            AccountType::Savings => "Savings",
+            AccountType::Business => "Business",
+        }
+    }
+    /// 小切手を利用できるかどうか
+    pub fn allows_check(&self) -> bool {
+        !matches!(self, AccountType::Savings)
+    }
+    /// 利息がつくかどうか
+    pub fn accrues_interest(&self) -> bool {
+        matches!(self, AccountType::Savings)
+    }
+    /// 集計期間あたりの引き出し回数の上限．Noneの場合は上限なし
+    pub fn withdrawal_count_limit(&self) -> Option<u64> {
+        match self {
+            AccountType::Savings => Some(CONFIG.SAVINGS_WITHDRAWAL_COUNT_LIMIT),
+            AccountType::Checking | AccountType::Business => None,
+        }
+    }
+}
+
+impl Display for AccountType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AccountType {
+    type Err = DomainError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Checking" => Ok(AccountType::Checking),
+            "Savings" => Ok(AccountType::Savings),
+            "Business" => Ok(AccountType::Business),
+            _ => Err(DomainError::DomainParseError(format!(
+                "Unknown account type: {s}"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<String> for AccountType {
+    type Error = DomainError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<AccountType> for String {
+    fn from(value: AccountType) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+#[cfg(feature = "orm")]
+impl From<&AccountType> for sea_orm::Value {
+    fn from(value: &AccountType) -> Self {
+        value.as_str().into()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// impl Dummy
+
+#[cfg(any(test, feature = "fake"))]
+impl fake::Dummy<fake::Faker> for AccountType {
+    fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
+        use rand::seq::SliceRandom;
+
+        *[
+            AccountType::Checking,
+            AccountType::Savings,
+            AccountType::Business,
+        ]
+        .choose(rng)
+        .unwrap()
+    }
+}
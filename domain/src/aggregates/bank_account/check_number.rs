@@ -0,0 +1,180 @@
+use crate::error::DomainError;
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+// -------------------------------------------------------------------------------------------------
+// CheckNumber
+
+/// 小切手番号の最小の桁数
+const MIN_DIGITS: usize = 4;
+/// 小切手番号の最大の桁数
+const MAX_DIGITS: usize = 10;
+
+/// 小切手番号．4桁から10桁の数字
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+#[cfg_attr(feature = "orm", derive(sea_orm_newtype::DeriveNewType))]
+#[cfg_attr(feature = "orm", sea_orm_newtype(try_from_into = "String"))]
+pub struct CheckNumber(String);
+
+impl CheckNumber {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for CheckNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for CheckNumber {
+    type Err = DomainError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if (MIN_DIGITS..=MAX_DIGITS).contains(&s.len()) && s.chars().all(|c| c.is_ascii_digit()) {
+            Ok(Self(s.to_string()))
+        } else {
+            Err(DomainError::DomainParseError(format!(
+                "Check number must be {MIN_DIGITS} to {MAX_DIGITS} digits: {s}"
+            )))
+        }
+    }
+}
+
+impl TryFrom<String> for CheckNumber {
+    type Error = DomainError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<CheckNumber> for String {
+    fn from(value: CheckNumber) -> Self {
+        value.0
+    }
+}
+
+#[cfg(feature = "orm")]
+impl From<&CheckNumber> for sea_orm::Value {
+    fn from(value: &CheckNumber) -> Self {
+        value.as_str().into()
+    }
+}
+
+#[cfg(any(test, feature = "fake"))]
+impl fake::Dummy<fake::Faker> for CheckNumber {
+    fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
+        Self(format!("{:06}", rng.gen_range(0..1_000_000)))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// CheckSettlement
+
+/// 発行した小切手の決済の結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "orm", derive(sea_orm_newtype::DeriveNewType))]
+#[cfg_attr(feature = "orm", sea_orm_newtype(try_from_into = "String"))]
+pub enum CheckSettlement {
+    /// 決済された
+    Cleared,
+    /// 不渡りとなり返金した
+    Bounced,
+}
+
+impl CheckSettlement {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckSettlement::Cleared => "Cleared",
+            CheckSettlement::Bounced => "Bounced",
+        }
+    }
+}
+
+impl Display for CheckSettlement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CheckSettlement {
+    type Err = DomainError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Cleared" => Ok(CheckSettlement::Cleared),
+            "Bounced" => Ok(CheckSettlement::Bounced),
+            _ => Err(DomainError::DomainParseError(format!(
+                "Unknown check settlement: {s}"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<String> for CheckSettlement {
+    type Error = DomainError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<CheckSettlement> for String {
+    fn from(value: CheckSettlement) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+#[cfg(feature = "orm")]
+impl From<&CheckSettlement> for sea_orm::Value {
+    fn from(value: &CheckSettlement) -> Self {
+        value.as_str().into()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// sea_orm用Model
+
+#[cfg(feature = "orm")]
+pub mod orm {
+    use super::{CheckNumber, CheckSettlement};
+    use crate::aggregates::bank_account::{self, BankAccountId};
+
+    use sea_orm::entity::prelude::*;
+    use serde::Serialize;
+
+    /// 口座ごとの使用済みの小切手番号．(account_id, check_number)に一意なインデックスを持つ
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize)]
+    #[sea_orm(table_name = "used_check_number")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        #[sea_orm(indexed)]
+        pub account_id: BankAccountId,
+        pub check_number: CheckNumber,
+        /// 小切手を発行した日時
+        pub used_at: DateTimeUtc,
+        /// 決済の結果．結果が得られるまではNone
+        pub settlement: Option<CheckSettlement>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(
+            belongs_to = "bank_account::orm::Entity",
+            from = "Column::AccountId",
+            to = "bank_account::orm::Column::Id",
+            on_delete = "Cascade"
+        )]
+        BankAccount,
+    }
+
+    impl Related<bank_account::orm::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::BankAccount.def()
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
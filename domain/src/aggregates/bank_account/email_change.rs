@@ -0,0 +1,116 @@
+use super::EmailAddress;
+use crate::error::DomainError;
+
+use chrono::{DateTime, Utc};
+use config::CONFIG;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use uuid::Uuid;
+
+// -------------------------------------------------------------------------------------------------
+// EmailChangeToken
+
+/// メールアドレスの変更を確認するトークン．新しいメールアドレスに送信する
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+#[cfg_attr(feature = "orm", derive(sea_orm_newtype::DeriveNewType))]
+#[cfg_attr(feature = "orm", sea_orm_newtype(try_from_into = "String"))]
+pub struct EmailChangeToken(String);
+
+impl EmailChangeToken {
+    /// 推測できないトークンを生成
+    pub fn generate() -> Self {
+        Self(Uuid::new_v4().simple().to_string())
+    }
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for EmailChangeToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for EmailChangeToken {
+    type Error = DomainError;
+    /// 32桁の16進数でなければならない
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.len() == 32 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+            Ok(Self(value.to_ascii_lowercase()))
+        } else {
+            Err(DomainError::DomainParseError(format!(
+                "Invalid email change token: {value}"
+            )))
+        }
+    }
+}
+
+impl From<EmailChangeToken> for String {
+    fn from(value: EmailChangeToken) -> Self {
+        value.0
+    }
+}
+
+#[cfg(feature = "orm")]
+impl From<&EmailChangeToken> for sea_orm::Value {
+    fn from(value: &EmailChangeToken) -> Self {
+        value.as_str().into()
+    }
+}
+
+#[cfg(any(test, feature = "fake"))]
+impl fake::Dummy<fake::Faker> for EmailChangeToken {
+    fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, _: &mut R) -> Self {
+        EmailChangeToken::generate()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// PendingEmailChange
+
+/// 確認待ちのメールアドレスの変更
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingEmailChange {
+    email_address: EmailAddress,
+    token: EmailChangeToken,
+    expires_at: DateTime<Utc>,
+}
+
+impl PendingEmailChange {
+    pub fn new(
+        email_address: EmailAddress,
+        token: EmailChangeToken,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            email_address,
+            token,
+            expires_at,
+        }
+    }
+    /// 変更後のメールアドレス
+    pub fn email_address(&self) -> &EmailAddress {
+        &self.email_address
+    }
+    pub fn token(&self) -> &EmailChangeToken {
+        &self.token
+    }
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        self.expires_at
+    }
+    /// (変更後のメールアドレス，トークン，有効期限)に分解する
+    pub fn into_parts(self) -> (EmailAddress, EmailChangeToken, DateTime<Utc>) {
+        (self.email_address, self.token, self.expires_at)
+    }
+    /// 時刻nowの時点で期限切れかどうか
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+}
+
+/// メールアドレスの変更を確認するトークンの有効期間
+pub fn email_change_token_ttl() -> chrono::Duration {
+    chrono::Duration::minutes(CONFIG.EMAIL_CHANGE_TOKEN_TTL_MINUTES)
+}
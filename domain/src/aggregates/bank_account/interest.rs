@@ -0,0 +1,85 @@
+use crate::error::DomainError;
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// 利息を計上する期間(月単位)．同じ期間に二度計上しないために利用する
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+#[cfg_attr(feature = "orm", derive(sea_orm_newtype::DeriveNewType))]
+#[cfg_attr(feature = "orm", sea_orm_newtype(try_from_into = "String"))]
+pub struct InterestPeriod {
+    year: i32,
+    month: u32,
+}
+
+impl InterestPeriod {
+    /// 月は1から12でなければならない
+    pub fn new(year: i32, month: u32) -> Result<Self, DomainError> {
+        if !(1..=12).contains(&month) {
+            return Err(DomainError::DomainParseError(format!(
+                "Invalid interest period month: {month}"
+            )));
+        }
+        Ok(Self { year, month })
+    }
+    /// 日付を含む期間
+    pub fn from_date(date: NaiveDate) -> Self {
+        Self {
+            year: date.year(),
+            month: date.month(),
+        }
+    }
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+}
+
+impl Display for InterestPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}", self.year, self.month)
+    }
+}
+
+impl FromStr for InterestPeriod {
+    type Err = DomainError;
+    /// "2023-08"の形式からパースする
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, month) = s.trim().split_once('-').ok_or_else(|| {
+            DomainError::DomainParseError(format!("Invalid interest period: {s}"))
+        })?;
+        let year = year
+            .parse()
+            .map_err(|_| DomainError::DomainParseError(format!("Invalid interest period: {s}")))?;
+        let month = month
+            .parse()
+            .map_err(|_| DomainError::DomainParseError(format!("Invalid interest period: {s}")))?;
+
+        InterestPeriod::new(year, month)
+    }
+}
+
+impl TryFrom<String> for InterestPeriod {
+    type Error = DomainError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<InterestPeriod> for String {
+    fn from(value: InterestPeriod) -> Self {
+        value.to_string()
+    }
+}
+
+#[cfg(feature = "orm")]
+impl From<&InterestPeriod> for sea_orm::Value {
+    fn from(value: &InterestPeriod) -> Self {
+        value.to_string().into()
+    }
+}
@@ -1,11 +1,18 @@
 use crate::error::DomainError;
 
 use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use unicode_normalization::UnicodeNormalization;
+
+/// 名・姓それぞれの最大の文字数(正規化後)
+const MAX_NAME_PART_CHARS: usize = 50;
+
+/// 名前に含めてよい記号．ミドルネームの区切りやハイフン付きの姓などに用いる
+const ALLOWED_PUNCTUATIONS: [char; 6] = [' ', '-', '\'', '’', '.', '・'];
 
 /// 口座名を表す型
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[cfg_attr(feature = "orm", derive(sea_orm_newtype::DeriveNewType))]
-#[cfg_attr(feature = "orm", sea_orm_newtype(try_from_into = "String"))]
+#[serde(try_from = "AccountNamePrimitives")]
 pub struct AccountName {
     // 名
     first_name: String,
@@ -13,11 +20,64 @@ pub struct AccountName {
     last_name: String,
 }
 
+/// デシリアライズ時に検証するための型
+#[derive(Deserialize)]
+struct AccountNamePrimitives {
+    first_name: String,
+    last_name: String,
+}
+
+impl TryFrom<AccountNamePrimitives> for AccountName {
+    type Error = DomainError;
+    fn try_from(value: AccountNamePrimitives) -> Result<Self, Self::Error> {
+        Self::from_primitives(value.first_name, value.last_name)
+    }
+}
+
+/// 名・姓の一方を正規化して検証する．
+/// NFKCで正規化し(全角英字・半角カナなどを統一する)，前後の空白を除いて連続する空白を一つにまとめる
+fn normalize_name_part(field: &str, value: &str) -> Result<String, DomainError> {
+    let normalized = value
+        .nfkc()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let invalid = |reason: String| {
+        Err(DomainError::DomainParseError(format!(
+            "Invalid account name: {field} {reason}"
+        )))
+    };
+
+    if normalized.is_empty() {
+        return invalid("must not be empty".to_string());
+    }
+    let chars = normalized.chars().count();
+    if chars > MAX_NAME_PART_CHARS {
+        return invalid(format!(
+            "must be at most {MAX_NAME_PART_CHARS} characters, but has {chars}"
+        ));
+    }
+    if let Some(c) = normalized
+        .chars()
+        .find(|c| !c.is_alphabetic() && !ALLOWED_PUNCTUATIONS.contains(c))
+    {
+        return invalid(format!("contains a disallowed character {c:?}"));
+    }
+    if !normalized.chars().any(char::is_alphabetic) {
+        return invalid("must contain at least one letter".to_string());
+    }
+
+    Ok(normalized)
+}
+
 impl AccountName {
+    /// 名・姓を正規化して作成する．空・長すぎる・文字以外を含む場合はエラー
     pub fn from_primitives(first_name: String, last_name: String) -> Result<Self, DomainError> {
         Ok(Self {
-            first_name,
-            last_name,
+            first_name: normalize_name_part("first_name", &first_name)?,
+            last_name: normalize_name_part("last_name", &last_name)?,
         })
     }
     pub fn first_name(&self) -> &str {
@@ -29,35 +89,19 @@ impl AccountName {
     pub fn to_name_string(&self) -> String {
         format!("{} {}", self.first_name, self.last_name)
     }
-}
-
-impl From<AccountName> for String {
-    fn from(value: AccountName) -> Self {
-        value.to_name_string()
-    }
-}
-
-impl TryFrom<String> for AccountName {
-    type Error = DomainError;
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        let names = value.split_whitespace().collect::<Vec<_>>();
-        if names.len() == 2 {
-            Ok(Self {
-                first_name: names[0].to_string(),
-                last_name: names[1].to_string(),
-            })
-        } else {
-            Err(DomainError::DomainParseError(
-                "Invalid account name.".to_string(),
-            ))
+    /// 保存時に検証済みの列から直接作成する．移行前の口座名はm20261019_000021で正規化・検証している
+    #[cfg(feature = "orm")]
+    pub(crate) fn from_columns(first_name: String, last_name: String) -> Self {
+        Self {
+            first_name,
+            last_name,
         }
     }
 }
 
-#[cfg(feature = "orm")]
-impl From<&AccountName> for sea_orm::Value {
-    fn from(value: &AccountName) -> Self {
-        value.to_name_string().into()
+impl Display for AccountName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_name_string())
     }
 }
 
@@ -72,3 +116,56 @@ impl fake::Dummy<fake::Faker> for AccountName {
         AccountName::from_primitives(first_name, last_name).unwrap()
     }
 }
+
+// -------------------------------------------------------------------------------------------------
+// test
+
+#[cfg(test)]
+mod test {
+    use super::AccountName;
+    use crate::DomainError;
+
+    #[test]
+    fn normalize_and_validate() {
+        let name = |first: &str, last: &str| {
+            AccountName::from_primitives(first.to_string(), last.to_string())
+        };
+
+        // 空白の整理とNFKCによる全角・半角の統一
+        let account_name = name("  Mary   Ann ", "Ｏ’Brien").unwrap();
+        assert_eq!(account_name.first_name(), "Mary Ann");
+        assert_eq!(account_name.last_name(), "O’Brien");
+        assert_eq!(name("ﾀﾛｳ", "山田").unwrap().first_name(), "タロウ");
+        // 結合文字は合成済みの文字にまとめる
+        assert_eq!(
+            name("Jose\u{301}", "García").unwrap(),
+            name("José", "García").unwrap()
+        );
+
+        let message = |res: Result<AccountName, DomainError>| match res {
+            Err(DomainError::DomainParseError(message)) => message,
+            res => panic!("unexpected: {res:?}"),
+        };
+        assert_eq!(
+            message(name(" ", "山田")),
+            "Invalid account name: first_name must not be empty"
+        );
+        assert_eq!(
+            message(name("太郎", "山田1")),
+            "Invalid account name: last_name contains a disallowed character '1'"
+        );
+        assert_eq!(
+            message(name("-", "山田")),
+            "Invalid account name: first_name must contain at least one letter"
+        );
+        assert!(message(name(&"あ".repeat(51), "山田")).contains("at most 50 characters"));
+
+        // デシリアライズでも検証・正規化する
+        let account_name: AccountName =
+            serde_json::from_str(r#"{"first_name":"太郎 ","last_name":"山田"}"#).unwrap();
+        assert_eq!(account_name, name("太郎", "山田").unwrap());
+        assert!(
+            serde_json::from_str::<AccountName>(r#"{"first_name":"","last_name":"山田"}"#).is_err()
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use super::{AccountName, EmailAddress};
+use crate::error::DomainError;
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+// -------------------------------------------------------------------------------------------------
+// OwnerRole
+
+/// 口座の名義人の役割
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "orm", derive(sea_orm_newtype::DeriveNewType))]
+#[cfg_attr(feature = "orm", sea_orm_newtype(try_from_into = "String"))]
+pub enum OwnerRole {
+    /// 主たる名義人．口座に一人だけ存在する
+    Primary,
+    /// 共同名義人
+    Secondary,
+}
+
+impl OwnerRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OwnerRole::Primary => "Primary",
+            OwnerRole::Secondary => "Secondary",
+        }
+    }
+}
+
+impl Display for OwnerRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OwnerRole {
+    type Err = DomainError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Primary" => Ok(OwnerRole::Primary),
+            "Secondary" => Ok(OwnerRole::Secondary),
+            _ => Err(DomainError::DomainParseError(format!(
+                "Unknown owner role: {s}"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<String> for OwnerRole {
+    type Error = DomainError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<OwnerRole> for String {
+    fn from(value: OwnerRole) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+#[cfg(feature = "orm")]
+impl From<&OwnerRole> for sea_orm::Value {
+    fn from(value: &OwnerRole) -> Self {
+        value.as_str().into()
+    }
+}
+
+#[cfg(any(test, feature = "fake"))]
+impl fake::Dummy<fake::Faker> for OwnerRole {
+    fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
+        if rng.gen_bool(0.5) {
+            OwnerRole::Primary
+        } else {
+            OwnerRole::Secondary
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// AccountOwner
+
+/// 口座の名義人
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountOwner {
+    account_name: AccountName,
+    email_address: EmailAddress,
+    role: OwnerRole,
+}
+
+impl AccountOwner {
+    pub fn new(account_name: AccountName, email_address: EmailAddress, role: OwnerRole) -> Self {
+        Self {
+            account_name,
+            email_address,
+            role,
+        }
+    }
+    pub fn account_name(&self) -> &AccountName {
+        &self.account_name
+    }
+    pub fn email_address(&self) -> &EmailAddress {
+        &self.email_address
+    }
+    pub fn role(&self) -> OwnerRole {
+        self.role
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// sea_orm用Model
+
+#[cfg(feature = "orm")]
+pub mod orm {
+    use super::{AccountName, AccountOwner, EmailAddress, OwnerRole};
+    use crate::aggregates::bank_account::{self, BankAccountId};
+
+    use sea_orm::entity::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    /// 共同名義人の子テーブル．主たる名義人はbank_accountのテーブルに保持する．
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+    #[sea_orm(table_name = "bank_account_owner")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        #[sea_orm(indexed)]
+        pub account_id: BankAccountId,
+        /// 口座名の名
+        pub first_name: String,
+        /// 口座名の姓
+        pub last_name: String,
+        #[sea_orm(indexed)]
+        pub email_address: EmailAddress,
+        pub role: OwnerRole,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(
+            belongs_to = "bank_account::orm::Entity",
+            from = "Column::AccountId",
+            to = "bank_account::orm::Column::Id",
+            on_delete = "Cascade"
+        )]
+        BankAccount,
+    }
+
+    impl Related<bank_account::orm::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::BankAccount.def()
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    impl Model {
+        /// 名義人からモデルを作成する．idは新しく生成する
+        pub fn from_owner(account_id: BankAccountId, owner: AccountOwner) -> Self {
+            let AccountOwner {
+                account_name,
+                email_address,
+                role,
+            } = owner;
+
+            Self {
+                id: Uuid::new_v4(),
+                account_id,
+                first_name: account_name.first_name().to_string(),
+                last_name: account_name.last_name().to_string(),
+                email_address,
+                role,
+            }
+        }
+    }
+
+    impl From<Model> for AccountOwner {
+        fn from(value: Model) -> Self {
+            let Model {
+                id: _,
+                account_id: _,
+                first_name,
+                last_name,
+                email_address,
+                role,
+            } = value;
+
+            Self {
+                account_name: AccountName::from_columns(first_name, last_name),
+                email_address,
+                role,
+            }
+        }
+    }
+}
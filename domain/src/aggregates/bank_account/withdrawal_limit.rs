@@ -0,0 +1,70 @@
+use crate::error::{DomainError, MoneyError};
+use crate::money::{Currency, Money};
+
+use config::CONFIG;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// 引き出し限度額の対象
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DailyLimitScope {
+    /// 口座ごとの限度額
+    Account,
+    /// Atmごとの限度額
+    Atm,
+}
+
+impl Display for DailyLimitScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DailyLimitScope::Account => f.write_str("account"),
+            DailyLimitScope::Atm => f.write_str("atm"),
+        }
+    }
+}
+
+/// 集計期間内にすでに引き出された金額
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DailyWithdrawals {
+    /// 口座から引き出された総額
+    pub by_account: Money,
+    /// 引き出しに用いるAtmから引き出された総額
+    pub at_atm: Money,
+    /// 口座からの引き出しの回数
+    pub count_by_account: u64,
+}
+
+impl DailyWithdrawals {
+    /// 集計期間内の引き出しが無い場合
+    pub fn none(currency: Currency) -> Self {
+        Self {
+            by_account: Money::zero(currency),
+            at_atm: Money::zero(currency),
+            count_by_account: 0,
+        }
+    }
+    /// リポジトリで集計した金額から作成する
+    pub fn from_totals(
+        by_account: Decimal,
+        at_atm: Decimal,
+        count_by_account: u64,
+        currency: Currency,
+    ) -> Result<Self, DomainError> {
+        Ok(Self {
+            by_account: Money::new(by_account, currency)?,
+            at_atm: Money::new(at_atm, currency)?,
+            count_by_account,
+        })
+    }
+}
+
+/// 引き出し限度額の集計期間．現在時刻からこの期間さかのぼった引き出しを集計する
+pub fn daily_withdrawal_window() -> chrono::Duration {
+    chrono::Duration::hours(CONFIG.DAILY_WITHDRAWAL_WINDOW_HOURS)
+}
+
+/// Atmごとの集計期間あたりの引き出し限度額
+pub fn atm_daily_withdrawal_limit(currency: Currency) -> Result<Money, MoneyError> {
+    Money::new(CONFIG.ATM_DAILY_WITHDRAWAL_LIMIT, currency)
+}
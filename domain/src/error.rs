@@ -1,3 +1,12 @@
+use crate::aggregates::atm::{AtmId, AtmStatus, Denomination};
+use crate::aggregates::bank_account::{
+    AccountStatus, AccountType, BankAccountId, CheckNumber, CheckSettlement, DailyLimitScope,
+    EmailAddress,
+};
+use crate::money::{Balance, Currency, Money};
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 // -------------------------------------------------------------------------------------------------
@@ -15,6 +24,30 @@ pub enum DomainError {
     /// Atmに関するエラー．Atmに関するロジック
     #[error("DomainError::AtmError: {0}")]
     AtmError(#[from] AtmError),
+    /// Moneyに関するエラー．金額の作成や演算で起こる
+    #[error("DomainError::MoneyError: {0}")]
+    MoneyError(#[from] MoneyError),
+}
+
+impl DomainError {
+    /// 機械可読な安定したエラーコード
+    pub fn code(&self) -> &'static str {
+        match self {
+            DomainError::DomainParseError(_) => "DOMAIN_PARSE_ERROR",
+            DomainError::BankAccountError(e) => e.code(),
+            DomainError::AtmError(e) => e.code(),
+            DomainError::MoneyError(e) => e.code(),
+        }
+    }
+    /// エラーの種類を表す短い説明
+    pub fn title(&self) -> &'static str {
+        match self {
+            DomainError::DomainParseError(_) => "Invalid domain value",
+            DomainError::BankAccountError(e) => e.title(),
+            DomainError::AtmError(e) => e.title(),
+            DomainError::MoneyError(e) => e.title(),
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -47,20 +80,102 @@ pub enum BankAccountError {
 BankAccountError::DepositExceedLimitError: As the deposit amount is {amount}, the balance is {exceed_balance}, which exceeds the {limit} limit. 
     "#)]
     DepositExceedLimitError {
-        limit: f64,
-        amount: f64,
-        exceed_balance: f64,
+        limit: Money,
+        amount: Money,
+        exceed_balance: Balance,
     },
     #[error(r#"
 BankAccountError::WithdrawExceedBalanceError: Attempts to withdraw amounts {amount} in excess of the deposit balance {balance}.
     "#)]
-    WithdrawExceedBalanceError { amount: f64, balance: f64 },
+    WithdrawExceedBalanceError { amount: Money, balance: Balance },
     #[error(r#"
 BankAccountError::CheckExceedBalanceError: Attempts to write check amounts {amount} in excess of the deposit balance {balance}.
     "#)]
-    CheckExceedBalanceError { amount: f64, balance: f64 },
+    CheckExceedBalanceError { amount: Money, balance: Balance },
+    #[error(r#"
+BankAccountError::TransferExceedBalanceError: Attempts to transfer amounts {amount} in excess of the deposit balance {balance}.
+    "#)]
+    TransferExceedBalanceError { amount: Money, balance: Balance },
+    #[error(r#"
+BankAccountError::SameAccountTransferError: Cannot transfer money from account {id} to itself.
+    "#, id = .account_id.to_uuid())]
+    SameAccountTransferError { account_id: BankAccountId },
+    #[error("BankAccountError::AccountNotOpenError: Account in {status} status cannot be used.")]
+    AccountNotOpenError { status: AccountStatus },
+    #[error("BankAccountError::InvalidStatusTransitionError: Cannot change from {from} to {to}.")]
+    InvalidStatusTransitionError {
+        from: AccountStatus,
+        to: AccountStatus,
+    },
+    #[error("BankAccountError::CloseWithBalanceError: Account has balance {balance}.")]
+    CloseWithBalanceError { balance: Balance },
+    #[error(r#"
+BankAccountError::DailyLimitExceeded: Withdrawing {amount} after {withdrawn} exceeds the {scope} daily limit {limit}.
+    "#)]
+    DailyLimitExceeded {
+        scope: DailyLimitScope,
+        limit: Money,
+        withdrawn: Money,
+        amount: Money,
+    },
+    #[error("BankAccountError::OverdraftLimitBelowBalanceError: Limit {limit} is below balance {balance}.")]
+    OverdraftLimitBelowBalanceError { limit: Money, balance: Balance },
+    #[error(
+        "BankAccountError::InvalidInterestRateError: Interest rate {rate} must not be negative."
+    )]
+    InvalidInterestRateError { rate: Decimal },
+    #[error("BankAccountError::CheckNotAllowedError: {account_type} account cannot write checks.")]
+    CheckNotAllowedError { account_type: AccountType },
+    #[error("BankAccountError::DuplicateCheck: Check {check_number} has already been presented.")]
+    DuplicateCheck { check_number: CheckNumber },
+    #[error("BankAccountError::CheckAlreadySettledError: Check {check_number} has already been {settlement}.")]
+    CheckAlreadySettledError {
+        check_number: CheckNumber,
+        settlement: CheckSettlement,
+    },
+    #[error("BankAccountError::WithdrawalCountExceeded: Already withdrew {count} times (limit {limit}).")]
+    WithdrawalCountExceeded { limit: u64, count: u64 },
+    #[error("BankAccountError::OwnerAlreadyExistsError: {email} is already an owner.", email = .email_address.as_str())]
+    OwnerAlreadyExistsError { email_address: EmailAddress },
+    #[error("BankAccountError::OwnerNotFoundError: {email} is not a joint owner.", email = .email_address.as_str())]
+    OwnerNotFoundError { email_address: EmailAddress },
+    #[error("BankAccountError::RemovePrimaryOwnerError: Cannot remove primary owner {email}.", email = .email_address.as_str())]
+    RemovePrimaryOwnerError { email_address: EmailAddress },
+    #[error("BankAccountError::NoPendingEmailChangeError: No email change has been requested.")]
+    NoPendingEmailChangeError,
+    #[error(
+        "BankAccountError::InvalidEmailChangeTokenError: The email change token does not match."
+    )]
+    InvalidEmailChangeTokenError,
+    #[error("BankAccountError::EmailChangeTokenExpiredError: The email change token expired at {expires_at}.")]
+    EmailChangeTokenExpiredError { expires_at: DateTime<Utc> },
 }
 
+crate::impl_error_code!(
+    BankAccountError,
+    DepositExceedLimitError => ("DEPOSIT_EXCEED_LIMIT", "Deposit exceeds balance limit"),
+    WithdrawExceedBalanceError => ("WITHDRAW_EXCEED_BALANCE", "Withdrawal exceeds balance"),
+    CheckExceedBalanceError => ("CHECK_EXCEED_BALANCE", "Check exceeds balance"),
+    TransferExceedBalanceError => ("TRANSFER_EXCEED_BALANCE", "Transfer exceeds balance"),
+    SameAccountTransferError => ("SAME_ACCOUNT_TRANSFER", "Transfer to the same account"),
+    AccountNotOpenError => ("ACCOUNT_NOT_OPEN", "Account is not open"),
+    InvalidStatusTransitionError => ("INVALID_ACCOUNT_STATUS_TRANSITION", "Invalid account status transition"),
+    CloseWithBalanceError => ("CLOSE_WITH_BALANCE", "Account still has balance"),
+    DailyLimitExceeded => ("DAILY_LIMIT_EXCEEDED", "Daily withdrawal limit exceeded"),
+    OverdraftLimitBelowBalanceError => ("OVERDRAFT_LIMIT_BELOW_BALANCE", "Overdraft limit is below overdrawn balance"),
+    InvalidInterestRateError => ("INVALID_INTEREST_RATE", "Invalid interest rate"),
+    CheckNotAllowedError => ("CHECK_NOT_ALLOWED", "Check is not allowed for the account type"),
+    DuplicateCheck => ("DUPLICATE_CHECK", "Check has already been presented"),
+    CheckAlreadySettledError => ("CHECK_ALREADY_SETTLED", "Check has already been settled"),
+    WithdrawalCountExceeded => ("WITHDRAWAL_COUNT_EXCEEDED", "Withdrawal count limit exceeded"),
+    OwnerAlreadyExistsError => ("OWNER_ALREADY_EXISTS", "Owner already exists"),
+    OwnerNotFoundError => ("OWNER_NOT_FOUND", "Owner not found"),
+    RemovePrimaryOwnerError => ("REMOVE_PRIMARY_OWNER", "Primary owner cannot be removed"),
+    NoPendingEmailChangeError => ("NO_PENDING_EMAIL_CHANGE", "No pending email change"),
+    InvalidEmailChangeTokenError => ("INVALID_EMAIL_CHANGE_TOKEN", "Invalid email change token"),
+    EmailChangeTokenExpiredError => ("EMAIL_CHANGE_TOKEN_EXPIRED", "Email change token expired"),
+);
+
 // -------------------------------------------------------------------------------------------------
 // AtmError
 
@@ -71,7 +186,89 @@ pub enum AtmError {
 AtmError::CannotWithdrawError: Total cash {total_cash} in Atm is less than withdraw amount {withdraw_amount}.
     "#)]
     CannotWithdrawError {
-        total_cash: f64,
-        withdraw_amount: f64,
+        total_cash: Money,
+        withdraw_amount: Money,
+    },
+    #[error("AtmError::AtmNotOperationalError: Atm {id} in {status} status cannot be used.", id = .atm_id.to_uuid())]
+    AtmNotOperationalError { atm_id: AtmId, status: AtmStatus },
+    #[error("AtmError::InvalidStatusTransitionError: Cannot change from {from} to {to}.")]
+    InvalidStatusTransitionError { from: AtmStatus, to: AtmStatus },
+    #[error("AtmError::AtmDecommissionedError: Atm {id} has been decommissioned.", id = .atm_id.to_uuid())]
+    AtmDecommissionedError { atm_id: AtmId },
+    #[error("AtmError::InvalidDenominationError: {denomination} is not a note of {currency}.")]
+    InvalidDenominationError {
+        denomination: Denomination,
+        currency: Currency,
     },
+    #[error("AtmError::UnrepresentableAmountError: {amount} cannot be made of notes.")]
+    UnrepresentableAmountError { amount: Money },
+    #[error("AtmError::CannotDispenseError: No combination of notes in cassettes makes {amount}.")]
+    CannotDispenseError { amount: Money },
+    #[error("AtmError::InvalidAddressError: Address {field} {reason}.")]
+    InvalidAddressError { field: String, reason: String },
+    #[error("AtmError::InvalidCoordinatesError: ({latitude}, {longitude}) is not a valid latitude and longitude.")]
+    InvalidCoordinatesError { latitude: f64, longitude: f64 },
+    #[error("AtmError::AtmLocationNotRegisteredError: Atm {id} has no registered location.", id = .atm_id.to_uuid())]
+    AtmLocationNotRegisteredError { atm_id: AtmId },
 }
+
+crate::impl_error_code!(
+    AtmError,
+    CannotWithdrawError => ("ATM_CANNOT_WITHDRAW", "Atm cannot dispense cash"),
+    AtmNotOperationalError => ("ATM_NOT_OPERATIONAL", "Atm is not in service"),
+    InvalidStatusTransitionError => ("INVALID_ATM_STATUS_TRANSITION", "Invalid atm status transition"),
+    AtmDecommissionedError => ("ATM_DECOMMISSIONED", "Atm has been decommissioned"),
+    InvalidDenominationError => ("ATM_INVALID_DENOMINATION", "Invalid note denomination"),
+    UnrepresentableAmountError => ("ATM_UNREPRESENTABLE_AMOUNT", "Amount cannot be made of notes"),
+    CannotDispenseError => ("ATM_CANNOT_DISPENSE", "Atm cannot dispense the amount with its notes"),
+    InvalidAddressError => ("ATM_INVALID_ADDRESS", "Invalid atm address"),
+    InvalidCoordinatesError => ("ATM_INVALID_COORDINATES", "Invalid atm coordinates"),
+    AtmLocationNotRegisteredError => ("ATM_LOCATION_NOT_REGISTERED", "Atm location is not registered"),
+);
+
+// -------------------------------------------------------------------------------------------------
+// MoneyError
+
+/// Moneyに関するエラー
+#[derive(thiserror::Error, Debug, Clone, Serialize, Deserialize)]
+pub enum MoneyError {
+    #[error(
+        r#"
+MoneyError::NegativeAmountError: Amount {amount} {currency} must not be negative.
+    "#
+    )]
+    NegativeAmountError { amount: Decimal, currency: Currency },
+    #[error(r#"
+MoneyError::InvalidPrecisionError: Amount {amount} is more precise than the minor unit of {currency}.
+    "#)]
+    InvalidPrecisionError { amount: Decimal, currency: Currency },
+    #[error(
+        r#"
+MoneyError::CurrencyMismatchError: Expected currency {expected}, but got {actual}.
+    "#
+    )]
+    CurrencyMismatchError {
+        expected: Currency,
+        actual: Currency,
+    },
+    #[error("MoneyError::OverflowError: Arithmetic overflow occurred.")]
+    OverflowError,
+    #[error("MoneyError::InvalidExchangeRateError: Rate {rate} from {from} to {to} is invalid.")]
+    InvalidExchangeRateError {
+        from: Currency,
+        to: Currency,
+        rate: Decimal,
+    },
+    #[error("MoneyError::ExchangeRateNotFoundError: No exchange rate from {from} to {to}.")]
+    ExchangeRateNotFoundError { from: Currency, to: Currency },
+}
+
+crate::impl_error_code!(
+    MoneyError,
+    NegativeAmountError => ("MONEY_NEGATIVE_AMOUNT", "Negative money amount"),
+    InvalidPrecisionError => ("MONEY_INVALID_PRECISION", "Money amount is too precise"),
+    CurrencyMismatchError => ("MONEY_CURRENCY_MISMATCH", "Currency mismatch"),
+    OverflowError => ("MONEY_OVERFLOW", "Money arithmetic overflow"),
+    InvalidExchangeRateError => ("INVALID_EXCHANGE_RATE", "Invalid exchange rate"),
+    ExchangeRateNotFoundError => ("EXCHANGE_RATE_NOT_FOUND", "Exchange rate not available"),
+);
@@ -1,2 +1,99 @@
-#[derive(Debug, Clone, PartialEq)]
-pub enum AtmEvent {}
+use crate::aggregates::atm::{AtmId, AtmLocation, AtmStatus, NoteBreakdown};
+use crate::money::Money;
+
+use serde::{Deserialize, Serialize};
+
+/// Atmが登録された時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct AtmRegisteredEvent {
+    pub atm_id: AtmId,
+    pub location: AtmLocation,
+    /// カセットに入れた紙幣の内訳
+    pub notes: NoteBreakdown,
+    pub total_cash: Money,
+}
+
+/// Atmに現金をチャージした時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct AtmCashChargedEvent {
+    pub atm_id: AtmId,
+    pub amount: Money,
+    /// チャージした紙幣の内訳
+    pub notes: NoteBreakdown,
+    /// チャージ後の現金の総額
+    pub total_cash: Money,
+}
+
+/// Atmから現金を払い出した時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct AtmCashDispensedEvent {
+    pub atm_id: AtmId,
+    pub amount: Money,
+    /// 払い出した紙幣の内訳
+    pub notes: NoteBreakdown,
+    /// 払い出し後の現金の総額
+    pub total_cash: Money,
+}
+
+/// Atmの現金の総額がしきい値を下回った時にレイズされるイベント．
+/// 下回った時点で一度だけレイズし，チャージによりしきい値以上に戻るまで再びレイズしない
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct AtmCashLowEvent {
+    pub atm_id: AtmId,
+    pub total_cash: Money,
+    pub threshold: Money,
+}
+
+/// Atmの稼働状態を変更した時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct AtmStatusChangedEvent {
+    pub atm_id: AtmId,
+    pub previous_status: AtmStatus,
+    pub status: AtmStatus,
+}
+
+/// Atmを撤去した時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct AtmDecommissionedEvent {
+    pub atm_id: AtmId,
+    /// 撤去時にAtmに残っていた現金の総額．回収の対象となる
+    pub total_cash: Money,
+}
+
+/// Atmを移設した時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct AtmRelocatedEvent {
+    pub atm_id: AtmId,
+    /// 移設前の場所．場所が未登録だったAtmの場合はNone
+    pub previous_location: Option<AtmLocation>,
+    pub location: AtmLocation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AtmEvent {
+    AtmRegisteredEvent(AtmRegisteredEvent),
+    AtmCashChargedEvent(AtmCashChargedEvent),
+    AtmCashDispensedEvent(AtmCashDispensedEvent),
+    AtmCashLowEvent(AtmCashLowEvent),
+    AtmStatusChangedEvent(AtmStatusChangedEvent),
+    AtmDecommissionedEvent(AtmDecommissionedEvent),
+    AtmRelocatedEvent(AtmRelocatedEvent),
+}
+
+crate::generate_enum_from!(
+    AtmEvent,
+    AtmRegisteredEvent,
+    AtmCashChargedEvent,
+    AtmCashDispensedEvent,
+    AtmCashLowEvent,
+    AtmStatusChangedEvent,
+    AtmDecommissionedEvent,
+    AtmRelocatedEvent
+);
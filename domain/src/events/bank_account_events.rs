@@ -1,6 +1,11 @@
 use crate::aggregates::atm::AtmId;
-use crate::aggregates::bank_account::{BankAccountId, EmailAddress};
+use crate::aggregates::bank_account::{
+    AccountOwner, BankAccountId, CheckNumber, EmailAddress, EmailChangeToken, InterestPeriod,
+};
+use crate::money::{Balance, ExchangeRate, Money};
 
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 /// アカウントが開設される時にレイズされるイベント
@@ -11,14 +16,40 @@ pub struct AccountOpenedEvent {
     pub email_address: EmailAddress,
 }
 
+/// アカウントが凍結された時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct AccountFrozenEvent {
+    pub account_id: BankAccountId,
+    /// 凍結の理由
+    pub reason: String,
+}
+
+/// アカウントの凍結が解除された時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct AccountUnfrozenEvent {
+    pub account_id: BankAccountId,
+}
+
+/// アカウントが解約された時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct AccountClosedEvent {
+    pub account_id: BankAccountId,
+}
+
 /// 預金する時にレイズされるイベント
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "server", derive(event_bus::Event))]
 pub struct CustomerDepositedMoneyEvent {
     pub account_id: BankAccountId,
-    pub amount: f64,
-    pub balance: f64,
+    /// Atmに預け入れた現金(Atmの通貨)
+    pub amount: Money,
+    pub balance: Balance,
     pub atm_id: AtmId,
+    /// 口座の通貨への換算に適用したレート
+    pub applied_rate: ExchangeRate,
 }
 
 /// 引き出した時にレイズされるイベント
@@ -26,9 +57,11 @@ pub struct CustomerDepositedMoneyEvent {
 #[cfg_attr(feature = "server", derive(event_bus::Event))]
 pub struct CustomerWithdrewCashEvent {
     pub account_id: BankAccountId,
-    pub amount: f64,
-    pub balance: f64,
+    pub amount: Money,
+    pub balance: Balance,
     pub atm_id: AtmId,
+    /// 引き出した日時．引き出し限度額の集計に利用する
+    pub withdrawn_at: DateTime<Utc>,
 }
 
 /// 小切手を発行したときにレイズされるイベント
@@ -36,24 +69,250 @@ pub struct CustomerWithdrewCashEvent {
 #[cfg_attr(feature = "server", derive(event_bus::Event))]
 pub struct CustomerWroteCheckEvent {
     pub account_id: BankAccountId,
-    /// 外部マイクロサービスを用いるため，プリミティブな型
-    pub check_number: String,
-    pub amount: f64,
-    pub balance: f64,
+    pub check_number: CheckNumber,
+    pub amount: Money,
+    /// 引き落とし後の残高
+    pub balance: Balance,
+}
+
+/// 発行した小切手が決済された時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct CheckClearedEvent {
+    pub account_id: BankAccountId,
+    pub check_number: CheckNumber,
+    pub amount: Money,
+}
+
+/// 発行した小切手が不渡りとなり，金額を返金した時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct CheckBouncedEvent {
+    pub account_id: BankAccountId,
+    pub check_number: CheckNumber,
+    pub amount: Money,
+    /// 不渡りの理由
+    pub reason: String,
+    /// 返金後の残高
+    pub balance: Balance,
+}
+
+/// 他のアカウントへ送金した時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct MoneyTransferredOutEvent {
+    pub account_id: BankAccountId,
+    pub to_account_id: BankAccountId,
+    /// 送金額(送金元の通貨)
+    pub amount: Money,
+    pub balance: Balance,
+}
+
+/// 他のアカウントから送金を受けた時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct MoneyTransferredInEvent {
+    pub account_id: BankAccountId,
+    pub from_account_id: BankAccountId,
+    /// 入金額(送金先の通貨)
+    pub amount: Money,
+    pub balance: Balance,
+    /// 送金先の通貨への換算に適用したレート
+    pub applied_rate: ExchangeRate,
+}
+
+/// 当座貸越の限度額を設定した時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct OverdraftLimitSetEvent {
+    pub account_id: BankAccountId,
+    /// 変更前の限度額
+    pub previous_limit: Money,
+    pub limit: Money,
+}
+
+/// 当座貸越の手数料を引き落とした時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct OverdraftFeeChargedEvent {
+    pub account_id: BankAccountId,
+    pub fee: Money,
+    pub balance: Balance,
+}
+
+/// 利息を計上した時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct InterestAccruedEvent {
+    pub account_id: BankAccountId,
+    /// 計上した期間
+    pub period: InterestPeriod,
+    /// 適用した年利
+    pub rate: Decimal,
+    pub interest: Money,
+    pub balance: Balance,
+}
+
+/// 名義人を追加した時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct OwnerAddedEvent {
+    pub account_id: BankAccountId,
+    pub owner: AccountOwner,
+}
+
+/// 共同名義人を削除した時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct OwnerRemovedEvent {
+    pub account_id: BankAccountId,
+    pub email_address: EmailAddress,
+}
+
+/// メールアドレスの変更を要求した時にレイズされるイベント．トークンを新しいメールアドレスに送信する
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct EmailChangeRequestedEvent {
+    pub account_id: BankAccountId,
+    /// 変更後のメールアドレス
+    pub email_address: EmailAddress,
+    pub token: EmailChangeToken,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// メールアドレスの変更を確定した時にレイズされるイベント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(event_bus::Event))]
+pub struct EmailAddressChangedEvent {
+    pub account_id: BankAccountId,
+    pub previous_email_address: EmailAddress,
+    pub email_address: EmailAddress,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum BankAccountEvent {
     AccountOpenedEvent(AccountOpenedEvent),
+    AccountFrozenEvent(AccountFrozenEvent),
+    AccountUnfrozenEvent(AccountUnfrozenEvent),
+    AccountClosedEvent(AccountClosedEvent),
     CustomerDepositedMoneyEvent(CustomerDepositedMoneyEvent),
     CustomerWithdrewCashEvent(CustomerWithdrewCashEvent),
     CustomerWroteCheckEvent(CustomerWroteCheckEvent),
+    CheckClearedEvent(CheckClearedEvent),
+    CheckBouncedEvent(CheckBouncedEvent),
+    MoneyTransferredOutEvent(MoneyTransferredOutEvent),
+    MoneyTransferredInEvent(MoneyTransferredInEvent),
+    OverdraftLimitSetEvent(OverdraftLimitSetEvent),
+    OverdraftFeeChargedEvent(OverdraftFeeChargedEvent),
+    InterestAccruedEvent(InterestAccruedEvent),
+    OwnerAddedEvent(OwnerAddedEvent),
+    OwnerRemovedEvent(OwnerRemovedEvent),
+    EmailChangeRequestedEvent(EmailChangeRequestedEvent),
+    EmailAddressChangedEvent(EmailAddressChangedEvent),
 }
 
 crate::generate_enum_from!(
     BankAccountEvent,
     AccountOpenedEvent,
+    AccountFrozenEvent,
+    AccountUnfrozenEvent,
+    AccountClosedEvent,
     CustomerDepositedMoneyEvent,
     CustomerWithdrewCashEvent,
-    CustomerWroteCheckEvent
+    CustomerWroteCheckEvent,
+    CheckClearedEvent,
+    CheckBouncedEvent,
+    MoneyTransferredOutEvent,
+    MoneyTransferredInEvent,
+    OverdraftLimitSetEvent,
+    OverdraftFeeChargedEvent,
+    InterestAccruedEvent,
+    OwnerAddedEvent,
+    OwnerRemovedEvent,
+    EmailChangeRequestedEvent,
+    EmailAddressChangedEvent
 );
+
+// -------------------------------------------------------------------------------------------------
+// sea_orm用Model
+
+#[cfg(feature = "orm")]
+pub mod orm {
+    /// 引き出しのイベントを記録するORMモデル．引き出し限度額の集計に利用する
+    pub mod customer_withdrew_cash {
+        use super::super::CustomerWithdrewCashEvent;
+        use crate::aggregates::atm::AtmId;
+        use crate::aggregates::bank_account::BankAccountId;
+        use crate::money::{Balance, Currency, Money};
+
+        use sea_orm::entity::prelude::*;
+        use serde::Serialize;
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize)]
+        #[sea_orm(table_name = "withdrawal_event")]
+        pub struct Model {
+            #[sea_orm(primary_key, auto_increment = false)]
+            pub id: Uuid,
+            #[sea_orm(indexed)]
+            pub account_id: BankAccountId,
+            #[sea_orm(indexed)]
+            pub atm_id: AtmId,
+            /// 引き出した金額．通貨はcurrencyの列で保持する．
+            pub amount: Money,
+            pub balance: Balance,
+            pub currency: Currency,
+            #[sea_orm(indexed)]
+            pub withdrawn_at: DateTimeUtc,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+
+        impl From<Model> for CustomerWithdrewCashEvent {
+            fn from(value: Model) -> Self {
+                let Model {
+                    id: _,
+                    account_id,
+                    atm_id,
+                    amount,
+                    balance,
+                    currency,
+                    withdrawn_at,
+                } = value;
+
+                Self {
+                    account_id,
+                    amount: amount.with_currency(currency),
+                    balance: balance.with_currency(currency),
+                    atm_id,
+                    withdrawn_at,
+                }
+            }
+        }
+
+        /// イベントにはidが無いため，新しく生成する
+        impl From<CustomerWithdrewCashEvent> for Model {
+            fn from(value: CustomerWithdrewCashEvent) -> Self {
+                let CustomerWithdrewCashEvent {
+                    account_id,
+                    amount,
+                    balance,
+                    atm_id,
+                    withdrawn_at,
+                } = value;
+
+                Self {
+                    id: Uuid::new_v4(),
+                    account_id,
+                    atm_id,
+                    currency: amount.currency(),
+                    amount,
+                    balance,
+                    withdrawn_at,
+                }
+            }
+        }
+    }
+}
@@ -1,12 +1,18 @@
 pub mod aggregates;
 pub mod events;
+pub mod sagas;
 
 #[cfg(feature = "server")]
 pub mod repositories;
+#[cfg(feature = "server")]
+pub mod services;
 
 mod error;
 mod id;
 mod macros;
+mod money;
 
-pub use error::DomainError;
+pub use error::{AtmError, BankAccountError, DomainError, MoneyError};
 pub use id::Id;
+pub use money::{Balance, Currency, ExchangeRate, Money};
+pub use rust_decimal::Decimal;
@@ -12,3 +12,31 @@ macro_rules! generate_enum_from {
 
     };
 }
+
+/// エラーの列挙体にエラーコード・タイトルとエラーコードからのバリアント名の逆引きを実装する．
+#[macro_export]
+macro_rules! impl_error_code {
+    ($enum_ty:ident, $( $variant:ident => ($code:literal, $title:literal) ),* $(,)?) => {
+        impl $enum_ty {
+            /// 機械可読な安定したエラーコード
+            pub fn code(&self) -> &'static str {
+                match self {
+                    $( $enum_ty::$variant { .. } => $code, )*
+                }
+            }
+            /// エラーの種類を表す短い説明
+            pub fn title(&self) -> &'static str {
+                match self {
+                    $( $enum_ty::$variant { .. } => $title, )*
+                }
+            }
+            /// エラーコードからバリアント名を取得する
+            pub fn variant_name_from_code(code: &str) -> Option<&'static str> {
+                match code {
+                    $( $code => Some(stringify!($variant)), )*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
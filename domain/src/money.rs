@@ -0,0 +1,591 @@
+use crate::error::{DomainError, MoneyError};
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::str::FromStr;
+
+// -------------------------------------------------------------------------------------------------
+// Currency
+
+/// 通貨を表す型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "orm", derive(sea_orm_newtype::DeriveNewType))]
+#[cfg_attr(feature = "orm", sea_orm_newtype(try_from_into = "String"))]
+pub enum Currency {
+    /// 日本円
+    #[default]
+    JPY,
+    /// 米ドル
+    USD,
+    /// ユーロ
+    EUR,
+}
+
+impl Currency {
+    /// 補助単位の桁数．金額はこれより細かい精度を持てない．
+    pub fn minor_units(&self) -> u32 {
+        match self {
+            Currency::JPY => 0,
+            Currency::USD | Currency::EUR => 2,
+        }
+    }
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Currency::JPY => "JPY",
+            Currency::USD => "USD",
+            Currency::EUR => "EUR",
+        }
+    }
+}
+
+impl Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = DomainError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "JPY" => Ok(Currency::JPY),
+            "USD" => Ok(Currency::USD),
+            "EUR" => Ok(Currency::EUR),
+            _ => Err(DomainError::DomainParseError(format!(
+                "Unknown currency: {s}"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<String> for Currency {
+    type Error = DomainError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Currency> for String {
+    fn from(value: Currency) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+#[cfg(feature = "orm")]
+impl From<&Currency> for sea_orm::Value {
+    fn from(value: &Currency) -> Self {
+        value.as_str().into()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Money
+
+/// 通貨付きの金額を表す値オブジェクト．金額は十進数で保持し，負の値をとらない．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+#[cfg_attr(feature = "orm", derive(sea_orm_newtype::DeriveNewType))]
+#[cfg_attr(feature = "orm", sea_orm_newtype(try_from_into = "Decimal"))]
+pub struct Money {
+    /// 金額
+    amount: Decimal,
+    /// 通貨
+    currency: Currency,
+}
+
+impl Money {
+    /// 金額と通貨から作成する．負の金額や通貨の補助単位より細かい金額はエラーとなる．
+    pub fn new(amount: Decimal, currency: Currency) -> Result<Self, MoneyError> {
+        if amount.is_sign_negative() && !amount.is_zero() {
+            return Err(MoneyError::NegativeAmountError { amount, currency });
+        }
+        if amount.normalize().scale() > currency.minor_units() {
+            return Err(MoneyError::InvalidPrecisionError { amount, currency });
+        }
+        Ok(Self { amount, currency })
+    }
+    /// 金額0
+    pub fn zero(currency: Currency) -> Self {
+        Self {
+            amount: Decimal::ZERO,
+            currency,
+        }
+    }
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+    pub fn is_zero(&self) -> bool {
+        self.amount.is_zero()
+    }
+    /// データベースから読み込んだ金額に通貨の列の値を設定する．保存時に検証済みのため検証は行わない．
+    #[cfg(feature = "orm")]
+    pub(crate) fn with_currency(self, currency: Currency) -> Self {
+        Self {
+            amount: self.amount,
+            currency,
+        }
+    }
+    /// 加算．通貨が異なる場合やオーバーフローした場合はエラーとなる．
+    pub fn checked_add(self, rhs: Money) -> Result<Money, MoneyError> {
+        self.check_currency(&rhs)?;
+        let amount = self
+            .amount
+            .checked_add(rhs.amount)
+            .ok_or(MoneyError::OverflowError)?;
+        Money::new(amount, self.currency)
+    }
+    /// 減算．通貨が異なる場合や結果が負となる場合はエラーとなる．
+    pub fn checked_sub(self, rhs: Money) -> Result<Money, MoneyError> {
+        self.check_currency(&rhs)?;
+        let amount = self
+            .amount
+            .checked_sub(rhs.amount)
+            .ok_or(MoneyError::OverflowError)?;
+        Money::new(amount, self.currency)
+    }
+    fn check_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency == other.currency {
+            Ok(())
+        } else {
+            Err(MoneyError::CurrencyMismatchError {
+                expected: self.currency,
+                actual: other.currency,
+            })
+        }
+    }
+}
+
+/// 通貨が異なる場合は比較できない
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.currency == other.currency {
+            self.amount.partial_cmp(&other.amount)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency)
+    }
+}
+
+impl FromStr for Money {
+    type Err = DomainError;
+    /// "1000 JPY"の形式からパースする
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (amount, currency) = s
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| DomainError::DomainParseError(format!("Invalid money: {s}")))?;
+        let amount = Decimal::from_str(amount)
+            .map_err(|e| DomainError::DomainParseError(format!("Invalid money amount: {e}")))?;
+
+        Ok(Money::new(amount, currency.trim().parse()?)?)
+    }
+}
+
+impl TryFrom<String> for Money {
+    type Error = DomainError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Money> for String {
+    fn from(value: Money) -> Self {
+        value.to_string()
+    }
+}
+
+/// データベースのNUMERIC型からの変換．通貨は別の列で保持するため，ここではデフォルトの通貨とし補助単位の検査は行わない．
+impl TryFrom<Decimal> for Money {
+    type Error = DomainError;
+    fn try_from(value: Decimal) -> Result<Self, Self::Error> {
+        if value.is_sign_negative() && !value.is_zero() {
+            return Err(MoneyError::NegativeAmountError {
+                amount: value,
+                currency: Currency::default(),
+            }
+            .into());
+        }
+        Ok(Self {
+            amount: value,
+            currency: Currency::default(),
+        })
+    }
+}
+
+impl From<Money> for Decimal {
+    fn from(value: Money) -> Self {
+        value.amount
+    }
+}
+
+#[cfg(feature = "orm")]
+impl From<&Money> for sea_orm::Value {
+    fn from(value: &Money) -> Self {
+        value.amount.into()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Balance
+
+/// 口座の残高を表す値オブジェクト．当座貸越により負の値をとることができる．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+#[cfg_attr(feature = "orm", derive(sea_orm_newtype::DeriveNewType))]
+#[cfg_attr(feature = "orm", sea_orm_newtype(try_from_into = "Decimal"))]
+pub struct Balance {
+    /// 金額
+    amount: Decimal,
+    /// 通貨
+    currency: Currency,
+}
+
+impl Balance {
+    /// 金額と通貨から作成する．通貨の補助単位より細かい金額はエラーとなる．
+    pub fn new(amount: Decimal, currency: Currency) -> Result<Self, MoneyError> {
+        if amount.normalize().scale() > currency.minor_units() {
+            return Err(MoneyError::InvalidPrecisionError { amount, currency });
+        }
+        Ok(Self { amount, currency })
+    }
+    /// 残高0
+    pub fn zero(currency: Currency) -> Self {
+        Money::zero(currency).into()
+    }
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+    pub fn is_zero(&self) -> bool {
+        self.amount.is_zero()
+    }
+    /// 残高が負(当座貸越を利用中)かどうか
+    pub fn is_negative(&self) -> bool {
+        self.amount.is_sign_negative() && !self.amount.is_zero()
+    }
+    /// 残高が正かどうか
+    pub fn is_positive(&self) -> bool {
+        self.amount.is_sign_positive() && !self.amount.is_zero()
+    }
+    /// データベースから読み込んだ金額に通貨の列の値を設定する．保存時に検証済みのため検証は行わない．
+    #[cfg(feature = "orm")]
+    pub(crate) fn with_currency(self, currency: Currency) -> Self {
+        Self {
+            amount: self.amount,
+            currency,
+        }
+    }
+    /// 加算．通貨が異なる場合やオーバーフローした場合はエラーとなる．
+    pub fn checked_add(self, rhs: Money) -> Result<Balance, MoneyError> {
+        self.check_currency(&rhs)?;
+        let amount = self
+            .amount
+            .checked_add(rhs.amount)
+            .ok_or(MoneyError::OverflowError)?;
+        Balance::new(amount, self.currency)
+    }
+    /// 減算．結果は負となってもよい．通貨が異なる場合やオーバーフローした場合はエラーとなる．
+    pub fn checked_sub(self, rhs: Money) -> Result<Balance, MoneyError> {
+        self.check_currency(&rhs)?;
+        let amount = self
+            .amount
+            .checked_sub(rhs.amount)
+            .ok_or(MoneyError::OverflowError)?;
+        Balance::new(amount, self.currency)
+    }
+    fn check_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency == other.currency {
+            Ok(())
+        } else {
+            Err(MoneyError::CurrencyMismatchError {
+                expected: self.currency,
+                actual: other.currency,
+            })
+        }
+    }
+}
+
+impl From<Money> for Balance {
+    fn from(value: Money) -> Self {
+        Self {
+            amount: value.amount,
+            currency: value.currency,
+        }
+    }
+}
+
+/// 負の残高は金額に変換できない
+impl TryFrom<Balance> for Money {
+    type Error = MoneyError;
+    fn try_from(value: Balance) -> Result<Self, Self::Error> {
+        Money::new(value.amount, value.currency)
+    }
+}
+
+/// 通貨が異なる場合は比較できない
+impl PartialOrd for Balance {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.currency == other.currency {
+            self.amount.partial_cmp(&other.amount)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Balance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency)
+    }
+}
+
+impl FromStr for Balance {
+    type Err = DomainError;
+    /// "-1000 JPY"の形式からパースする
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (amount, currency) = s
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| DomainError::DomainParseError(format!("Invalid balance: {s}")))?;
+        let amount = Decimal::from_str(amount)
+            .map_err(|e| DomainError::DomainParseError(format!("Invalid balance amount: {e}")))?;
+
+        Ok(Balance::new(amount, currency.trim().parse()?)?)
+    }
+}
+
+impl TryFrom<String> for Balance {
+    type Error = DomainError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Balance> for String {
+    fn from(value: Balance) -> Self {
+        value.to_string()
+    }
+}
+
+/// データベースのNUMERIC型からの変換．通貨は別の列で保持するため，ここではデフォルトの通貨とする．
+impl TryFrom<Decimal> for Balance {
+    type Error = DomainError;
+    fn try_from(value: Decimal) -> Result<Self, Self::Error> {
+        Ok(Self {
+            amount: value,
+            currency: Currency::default(),
+        })
+    }
+}
+
+impl From<Balance> for Decimal {
+    fn from(value: Balance) -> Self {
+        value.amount
+    }
+}
+
+#[cfg(feature = "orm")]
+impl From<&Balance> for sea_orm::Value {
+    fn from(value: &Balance) -> Self {
+        value.amount.into()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// ExchangeRate
+
+/// 通貨の換算に用いる為替レート．1単位のfromがrate単位のtoに相当する．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExchangeRate {
+    /// 換算元の通貨
+    from: Currency,
+    /// 換算先の通貨
+    to: Currency,
+    /// レート
+    rate: Decimal,
+}
+
+impl ExchangeRate {
+    /// レートは正でなければならない
+    pub fn new(from: Currency, to: Currency, rate: Decimal) -> Result<Self, MoneyError> {
+        if rate.is_sign_negative() || rate.is_zero() {
+            return Err(MoneyError::InvalidExchangeRateError { from, to, rate });
+        }
+        Ok(Self { from, to, rate })
+    }
+    /// 同一通貨間のレート
+    pub fn identity(currency: Currency) -> Self {
+        Self {
+            from: currency,
+            to: currency,
+            rate: Decimal::ONE,
+        }
+    }
+    pub fn from(&self) -> Currency {
+        self.from
+    }
+    pub fn to(&self) -> Currency {
+        self.to
+    }
+    pub fn rate(&self) -> Decimal {
+        self.rate
+    }
+    /// 逆方向のレート
+    pub fn inverse(&self) -> Result<Self, MoneyError> {
+        let rate = Decimal::ONE
+            .checked_div(self.rate)
+            .ok_or(MoneyError::OverflowError)?;
+        ExchangeRate::new(self.to, self.from, rate)
+    }
+    /// 金額を換算する．換算先の通貨の補助単位に銀行丸めで丸める．
+    pub fn convert(&self, money: Money) -> Result<Money, MoneyError> {
+        if money.currency != self.from {
+            return Err(MoneyError::CurrencyMismatchError {
+                expected: self.from,
+                actual: money.currency,
+            });
+        }
+        let amount = money
+            .amount
+            .checked_mul(self.rate)
+            .ok_or(MoneyError::OverflowError)?
+            .round_dp_with_strategy(
+                self.to.minor_units(),
+                rust_decimal::RoundingStrategy::MidpointNearestEven,
+            );
+        Money::new(amount, self.to)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// impl Dummy
+
+#[cfg(any(test, feature = "fake"))]
+impl fake::Dummy<fake::Faker> for Currency {
+    fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
+        use rand::seq::SliceRandom;
+
+        *[Currency::JPY, Currency::USD, Currency::EUR]
+            .choose(rng)
+            .unwrap()
+    }
+}
+
+#[cfg(any(test, feature = "fake"))]
+impl fake::Dummy<fake::Faker> for Money {
+    fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
+        use config::CONFIG;
+        use fake::{Fake, Faker};
+        use rust_decimal::prelude::ToPrimitive;
+
+        let currency: Currency = Faker.fake_with_rng(rng);
+        let upper = CONFIG.BALANCE_UPPER_LIM.to_i64().unwrap_or(i64::MAX);
+        let amount = Decimal::new(
+            (0..upper).fake_with_rng::<i64, R>(rng),
+            currency.minor_units(),
+        );
+        Money::new(amount, currency).unwrap()
+    }
+}
+
+#[cfg(any(test, feature = "fake"))]
+impl fake::Dummy<fake::Faker> for Balance {
+    fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
+        use fake::{Fake, Faker};
+
+        Faker.fake_with_rng::<Money, R>(rng).into()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// test
+
+#[cfg(test)]
+mod test {
+    use super::{Balance, Currency, ExchangeRate, Money};
+    use crate::error::MoneyError;
+    use rust_decimal::Decimal;
+
+    fn jpy(amount: i64) -> Money {
+        Money::new(Decimal::from(amount), Currency::JPY).unwrap()
+    }
+
+    #[test]
+    fn new_rejects_negative_and_invalid_precision() {
+        assert!(matches!(
+            Money::new(Decimal::from(-1), Currency::JPY),
+            Err(MoneyError::NegativeAmountError { .. })
+        ));
+        assert!(matches!(
+            Money::new(Decimal::new(105, 1), Currency::JPY),
+            Err(MoneyError::InvalidPrecisionError { .. })
+        ));
+        assert!(Money::new(Decimal::new(1000, 1), Currency::JPY).is_ok());
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        assert_eq!(jpy(100).checked_add(jpy(50)).unwrap(), jpy(150));
+        assert_eq!(jpy(100).checked_sub(jpy(100)).unwrap(), jpy(0));
+        assert!(matches!(
+            jpy(100).checked_sub(jpy(101)),
+            Err(MoneyError::NegativeAmountError { .. })
+        ));
+        assert!(matches!(
+            Money::new(Decimal::MAX, Currency::JPY)
+                .unwrap()
+                .checked_add(jpy(1)),
+            Err(MoneyError::OverflowError)
+        ));
+    }
+
+    #[test]
+    fn exchange_rate_convert() {
+        let usd = Money::new(Decimal::new(1050, 2), Currency::USD).unwrap();
+        let rate = ExchangeRate::new(Currency::USD, Currency::JPY, Decimal::new(1495, 1)).unwrap();
+
+        // 10.50 * 149.5 = 1569.75 -> 1570
+        assert_eq!(rate.convert(usd).unwrap(), jpy(1570));
+        assert!(matches!(
+            rate.convert(jpy(100)),
+            Err(MoneyError::CurrencyMismatchError { .. })
+        ));
+        assert!(ExchangeRate::new(Currency::USD, Currency::JPY, Decimal::ZERO).is_err());
+    }
+
+    #[test]
+    fn serde_as_string() {
+        let money = jpy(1234);
+        let json = serde_json::to_string(&money).unwrap();
+
+        assert_eq!(json, r#""1234 JPY""#);
+        assert_eq!(serde_json::from_str::<Money>(&json).unwrap(), money);
+        assert!(serde_json::from_str::<Money>(r#""-1 JPY""#).is_err());
+    }
+
+    #[test]
+    fn balance_can_be_negative() {
+        let balance = Balance::from(jpy(100)).checked_sub(jpy(150)).unwrap();
+
+        assert!(balance.is_negative());
+        assert_eq!(balance, "-50 JPY".parse::<Balance>().unwrap());
+        assert!(Money::try_from(balance).is_err());
+        assert_eq!(
+            Money::try_from(balance.checked_add(jpy(80)).unwrap()).unwrap(),
+            jpy(30)
+        );
+    }
+}
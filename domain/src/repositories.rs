@@ -1,72 +1,218 @@
-use ddd_cqrs_core::Aggregate;
-
-use crate::aggregates::{Atm, BankAccount};
-
-use std::future::Future;
-use std::pin::Pin;
-
-// -------------------------------------------------------------------------------------------------
-// Transaction
-
-/// トランザクション用のトレイト．ネストはできない．
-#[async_trait::async_trait]
-pub trait Transaction: Sized + Send + Sync {
-    // type Inner;
-    type Error;
-    type Pool: Send + Sync + Clone;
-    // // 内部のコネクション・トランザクション等を取得
-    // fn inner(&self) -> &Self::Inner;
-    // トランザクションのコンストラクタ
-    async fn begin(pool: &Self::Pool) -> Result<Self, Self::Error>;
-    // コミット
-    async fn commit(self) -> Result<(), Self::Error>;
-    // ロールバック
-    async fn rollback(self) -> Result<(), Self::Error>;
-    // クロージャーを与え、Okが返った場合はコミット，Errが返った場合はロールバックを行う．
-    async fn transaction<F, T>(&self, func: F) -> Result<T, Self::Error>
-    where
-        F: FnOnce() -> Pin<Box<dyn Future<Output = Result<T, Self::Error>> + Send>> + Send,
-        T: Send;
-}
-
-// -------------------------------------------------------------------------------------------------
-// 各種Repository
-
-/// ベースリポジトリ
-#[async_trait::async_trait]
-pub trait Repository: Send + Sync {
-    type Error: std::error::Error;
-    type Aggregate: Aggregate;
-    type Transaction: Transaction<Error = <Self as Repository>::Error>;
-
-    /// アグリゲイトを一つ保存(インサート)
-    async fn save<'t>(
-        &self,
-        aggregate: <Self as Repository>::Aggregate,
-        transaction: Option<&'t <Self as Repository>::Transaction>,
-    ) -> Result<(), <Self as Repository>::Error>;
-    /// アグリゲイトを一つアップデート
-    async fn edit<'t>(
-        &self,
-        aggregate: <Self as Repository>::Aggregate,
-        transaction: Option<&'t <Self as Repository>::Transaction>,
-    ) -> Result<(), <Self as Repository>::Error>;
-    /// アグリゲイトをidから取得
-    async fn find_by_id<'t>(
-        &self,
-        id: <<Self as Repository>::Aggregate as Aggregate>::IntoId,
-        transaction: Option<&'t <Self as Repository>::Transaction>,
-    ) -> Result<Self::Aggregate, <Self as Repository>::Error>;
-    /// 指定したidのアグリゲイトを削除
-    async fn remove<'t>(
-        &self,
-        id: <<Self as Repository>::Aggregate as Aggregate>::IntoId,
-        transaction: Option<&'t <Self as Repository>::Transaction>,
-    ) -> Result<(), <Self as Repository>::Error>;
-}
-
-/// BankAccountのリポジトリ(追加の処理を記述する)
-pub trait BankAccountRepository: Repository<Aggregate = BankAccount> {}
-
-/// Atmのリポジトリ(追加の処理を記述する)
-pub trait AtmRepository: Repository<Aggregate = Atm> {}
+use ddd_cqrs_core::{Aggregate, SagaRecord};
+
+use crate::aggregates::atm::AtmId;
+use crate::aggregates::bank_account::{BankAccountId, CheckNumber, CheckSettlement};
+use crate::aggregates::{Atm, BankAccount};
+use crate::events::bank_account_events::CustomerWithdrewCashEvent;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use std::future::Future;
+use std::pin::Pin;
+
+// -------------------------------------------------------------------------------------------------
+// Transaction
+
+/// トランザクション用のトレイト．ネストはできない．
+#[async_trait::async_trait]
+pub trait Transaction: Sized + Send + Sync {
+    // type Inner;
+    type Error;
+    type Pool: Send + Sync + Clone;
+    // // 内部のコネクション・トランザクション等を取得
+    // fn inner(&self) -> &Self::Inner;
+    // トランザクションのコンストラクタ
+    async fn begin(pool: &Self::Pool) -> Result<Self, Self::Error>;
+    // コミット
+    async fn commit(self) -> Result<(), Self::Error>;
+    // ロールバック
+    async fn rollback(self) -> Result<(), Self::Error>;
+    // クロージャーを与え、Okが返った場合はコミット，Errが返った場合はロールバックを行う．
+    async fn transaction<F, T>(&self, func: F) -> Result<T, Self::Error>
+    where
+        F: FnOnce() -> Pin<Box<dyn Future<Output = Result<T, Self::Error>> + Send>> + Send,
+        T: Send;
+}
+
+// -------------------------------------------------------------------------------------------------
+// 各種Repository
+
+/// ベースリポジトリ
+#[async_trait::async_trait]
+pub trait Repository: Send + Sync {
+    type Error: std::error::Error;
+    type Aggregate: Aggregate;
+    type Transaction: Transaction<Error = <Self as Repository>::Error>;
+
+    /// アグリゲイトを一つ保存(インサート)
+    async fn save<'t>(
+        &self,
+        aggregate: <Self as Repository>::Aggregate,
+        transaction: Option<&'t <Self as Repository>::Transaction>,
+    ) -> Result<(), <Self as Repository>::Error>;
+    /// アグリゲイトを一つアップデート
+    async fn edit<'t>(
+        &self,
+        aggregate: <Self as Repository>::Aggregate,
+        transaction: Option<&'t <Self as Repository>::Transaction>,
+    ) -> Result<(), <Self as Repository>::Error>;
+    /// アグリゲイトをidから取得
+    async fn find_by_id<'t>(
+        &self,
+        id: <<Self as Repository>::Aggregate as Aggregate>::IntoId,
+        transaction: Option<&'t <Self as Repository>::Transaction>,
+    ) -> Result<Self::Aggregate, <Self as Repository>::Error>;
+    /// 指定したidのアグリゲイトを削除
+    async fn remove<'t>(
+        &self,
+        id: <<Self as Repository>::Aggregate as Aggregate>::IntoId,
+        transaction: Option<&'t <Self as Repository>::Transaction>,
+    ) -> Result<(), <Self as Repository>::Error>;
+}
+
+/// BankAccountのリポジトリ(追加の処理を記述する)
+#[async_trait::async_trait]
+pub trait BankAccountRepository: Repository<Aggregate = BankAccount> {
+    /// アグリゲイトをidから取得し，トランザクションが終わるまで排他ロックを取得する
+    async fn find_by_id_for_update<'t>(
+        &self,
+        id: <<Self as Repository>::Aggregate as Aggregate>::IntoId,
+        transaction: &'t <Self as Repository>::Transaction,
+    ) -> Result<Self::Aggregate, <Self as Repository>::Error>;
+    /// 全てのアカウントのidを取得する．バッチ処理で全てのアカウントを走査するために利用する
+    async fn find_all_ids<'t>(
+        &self,
+        transaction: Option<&'t <Self as Repository>::Transaction>,
+    ) -> Result<Vec<BankAccountId>, <Self as Repository>::Error>;
+}
+
+/// Atmのリポジトリ(追加の処理を記述する)
+#[async_trait::async_trait]
+pub trait AtmRepository: Repository<Aggregate = Atm> {
+    /// アグリゲイトをidから取得し，トランザクションが終わるまで排他ロックを取得する．Atmを変更する場合は常に利用する
+    async fn find_by_id_for_update<'t>(
+        &self,
+        id: <<Self as Repository>::Aggregate as Aggregate>::IntoId,
+        transaction: &'t <Self as Repository>::Transaction,
+    ) -> Result<Self::Aggregate, <Self as Repository>::Error>;
+}
+
+// -------------------------------------------------------------------------------------------------
+// WithdrawalEventRepository
+
+/// 引き出しのイベントのリポジトリ．引き出し限度額の集計に利用する
+#[async_trait::async_trait]
+pub trait WithdrawalEventRepository: Send + Sync {
+    type Error: std::error::Error;
+    type Transaction: Transaction<Error = Self::Error>;
+
+    /// 引き出しのイベントを一つ保存
+    async fn save<'t>(
+        &self,
+        event: CustomerWithdrewCashEvent,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<(), Self::Error>;
+    /// since以降に指定したアカウントから引き出された金額の合計
+    async fn total_by_account<'t>(
+        &self,
+        account_id: BankAccountId,
+        since: DateTime<Utc>,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<Decimal, Self::Error>;
+    /// since以降に指定したアカウントから引き出された回数
+    async fn count_by_account<'t>(
+        &self,
+        account_id: BankAccountId,
+        since: DateTime<Utc>,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<u64, Self::Error>;
+    /// since以降に指定したAtmから引き出された金額の合計
+    async fn total_by_atm<'t>(
+        &self,
+        atm_id: AtmId,
+        since: DateTime<Utc>,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<Decimal, Self::Error>;
+}
+
+// -------------------------------------------------------------------------------------------------
+// UsedCheckNumberRepository
+
+/// 口座ごとの使用済みの小切手番号のリポジトリ．同じ小切手の二重の呈示の検出に利用する
+#[async_trait::async_trait]
+pub trait UsedCheckNumberRepository: Send + Sync {
+    type Error: std::error::Error;
+    type Transaction: Transaction<Error = Self::Error>;
+
+    /// 使用した小切手番号を一つ保存．使用済みの場合はBankAccountError::DuplicateCheck
+    async fn save<'t>(
+        &self,
+        account_id: BankAccountId,
+        check_number: CheckNumber,
+        used_at: DateTime<Utc>,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<(), Self::Error>;
+    /// 指定したアカウントで小切手番号が使用済みかどうか
+    async fn exists<'t>(
+        &self,
+        account_id: BankAccountId,
+        check_number: &CheckNumber,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<bool, Self::Error>;
+    /// 使用した小切手の決済の結果．結果が得られていない場合はNone
+    async fn find_settlement<'t>(
+        &self,
+        account_id: BankAccountId,
+        check_number: &CheckNumber,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<Option<CheckSettlement>, Self::Error>;
+    /// 使用した小切手の決済の結果を記録する
+    async fn settle<'t>(
+        &self,
+        account_id: BankAccountId,
+        check_number: &CheckNumber,
+        settlement: CheckSettlement,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<(), Self::Error>;
+}
+
+// -------------------------------------------------------------------------------------------------
+// SagaRepository
+
+/// サガの状態のリポジトリ．サガの種類に依存しないレコードとして保存する
+#[async_trait::async_trait]
+pub trait SagaRepository: Send + Sync {
+    type Error: std::error::Error;
+    type Transaction: Transaction<Error = Self::Error>;
+
+    /// 開始したサガを保存する
+    async fn save<'t>(
+        &self,
+        record: SagaRecord,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<(), Self::Error>;
+    /// 保存されているサガの版がexpected_versionの場合のみ，レコードの状態で更新する．更新した場合はtrue．
+    /// 複数の経路が同じサガを同時に進めないように，コマンドを実行する前にサガを確保するために利用する
+    async fn update<'t>(
+        &self,
+        record: SagaRecord,
+        expected_version: i64,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<bool, Self::Error>;
+    /// サガの状態をidから取得
+    async fn find_by_id<'t>(
+        &self,
+        id: Uuid,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<SagaRecord, Self::Error>;
+    /// 指定した種類の自動で進める状態のサガのうち，次に進める時刻がnowまでのもの．
+    /// 取得後に他の経路で進められる場合があるため，処理する前にupdateで確保する
+    async fn find_due<'t>(
+        &self,
+        saga_type: &'static str,
+        now: DateTime<Utc>,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<Vec<SagaRecord>, Self::Error>;
+}
@@ -0,0 +1,96 @@
+// -------------------------------------------------------------------------------------------------
+// sea_orm用Model
+
+#[cfg(feature = "orm")]
+pub mod orm {
+    use ddd_cqrs_core::{SagaRecord, SagaRecordError};
+
+    use sea_orm::entity::prelude::*;
+    use serde::Serialize;
+
+    /// サガの状態．状態と実行を待っているコマンドはサガの種類ごとのJSONとして保持し，
+    /// 進行状況と次に進める時刻で検索できるようにする
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize)]
+    #[sea_orm(table_name = "saga")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        #[sea_orm(indexed)]
+        pub saga_type: String,
+        #[sea_orm(indexed)]
+        pub status: String,
+        /// 保存した版．版を条件として更新し，同じサガを同時に進めないようにする
+        pub version: i64,
+        pub state: Json,
+        /// 実行を待っているコマンド
+        pub commands: Json,
+        pub started_at: DateTimeUtc,
+        /// タイムアウトの期限．期限の無いサガはNone
+        pub deadline: Option<DateTimeUtc>,
+        /// 次にサガを進める時刻
+        pub next_attempt_at: DateTimeUtc,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+
+    impl From<SagaRecord> for Model {
+        fn from(value: SagaRecord) -> Self {
+            let SagaRecord {
+                id,
+                saga_type,
+                status,
+                version,
+                state,
+                commands,
+                started_at,
+                deadline,
+                next_attempt_at,
+            } = value;
+
+            Self {
+                id,
+                saga_type,
+                status: status.to_string(),
+                version,
+                state,
+                commands,
+                started_at,
+                deadline,
+                next_attempt_at,
+            }
+        }
+    }
+
+    impl TryFrom<Model> for SagaRecord {
+        type Error = SagaRecordError;
+
+        fn try_from(value: Model) -> Result<Self, Self::Error> {
+            let Model {
+                id,
+                saga_type,
+                status,
+                version,
+                state,
+                commands,
+                started_at,
+                deadline,
+                next_attempt_at,
+            } = value;
+
+            Ok(Self {
+                id,
+                saga_type,
+                status: status.parse()?,
+                version,
+                state,
+                commands,
+                started_at,
+                deadline,
+                next_attempt_at,
+            })
+        }
+    }
+}
@@ -0,0 +1,3 @@
+mod currency_conversion;
+
+pub use currency_conversion::{CurrencyConversionService, StaticRateConversionService};
@@ -0,0 +1,80 @@
+use crate::error::{DomainError, MoneyError};
+use crate::money::{Currency, ExchangeRate, Money};
+
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+// -------------------------------------------------------------------------------------------------
+// CurrencyConversionService
+
+/// 通貨の換算を行うドメインサービス
+#[async_trait::async_trait]
+pub trait CurrencyConversionService: Send + Sync {
+    /// fromからtoへの為替レートを取得する
+    async fn exchange_rate(
+        &self,
+        from: Currency,
+        to: Currency,
+    ) -> Result<ExchangeRate, DomainError>;
+
+    /// 金額をtoの通貨に換算し，換算後の金額と適用したレートを返す
+    async fn convert(
+        &self,
+        money: Money,
+        to: Currency,
+    ) -> Result<(Money, ExchangeRate), DomainError> {
+        let rate = if money.currency() == to {
+            ExchangeRate::identity(to)
+        } else {
+            self.exchange_rate(money.currency(), to).await?
+        };
+
+        Ok((rate.convert(money)?, rate))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// StaticRateConversionService
+
+/// 固定のレートで換算を行うサービス．テストや外部サービスを利用できない場合に用いる．
+#[derive(Debug, Clone, Default)]
+pub struct StaticRateConversionService {
+    rates: HashMap<(Currency, Currency), ExchangeRate>,
+}
+
+impl StaticRateConversionService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// レートを登録する．逆方向のレートも同時に登録される．
+    pub fn with_rate(
+        mut self,
+        from: Currency,
+        to: Currency,
+        rate: Decimal,
+    ) -> Result<Self, DomainError> {
+        let rate = ExchangeRate::new(from, to, rate)?;
+        let inverse = rate.inverse()?;
+
+        self.rates.insert((from, to), rate);
+        self.rates.insert((to, from), inverse);
+        Ok(self)
+    }
+}
+
+#[async_trait::async_trait]
+impl CurrencyConversionService for StaticRateConversionService {
+    async fn exchange_rate(
+        &self,
+        from: Currency,
+        to: Currency,
+    ) -> Result<ExchangeRate, DomainError> {
+        if from == to {
+            return Ok(ExchangeRate::identity(from));
+        }
+        self.rates
+            .get(&(from, to))
+            .copied()
+            .ok_or_else(|| MoneyError::ExchangeRateNotFoundError { from, to }.into())
+    }
+}
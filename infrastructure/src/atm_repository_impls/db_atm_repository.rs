@@ -1,9 +1,12 @@
 use crate::{transactions::DbTransaction, InfraError};
-use domain::aggregates::atm::{self, Atm, AtmId};
+use domain::aggregates::atm::{self, cassette_orm, Atm, AtmId, NoteBreakdown};
 use domain::repositories::{AtmRepository, Repository};
 
 use derive_new::new;
-use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, IntoActiveModel};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    IntoActiveModel, ModelTrait, QueryFilter, QuerySelect,
+};
 
 /// データベースを用いたAtmRepository
 #[derive(Clone, Debug, new)]
@@ -11,6 +14,38 @@ pub struct DbAtmRepository {
     conn: DatabaseConnection,
 }
 
+impl DbAtmRepository {
+    /// カセットを子テーブルから取得し，アグリゲイトに変換する
+    async fn load_cassettes<C: ConnectionTrait>(
+        model: atm::orm::Model,
+        conn: &C,
+    ) -> Result<Atm, InfraError> {
+        let cassettes = model.find_related(cassette_orm::Entity).all(conn).await?;
+        Ok(model.into_aggregate(cassettes)?)
+    }
+    /// 子テーブルのカセットを置き換える
+    async fn replace_cassettes<C: ConnectionTrait>(
+        atm_id: AtmId,
+        notes: NoteBreakdown,
+        conn: &C,
+    ) -> Result<(), InfraError> {
+        cassette_orm::Entity::delete_many()
+            .filter(cassette_orm::Column::AtmId.eq(atm_id))
+            .exec(conn)
+            .await?;
+
+        if !notes.is_empty() {
+            cassette_orm::Entity::insert_many(notes.iter().map(|(denomination, count)| {
+                cassette_orm::Model::from_notes(atm_id, denomination, count).into_active_model()
+            }))
+            .exec(conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
 #[async_trait::async_trait]
 impl Repository for DbAtmRepository {
     type Error = InfraError;
@@ -22,14 +57,18 @@ impl Repository for DbAtmRepository {
         atm: Atm,
         transaction: Option<&'t Self::Transaction>,
     ) -> Result<(), Self::Error> {
+        let atm_id = atm.id();
+        let notes = atm.cassettes().notes().clone();
         let active_model = Into::<atm::orm::Model>::into(atm).into_active_model();
 
         match transaction {
             Some(transaction) => {
                 active_model.insert(transaction.inner()).await?;
+                Self::replace_cassettes(atm_id, notes, transaction.inner()).await?;
             }
             None => {
                 active_model.insert(&self.conn).await?;
+                Self::replace_cassettes(atm_id, notes, &self.conn).await?;
             }
         }
         Ok(())
@@ -39,6 +78,8 @@ impl Repository for DbAtmRepository {
         atm: Atm,
         transaction: Option<&'t Self::Transaction>,
     ) -> Result<(), Self::Error> {
+        let atm_id = atm.id();
+        let notes = atm.cassettes().notes().clone();
         let active_model = Into::<atm::orm::Model>::into(atm)
             .into_active_model()
             .reset_all();
@@ -46,9 +87,11 @@ impl Repository for DbAtmRepository {
         match transaction {
             Some(transaction) => {
                 active_model.update(transaction.inner()).await?;
+                Self::replace_cassettes(atm_id, notes, transaction.inner()).await?;
             }
             None => {
                 active_model.update(&self.conn).await?;
+                Self::replace_cassettes(atm_id, notes, &self.conn).await?;
             }
         }
 
@@ -63,13 +106,19 @@ impl Repository for DbAtmRepository {
             let select = atm::orm::Entity::find_by_id(id);
 
             match transaction {
-                Some(transaction) => select.one(transaction.inner()).await?,
-                None => select.one(&self.conn).await?,
+                Some(transaction) => match select.one(transaction.inner()).await? {
+                    Some(model) => Some(Self::load_cassettes(model, transaction.inner()).await?),
+                    None => None,
+                },
+                None => match select.one(&self.conn).await? {
+                    Some(model) => Some(Self::load_cassettes(model, &self.conn).await?),
+                    None => None,
+                },
             }
         };
 
         match found_atm {
-            Some(res) => Ok(res.into()),
+            Some(res) => Ok(res),
             None => Err(InfraError::RecordNotFoundError(format!(
                 "Not found id: {}",
                 Into::<String>::into(id)
@@ -96,4 +145,25 @@ impl Repository for DbAtmRepository {
     }
 }
 
-impl AtmRepository for DbAtmRepository {}
+#[async_trait::async_trait]
+impl AtmRepository for DbAtmRepository {
+    async fn find_by_id_for_update<'t>(
+        &self,
+        id: AtmId,
+        transaction: &'t Self::Transaction,
+    ) -> Result<Self::Aggregate, Self::Error> {
+        // SELECT ... FOR UPDATE
+        let found_atm = atm::orm::Entity::find_by_id(id)
+            .lock_exclusive()
+            .one(transaction.inner())
+            .await?;
+
+        match found_atm {
+            Some(res) => Ok(Self::load_cassettes(res, transaction.inner()).await?),
+            None => Err(InfraError::RecordNotFoundError(format!(
+                "Not found id: {}",
+                Into::<String>::into(id)
+            ))),
+        }
+    }
+}
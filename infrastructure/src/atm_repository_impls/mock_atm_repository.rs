@@ -43,5 +43,12 @@ mock! {
         ) -> Result<(), <Self as Repository>::Error>;
     }
 
-    impl AtmRepository for AtmRepository {}
+    #[async_trait]
+    impl AtmRepository for AtmRepository {
+        async fn find_by_id_for_update<'t>(
+            &self,
+            id: <<Self as Repository>::Aggregate as Aggregate>::IntoId,
+            transaction: &'t <Self as Repository>::Transaction,
+        ) -> Result<<Self as Repository>::Aggregate, <Self as Repository>::Error>;
+    }
 }
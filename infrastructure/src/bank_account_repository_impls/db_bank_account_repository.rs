@@ -1,9 +1,12 @@
 use crate::{transactions::DbTransaction, InfraError};
-use domain::aggregates::bank_account::{self, BankAccount, BankAccountId};
+use domain::aggregates::bank_account::{self, owner_orm, AccountOwner, BankAccount, BankAccountId};
 use domain::repositories::{BankAccountRepository, Repository};
 
 use derive_new::new;
-use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, IntoActiveModel};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    IntoActiveModel, ModelTrait, QueryFilter, QuerySelect,
+};
 
 /// データベースを用いたBankAccountRepository
 #[derive(Clone, Debug, new)]
@@ -11,6 +14,40 @@ pub struct DbBankAccountRepository {
     conn: DatabaseConnection,
 }
 
+impl DbBankAccountRepository {
+    /// 共同名義人を子テーブルから取得し，アグリゲイトに変換する
+    async fn load_owners<C: ConnectionTrait>(
+        model: bank_account::orm::Model,
+        conn: &C,
+    ) -> Result<BankAccount, InfraError> {
+        let owners = model.find_related(owner_orm::Entity).all(conn).await?;
+        Ok(model.into_aggregate(owners))
+    }
+    /// 子テーブルの共同名義人を置き換える
+    async fn replace_owners<C: ConnectionTrait>(
+        account_id: BankAccountId,
+        owners: Vec<AccountOwner>,
+        conn: &C,
+    ) -> Result<(), InfraError> {
+        owner_orm::Entity::delete_many()
+            .filter(owner_orm::Column::AccountId.eq(account_id))
+            .exec(conn)
+            .await?;
+
+        if !owners.is_empty() {
+            owner_orm::Entity::insert_many(
+                owners.into_iter().map(|owner| {
+                    owner_orm::Model::from_owner(account_id, owner).into_active_model()
+                }),
+            )
+            .exec(conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
 #[async_trait::async_trait]
 impl Repository for DbBankAccountRepository {
     type Error = InfraError;
@@ -22,14 +59,18 @@ impl Repository for DbBankAccountRepository {
         bank_account: BankAccount,
         transaction: Option<&'t Self::Transaction>,
     ) -> Result<(), Self::Error> {
+        let account_id = bank_account.id();
+        let owners = bank_account.joint_owners().to_vec();
         let active_model = Into::<bank_account::orm::Model>::into(bank_account).into_active_model();
 
         match transaction {
             Some(transaction) => {
                 active_model.insert(transaction.inner()).await?;
+                Self::replace_owners(account_id, owners, transaction.inner()).await?;
             }
             None => {
                 active_model.insert(&self.conn).await?;
+                Self::replace_owners(account_id, owners, &self.conn).await?;
             }
         }
 
@@ -40,6 +81,8 @@ impl Repository for DbBankAccountRepository {
         bank_account: BankAccount,
         transaction: Option<&'t Self::Transaction>,
     ) -> Result<(), Self::Error> {
+        let account_id = bank_account.id();
+        let owners = bank_account.joint_owners().to_vec();
         let active_model = Into::<bank_account::orm::Model>::into(bank_account)
             .into_active_model() // 全ての値を更新
             .reset_all();
@@ -47,9 +90,11 @@ impl Repository for DbBankAccountRepository {
         match transaction {
             Some(transaction) => {
                 active_model.update(transaction.inner()).await?;
+                Self::replace_owners(account_id, owners, transaction.inner()).await?;
             }
             None => {
                 active_model.update(&self.conn).await?;
+                Self::replace_owners(account_id, owners, &self.conn).await?;
             }
         }
 
@@ -64,13 +109,19 @@ impl Repository for DbBankAccountRepository {
             let select = bank_account::orm::Entity::find_by_id(id);
 
             match transaction {
-                Some(transaction) => select.one(transaction.inner()).await?,
-                None => select.one(&self.conn).await?,
+                Some(transaction) => match select.one(transaction.inner()).await? {
+                    Some(model) => Some(Self::load_owners(model, transaction.inner()).await?),
+                    None => None,
+                },
+                None => match select.one(&self.conn).await? {
+                    Some(model) => Some(Self::load_owners(model, &self.conn).await?),
+                    None => None,
+                },
             }
         };
 
         match found_bank_account {
-            Some(res) => Ok(res.into()),
+            Some(res) => Ok(res),
             None => Err(InfraError::RecordNotFoundError(format!(
                 "Not found id: {}",
                 Into::<String>::into(id)
@@ -97,7 +148,44 @@ impl Repository for DbBankAccountRepository {
     }
 }
 
-impl BankAccountRepository for DbBankAccountRepository {}
+#[async_trait::async_trait]
+impl BankAccountRepository for DbBankAccountRepository {
+    async fn find_by_id_for_update<'t>(
+        &self,
+        id: BankAccountId,
+        transaction: &'t Self::Transaction,
+    ) -> Result<Self::Aggregate, Self::Error> {
+        // SELECT ... FOR UPDATE
+        let found_bank_account = bank_account::orm::Entity::find_by_id(id)
+            .lock_exclusive()
+            .one(transaction.inner())
+            .await?;
+
+        match found_bank_account {
+            Some(res) => Ok(Self::load_owners(res, transaction.inner()).await?),
+            None => Err(InfraError::RecordNotFoundError(format!(
+                "Not found id: {}",
+                Into::<String>::into(id)
+            ))),
+        }
+    }
+    async fn find_all_ids<'t>(
+        &self,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<Vec<BankAccountId>, Self::Error> {
+        let select = bank_account::orm::Entity::find()
+            .select_only()
+            .column(bank_account::orm::Column::Id)
+            .into_tuple::<BankAccountId>();
+
+        let ids = match transaction {
+            Some(transaction) => select.all(transaction.inner()).await?,
+            None => select.all(&self.conn).await?,
+        };
+
+        Ok(ids)
+    }
+}
 
 // -------------------------------------------------------------------------------------------------
 // test
@@ -110,6 +198,7 @@ mod test {
     use domain::{
         aggregates::{bank_account, BankAccount},
         repositories::Transaction,
+        Currency, Decimal, Money,
     };
 
     use rand::seq::SliceRandom;
@@ -167,7 +256,9 @@ mod test {
 
         for bank_account in bank_accounts.iter_mut().take(20) {
             bank_account
-                .deposit_money(rng.gen_range(0.0..100_000.0))
+                .deposit_money(
+                    Money::new(Decimal::from(rng.gen_range(0..100_000)), Currency::JPY).unwrap(),
+                )
                 .unwrap();
             repo.edit(bank_account.clone(), Some(&transaction))
                 .await
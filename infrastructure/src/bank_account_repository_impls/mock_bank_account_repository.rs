@@ -2,6 +2,7 @@ use crate::transactions::MockTransaction;
 use crate::InfraError;
 use async_trait::async_trait;
 use ddd_cqrs_core::Aggregate;
+use domain::aggregates::bank_account::BankAccountId;
 use domain::aggregates::BankAccount;
 use domain::repositories::{BankAccountRepository, Repository};
 
@@ -43,5 +44,17 @@ mock! {
         ) -> Result<(), <Self as Repository>::Error>;
     }
 
-    impl BankAccountRepository for BankAccountRepository {}
+    #[async_trait]
+    impl BankAccountRepository for BankAccountRepository {
+        async fn find_by_id_for_update<'t>(
+            &self,
+            id: <<Self as Repository>::Aggregate as Aggregate>::IntoId,
+            transaction: &'t <Self as Repository>::Transaction,
+        ) -> Result<<Self as Repository>::Aggregate, <Self as Repository>::Error>;
+
+        async fn find_all_ids<'t>(
+            &self,
+            transaction: Option<&'t <Self as Repository>::Transaction>,
+        ) -> Result<Vec<BankAccountId>, <Self as Repository>::Error>;
+    }
 }
@@ -0,0 +1,52 @@
+mod circuit_breaker;
+mod http_check_clearing_service;
+
+#[cfg(feature = "mock")]
+mod mock_check_clearing_service;
+
+pub use circuit_breaker::{BreakerPermit, CircuitBreaker};
+pub use http_check_clearing_service::{HttpCheckClearingService, HttpClientPolicy};
+
+#[cfg(feature = "mock")]
+pub use mock_check_clearing_service::MockCheckClearingService;
+
+use crate::InfraError;
+use domain::aggregates::bank_account::{BankAccountId, CheckNumber};
+use domain::Money;
+
+use derive_new::new;
+use serde::{Deserialize, Serialize};
+
+/// 小切手の決済を依頼するリクエスト
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, new)]
+pub struct CheckClearingRequest {
+    pub account_id: BankAccountId,
+    pub check_number: CheckNumber,
+    pub amount: Money,
+}
+
+impl CheckClearingRequest {
+    /// 同じ小切手の決済の依頼を識別するキー．リトライや再実行で二重に決済されないように利用する
+    pub fn idempotency_key(&self) -> String {
+        format!("{}-{}", self.account_id.to_uuid(), self.check_number)
+    }
+}
+
+/// 小切手の決済の結果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status")]
+pub enum CheckClearingResult {
+    /// 決済された
+    Cleared,
+    /// 不渡りとなった
+    Bounced { reason: String },
+}
+
+/// 外部の小切手決済サービス．通信に失敗した場合はリトライできるようにエラーを返す
+#[async_trait::async_trait]
+pub trait CheckClearingService: Send + Sync {
+    async fn clear_check(
+        &self,
+        request: &CheckClearingRequest,
+    ) -> Result<CheckClearingResult, InfraError>;
+}
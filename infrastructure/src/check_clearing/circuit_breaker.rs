@@ -0,0 +1,120 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 連続した失敗が閾値に達すると一定時間呼び出しを遮断するサーキットブレーカー．
+/// 遮断後にクールダウンが経過すると，1つの試行のみ許可する(半開)
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    /// 遮断するまでの連続した失敗の回数
+    failure_threshold: u32,
+    /// 遮断してから試行を許可するまでの時間
+    cooldown: Duration,
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    /// 遮断した時刻．Noneの場合は閉じている
+    opened_at: Option<Instant>,
+    /// 半開の状態で試行中かどうか
+    trial_in_flight: bool,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold,
+            cooldown,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+    /// 呼び出しを許可するかどうか．許可した場合は結果をBreakerPermitで記録する
+    pub fn try_acquire(&self) -> Option<BreakerPermit<'_>> {
+        let mut state = self.state.lock().unwrap();
+        match state.opened_at {
+            None => Some(BreakerPermit {
+                breaker: self,
+                trial: false,
+            }),
+            Some(opened_at) if opened_at.elapsed() >= self.cooldown && !state.trial_in_flight => {
+                state.trial_in_flight = true;
+                Some(BreakerPermit {
+                    breaker: self,
+                    trial: true,
+                })
+            }
+            Some(_) => None,
+        }
+    }
+    fn record_success(&self) {
+        *self.state.lock().unwrap() = BreakerState::default();
+    }
+    fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures += 1;
+        state.trial_in_flight = false;
+        // 半開での試行に失敗した場合は再び遮断する
+        if state.opened_at.is_some() || state.consecutive_failures >= self.failure_threshold {
+            state.opened_at = Some(Instant::now());
+        }
+    }
+    /// 遮断中かどうか
+    pub fn is_open(&self) -> bool {
+        self.state.lock().unwrap().opened_at.is_some()
+    }
+}
+
+/// CircuitBreakerが許可した一回の呼び出し．
+/// 結果を記録せずに破棄された場合(呼び出し側のFutureのキャンセルなど)は半開の試行を取り消し，次の呼び出しで再び試行できるようにする
+#[must_use]
+#[derive(Debug)]
+pub struct BreakerPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    /// 半開の状態での試行かどうか
+    trial: bool,
+}
+
+impl BreakerPermit<'_> {
+    pub fn record_success(mut self) {
+        self.trial = false;
+        self.breaker.record_success();
+    }
+    pub fn record_failure(mut self) {
+        self.trial = false;
+        self.breaker.record_failure();
+    }
+}
+
+impl Drop for BreakerPermit<'_> {
+    fn drop(&mut self) {
+        if self.trial {
+            self.breaker.state.lock().unwrap().trial_in_flight = false;
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// test
+
+#[cfg(test)]
+mod test {
+    use super::CircuitBreaker;
+    use std::time::Duration;
+
+    #[test]
+    fn dropped_trial_is_released() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        breaker.try_acquire().unwrap().record_failure();
+        assert!(breaker.is_open());
+
+        // 半開の試行中は他の呼び出しを許可しない
+        let trial = breaker.try_acquire().unwrap();
+        assert!(breaker.try_acquire().is_none());
+
+        // 結果を記録せずに破棄された試行は取り消され，再び試行できる
+        drop(trial);
+        breaker.try_acquire().unwrap().record_success();
+        assert!(!breaker.is_open());
+    }
+}
@@ -0,0 +1,261 @@
+use super::{CheckClearingRequest, CheckClearingResult, CheckClearingService, CircuitBreaker};
+use crate::InfraError;
+
+use config::CONFIG;
+use reqwest::StatusCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// HTTPクライアントのタイムアウト・リトライ・サーキットブレーカーの設定
+#[derive(Debug, Clone)]
+pub struct HttpClientPolicy {
+    /// 1回のリクエストのタイムアウト
+    pub timeout: Duration,
+    /// 最初のリクエストに加えてリトライする回数
+    pub max_retries: u32,
+    /// 最初のリトライまでの待ち時間．リトライごとに2倍になる
+    pub retry_backoff: Duration,
+    /// サーキットブレーカーが遮断するまでの連続した失敗の回数
+    pub failure_threshold: u32,
+    /// サーキットブレーカーが遮断してから試行を許可するまでの時間
+    pub cooldown: Duration,
+}
+
+impl Default for HttpClientPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_millis(CONFIG.CHECK_CLEARING_TIMEOUT_MILLIS),
+            max_retries: CONFIG.CHECK_CLEARING_MAX_RETRIES,
+            retry_backoff: Duration::from_millis(CONFIG.CHECK_CLEARING_RETRY_BACKOFF_MILLIS),
+            failure_threshold: CONFIG.CHECK_CLEARING_FAILURE_THRESHOLD,
+            cooldown: Duration::from_secs(CONFIG.CHECK_CLEARING_COOLDOWN_SECS),
+        }
+    }
+}
+
+/// 1回のリクエストの失敗
+enum AttemptError {
+    /// 通信の失敗や5xxなど，リトライで回復しうるもの
+    Retryable(String),
+    /// 4xxや不正なレスポンスなど，リトライしても回復しないもの
+    Fatal(String),
+}
+
+/// HTTPで外部の小切手決済サービスを利用するCheckClearingService
+#[derive(Clone, Debug)]
+pub struct HttpCheckClearingService {
+    client: reqwest::Client,
+    /// 決済を依頼するエンドポイントのURL
+    endpoint: String,
+    max_retries: u32,
+    retry_backoff: Duration,
+    /// クローンしたサービス間で共有する
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl HttpCheckClearingService {
+    pub fn new(base_url: &str, policy: HttpClientPolicy) -> Result<Self, InfraError> {
+        let client = reqwest::Client::builder()
+            .timeout(policy.timeout)
+            .build()
+            .map_err(|e| InfraError::ExternalServiceError(e.to_string()))?;
+
+        Ok(Self {
+            client,
+            endpoint: format!("{}/checks/clear", base_url.trim_end_matches('/')),
+            max_retries: policy.max_retries,
+            retry_backoff: policy.retry_backoff,
+            breaker: Arc::new(CircuitBreaker::new(
+                policy.failure_threshold,
+                policy.cooldown,
+            )),
+        })
+    }
+    async fn send_once(
+        &self,
+        request: &CheckClearingRequest,
+    ) -> Result<CheckClearingResult, AttemptError> {
+        let response = self
+            .client
+            .post(&self.endpoint)
+            // POSTはべき等でないため，リトライで同じ小切手を二重に決済しないようにキーを付ける
+            .header("Idempotency-Key", request.idempotency_key())
+            .json(request)
+            .send()
+            .await
+            .map_err(|e| AttemptError::Retryable(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(AttemptError::Retryable(format!(
+                "Check clearing service responded with {status}"
+            )));
+        }
+        if !status.is_success() {
+            return Err(AttemptError::Fatal(format!(
+                "Check clearing service responded with {status}"
+            )));
+        }
+
+        response
+            .json::<CheckClearingResult>()
+            .await
+            .map_err(|e| AttemptError::Fatal(e.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl CheckClearingService for HttpCheckClearingService {
+    async fn clear_check(
+        &self,
+        request: &CheckClearingRequest,
+    ) -> Result<CheckClearingResult, InfraError> {
+        // 呼び出し側でキャンセルされた場合は，BreakerPermitの破棄により試行が取り消される
+        let Some(permit) = self.breaker.try_acquire() else {
+            return Err(InfraError::ExternalServiceError(
+                "Check clearing service circuit is open".to_string(),
+            ));
+        };
+
+        let mut backoff = self.retry_backoff;
+        let mut retries = 0;
+        loop {
+            match self.send_once(request).await {
+                Ok(result) => {
+                    permit.record_success();
+                    return Ok(result);
+                }
+                Err(AttemptError::Retryable(_)) if retries < self.max_retries => {
+                    retries += 1;
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                Err(AttemptError::Retryable(e)) => {
+                    permit.record_failure();
+                    return Err(InfraError::ExternalServiceError(e));
+                }
+                // サービス自体は応答しているため，遮断の対象としない
+                Err(AttemptError::Fatal(e)) => {
+                    permit.record_success();
+                    return Err(InfraError::ExternalServiceError(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{HttpCheckClearingService, HttpClientPolicy};
+    use crate::check_clearing::{CheckClearingRequest, CheckClearingResult, CheckClearingService};
+
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::{extract::State, routing::post, Json, Router};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// 最初のfailures回は503を返し，その後は金額が1000以上の小切手を不渡りとするスタブサーバー．
+    /// べき等キーの無いリクエストは400とする
+    async fn stub_server(failures: u32) -> (String, Arc<AtomicU32>) {
+        let calls = Arc::new(AtomicU32::new(0));
+
+        async fn clear(
+            State((failures, calls)): State<(u32, Arc<AtomicU32>)>,
+            headers: HeaderMap,
+            Json(request): Json<CheckClearingRequest>,
+        ) -> Result<Json<CheckClearingResult>, StatusCode> {
+            let key = headers
+                .get("Idempotency-Key")
+                .ok_or(StatusCode::BAD_REQUEST)?;
+            if key.to_str().ok() != Some(request.idempotency_key().as_str()) {
+                return Err(StatusCode::BAD_REQUEST);
+            }
+            if calls.fetch_add(1, Ordering::SeqCst) < failures {
+                return Err(StatusCode::SERVICE_UNAVAILABLE);
+            }
+            if request.amount.amount() >= 1000.into() {
+                Ok(Json(CheckClearingResult::Bounced {
+                    reason: "Insufficient funds".to_string(),
+                }))
+            } else {
+                Ok(Json(CheckClearingResult::Cleared))
+            }
+        }
+
+        let app = Router::new()
+            .route("/checks/clear", post(clear))
+            .with_state((failures, calls.clone()));
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(
+            axum::Server::from_tcp(listener)
+                .unwrap()
+                .serve(app.into_make_service()),
+        );
+
+        (base_url, calls)
+    }
+
+    fn policy() -> HttpClientPolicy {
+        HttpClientPolicy {
+            timeout: Duration::from_secs(5),
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(1),
+            failure_threshold: 2,
+            cooldown: Duration::from_secs(60),
+        }
+    }
+
+    fn request(amount: &str) -> CheckClearingRequest {
+        CheckClearingRequest::new(
+            Default::default(),
+            "0001".parse().unwrap(),
+            format!("{amount} JPY").parse().unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn clear_and_bounce() {
+        let (base_url, _) = stub_server(0).await;
+        let service = HttpCheckClearingService::new(&base_url, policy()).unwrap();
+
+        assert_eq!(
+            service.clear_check(&request("100")).await.unwrap(),
+            CheckClearingResult::Cleared
+        );
+        assert!(matches!(
+            service.clear_check(&request("1000")).await.unwrap(),
+            CheckClearingResult::Bounced { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn retry_server_error() {
+        let (base_url, calls) = stub_server(2).await;
+        let service = HttpCheckClearingService::new(&base_url, policy()).unwrap();
+
+        assert_eq!(
+            service.clear_check(&request("100")).await.unwrap(),
+            CheckClearingResult::Cleared
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn circuit_opens_after_failures() {
+        let (base_url, calls) = stub_server(u32::MAX).await;
+        let service = HttpCheckClearingService::new(&base_url, policy()).unwrap();
+
+        for _ in 0..2 {
+            assert!(service.clear_check(&request("100")).await.is_err());
+        }
+        assert!(service.breaker.is_open());
+
+        // 遮断中はリクエストを送らない
+        let calls_before = calls.load(Ordering::SeqCst);
+        assert!(service.clear_check(&request("100")).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), calls_before);
+    }
+}
@@ -0,0 +1,19 @@
+use super::{CheckClearingRequest, CheckClearingResult, CheckClearingService};
+use crate::InfraError;
+use async_trait::async_trait;
+
+use mockall::mock;
+
+mock! {
+    /// CheckClearingServiceのモック
+    #[derive(Clone, Debug)]
+    pub CheckClearingService{}
+
+    #[async_trait]
+    impl CheckClearingService for CheckClearingService {
+        async fn clear_check(
+            &self,
+            request: &CheckClearingRequest,
+        ) -> Result<CheckClearingResult, InfraError>;
+    }
+}
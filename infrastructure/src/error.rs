@@ -13,18 +13,45 @@ pub enum InfraError {
     #[error("InfraError::RecordNotFoundError: {0}")]
     RecordNotFoundError(String),
 
+    /// 一意制約違反・更新対象の消失など，並行処理や重複による競合のエラー
+    #[error("InfraError::ConflictError: {0}")]
+    ConflictError(String),
+
     /// その他のormに関するエラー
     #[error("InfraError::OtherDbError: {0}")]
     OtherDbError(String),
+
+    /// メールの送信に関するエラー
+    #[error("InfraError::MailError: {0}")]
+    MailError(String),
+
+    /// 外部サービスの呼び出しに関するエラー
+    #[error("InfraError::ExternalServiceError: {0}")]
+    ExternalServiceError(String),
+
+    /// 永続化したサガの状態の変換に関するエラー
+    #[error("InfraError::SagaStateError: {0}")]
+    SagaStateError(String),
+}
+
+impl From<ddd_cqrs_core::SagaRecordError> for InfraError {
+    fn from(value: ddd_cqrs_core::SagaRecordError) -> Self {
+        Self::SagaStateError(value.to_string())
+    }
 }
 
 impl From<sea_orm::DbErr> for InfraError {
     fn from(value: sea_orm::DbErr) -> Self {
-        use sea_orm::DbErr;
+        use sea_orm::{DbErr, SqlErr};
+
+        if let Some(SqlErr::UniqueConstraintViolation(e)) = value.sql_err() {
+            return Self::ConflictError(e);
+        }
 
         match value {
             e @ DbErr::RecordNotFound(_) => Self::RecordNotFoundError(e.to_string()),
-            e @ _ => Self::OtherDbError(e.to_string()),
+            e @ DbErr::RecordNotUpdated => Self::ConflictError(e.to_string()),
+            e => Self::OtherDbError(e.to_string()),
         }
     }
 }
@@ -1,7 +1,12 @@
 pub mod atm_repository_impls;
 pub mod bank_account_repository_impls;
+pub mod check_clearing;
 mod error;
+pub mod mailer;
+pub mod saga_repository_impls;
 pub mod transactions;
+pub mod used_check_number_repository_impls;
+pub mod withdrawal_event_repository_impls;
 
 pub use error::InfraError;
 
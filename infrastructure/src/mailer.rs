@@ -0,0 +1,56 @@
+mod file_mailer;
+mod smtp_mailer;
+
+#[cfg(feature = "mock")]
+mod mock_mailer;
+
+pub use file_mailer::FileMailer;
+pub use smtp_mailer::SmtpMailer;
+
+#[cfg(feature = "mock")]
+pub use mock_mailer::MockMailer;
+
+use crate::InfraError;
+use domain::aggregates::bank_account::EmailAddress;
+
+use chrono::{DateTime, Utc};
+use derive_new::new;
+use std::sync::Arc;
+
+/// 送信するメール．本文はプレーンテキスト
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct Mail {
+    pub to: EmailAddress,
+    pub subject: String,
+    pub body: String,
+}
+
+impl Mail {
+    /// RFC 5322の形式のメッセージに変換する．改行はCRLFに統一する
+    pub fn to_message(&self, from: &EmailAddress, date: DateTime<Utc>) -> String {
+        // ヘッダインジェクションを防ぐため，件名の改行は空白に置き換える
+        let subject = self.subject.replace(['\r', '\n'], " ");
+        let body = self.body.lines().collect::<Vec<_>>().join("\r\n");
+
+        format!(
+            "From: <{from}>\r\nTo: <{to}>\r\nSubject: {subject}\r\nDate: {date}\r\nMIME-Version: 1.0\r\nContent-Type: text/plain; charset=UTF-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n{body}\r\n",
+            from = from.as_str(),
+            to = self.to.as_str(),
+            date = date.to_rfc2822(),
+        )
+    }
+}
+
+/// メールを送信するサービス．送信に失敗した場合はリトライできるようにエラーを返す
+#[async_trait::async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, mail: Mail) -> Result<(), InfraError>;
+}
+
+/// 実行時に実装を選択できるように，Arc<dyn Mailer>などもMailerとして扱う
+#[async_trait::async_trait]
+impl<M: Mailer + ?Sized> Mailer for Arc<M> {
+    async fn send(&self, mail: Mail) -> Result<(), InfraError> {
+        (**self).send(mail).await
+    }
+}
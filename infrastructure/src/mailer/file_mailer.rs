@@ -0,0 +1,80 @@
+use super::{Mail, Mailer};
+use crate::InfraError;
+use domain::aggregates::bank_account::EmailAddress;
+
+use chrono::Utc;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// メールをMaildir形式でファイルに書き出すMailer．開発用
+#[derive(Clone, Debug)]
+pub struct FileMailer {
+    /// Maildirのディレクトリ．tmp/とnew/が作成される
+    dir: PathBuf,
+    /// 送信元のメールアドレス
+    from: EmailAddress,
+}
+
+impl FileMailer {
+    pub fn new(dir: impl Into<PathBuf>, from: EmailAddress) -> Self {
+        Self {
+            dir: dir.into(),
+            from,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, mail: Mail) -> Result<(), InfraError> {
+        let now = Utc::now();
+        let file_name = format!("{}.{}.eml", now.timestamp_millis(), Uuid::new_v4().simple());
+        let tmp_path = self.dir.join("tmp").join(&file_name);
+        let new_path = self.dir.join("new").join(&file_name);
+
+        // 書き込み途中のファイルが読まれないように，tmp/に書き込んでからnew/に移動する
+        tokio::fs::create_dir_all(self.dir.join("tmp"))
+            .await
+            .map_err(io_error)?;
+        tokio::fs::create_dir_all(self.dir.join("new"))
+            .await
+            .map_err(io_error)?;
+        tokio::fs::write(&tmp_path, mail.to_message(&self.from, now))
+            .await
+            .map_err(io_error)?;
+        tokio::fs::rename(&tmp_path, &new_path)
+            .await
+            .map_err(io_error)?;
+
+        Ok(())
+    }
+}
+
+fn io_error(e: std::io::Error) -> InfraError {
+    InfraError::MailError(e.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FileMailer, Mail, Mailer};
+
+    #[tokio::test]
+    async fn write_to_maildir() {
+        let dir = std::env::temp_dir().join(format!("file_mailer_{}", uuid::Uuid::new_v4()));
+        let mailer = FileMailer::new(&dir, "noreply@example.com".to_string().try_into().unwrap());
+
+        let mail = Mail::new(
+            "taro@example.com".to_string().try_into().unwrap(),
+            "Hello".to_string(),
+            "body".to_string(),
+        );
+        mailer.send(mail).await.unwrap();
+
+        let mut entries = std::fs::read_dir(dir.join("new")).unwrap();
+        let message = std::fs::read_to_string(entries.next().unwrap().unwrap().path()).unwrap();
+        assert!(message.contains("To: <taro@example.com>\r\n"));
+        assert!(message.ends_with("\r\n\r\nbody\r\n"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,16 @@
+use super::{Mail, Mailer};
+use crate::InfraError;
+use async_trait::async_trait;
+
+use mockall::mock;
+
+mock! {
+    /// Mailerのモック
+    #[derive(Clone, Debug)]
+    pub Mailer{}
+
+    #[async_trait]
+    impl Mailer for Mailer {
+        async fn send(&self, mail: Mail) -> Result<(), InfraError>;
+    }
+}
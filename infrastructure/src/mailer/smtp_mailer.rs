@@ -0,0 +1,234 @@
+use super::{Mail, Mailer};
+use crate::InfraError;
+use domain::aggregates::bank_account::EmailAddress;
+
+use chrono::Utc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::TcpStream;
+
+/// SMTPでメールを送信するMailer．TLSと認証は扱わないため，ローカルのリレーやSMTPシンクに送信する
+#[derive(Clone, Debug)]
+pub struct SmtpMailer {
+    /// SMTPサーバーのアドレス(host:port)
+    addr: String,
+    /// 送信元のメールアドレス
+    from: EmailAddress,
+    /// EHLOで名乗るホスト名
+    hello_name: String,
+    /// 1通の送信全体のタイムアウト
+    timeout: Duration,
+}
+
+impl SmtpMailer {
+    pub fn new(addr: impl Into<String>, from: EmailAddress, timeout: Duration) -> Self {
+        Self {
+            addr: addr.into(),
+            from,
+            hello_name: "localhost".to_string(),
+            timeout,
+        }
+    }
+    /// SMTPの対話を行う
+    async fn deliver(&self, mail: &Mail) -> Result<(), InfraError> {
+        let stream = TcpStream::connect(&self.addr).await.map_err(io_error)?;
+        let mut conn = SmtpConnection::new(stream);
+
+        conn.read_reply(220).await?;
+        conn.command(&format!("EHLO {}", self.hello_name), 250)
+            .await?;
+        conn.command(&format!("MAIL FROM:<{}>", self.from.as_str()), 250)
+            .await?;
+        conn.command(&format!("RCPT TO:<{}>", mail.to.as_str()), 250)
+            .await?;
+        conn.command("DATA", 354).await?;
+        conn.write(&dot_stuff(&mail.to_message(&self.from, Utc::now())))
+            .await?;
+        conn.write(".\r\n").await?;
+        conn.read_reply(250).await?;
+        conn.command("QUIT", 221).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, mail: Mail) -> Result<(), InfraError> {
+        match tokio::time::timeout(self.timeout, self.deliver(&mail)).await {
+            Ok(res) => res,
+            Err(_) => Err(InfraError::MailError(format!(
+                "SMTP delivery to {} timed out after {:?}",
+                self.addr, self.timeout
+            ))),
+        }
+    }
+}
+
+/// SMTPの接続
+struct SmtpConnection {
+    reader: BufReader<OwnedReadHalf>,
+    writer: OwnedWriteHalf,
+}
+
+impl SmtpConnection {
+    fn new(stream: TcpStream) -> Self {
+        let (reader, writer) = stream.into_split();
+        Self {
+            reader: BufReader::new(reader),
+            writer,
+        }
+    }
+    async fn write(&mut self, data: &str) -> Result<(), InfraError> {
+        self.writer
+            .write_all(data.as_bytes())
+            .await
+            .map_err(io_error)
+    }
+    /// 応答を読み込み，応答コードの種類(上1桁)が期待したものか確認する．複数行の応答にも対応する
+    async fn read_reply(&mut self, expected: u16) -> Result<(), InfraError> {
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line).await.map_err(io_error)? == 0 {
+                return Err(InfraError::MailError(
+                    "SMTP connection closed unexpectedly".to_string(),
+                ));
+            }
+            let code = line
+                .get(..3)
+                .and_then(|code| code.parse::<u16>().ok())
+                .ok_or_else(|| InfraError::MailError(format!("Invalid SMTP reply: {line}")))?;
+
+            if code / 100 != expected / 100 {
+                return Err(InfraError::MailError(format!(
+                    "Unexpected SMTP reply (expected {expected}): {}",
+                    line.trim_end()
+                )));
+            }
+            // "250-"は続きがあることを表す
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return Ok(());
+            }
+        }
+    }
+    async fn command(&mut self, command: &str, expected: u16) -> Result<(), InfraError> {
+        self.write(&format!("{command}\r\n")).await?;
+        self.read_reply(expected).await
+    }
+}
+
+/// "."で始まる行の先頭に"."を追加する(RFC 5321 4.5.2)
+fn dot_stuff(message: &str) -> String {
+    message
+        .split("\r\n")
+        .map(|line| {
+            if line.starts_with('.') {
+                format!(".{line}")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\r\n")
+}
+
+fn io_error(e: std::io::Error) -> InfraError {
+    InfraError::MailError(e.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Mail, Mailer, SmtpMailer};
+
+    use std::time::Duration;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    /// 1通だけ受信し，DATAの内容を返すSMTPシンク
+    async fn smtp_sink(listener: TcpListener) -> String {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+
+        writer.write_all(b"220 sink ready\r\n").await.unwrap();
+
+        let mut data = String::new();
+        let mut in_data = false;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).await.unwrap() == 0 {
+                break;
+            }
+            if in_data {
+                if line == ".\r\n" {
+                    in_data = false;
+                    writer.write_all(b"250 queued\r\n").await.unwrap();
+                } else {
+                    data.push_str(&line);
+                }
+                continue;
+            }
+            let reply: &[u8] = match line.get(..4).unwrap_or_default() {
+                "EHLO" => b"250-sink\r\n250 8BITMIME\r\n",
+                "DATA" => {
+                    in_data = true;
+                    b"354 end with .\r\n"
+                }
+                "QUIT" => {
+                    writer.write_all(b"221 bye\r\n").await.unwrap();
+                    break;
+                }
+                _ => b"250 ok\r\n",
+            };
+            writer.write_all(reply).await.unwrap();
+        }
+        data
+    }
+
+    #[tokio::test]
+    async fn send_to_smtp_sink() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let sink = tokio::spawn(smtp_sink(listener));
+
+        let mailer = SmtpMailer::new(
+            addr,
+            "noreply@example.com".to_string().try_into().unwrap(),
+            Duration::from_secs(5),
+        );
+        let mail = Mail::new(
+            "taro@example.com".to_string().try_into().unwrap(),
+            "Hello".to_string(),
+            "first line\n.dotted line".to_string(),
+        );
+        mailer.send(mail).await.unwrap();
+
+        let data = sink.await.unwrap();
+        assert!(data.contains("To: <taro@example.com>\r\n"));
+        assert!(data.contains("Subject: Hello\r\n"));
+        assert!(data.contains("first line\r\n..dotted line\r\n"));
+    }
+
+    #[tokio::test]
+    async fn smtp_error_reply() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            stream.write_all(b"554 no service\r\n").await.unwrap();
+        });
+
+        let mailer = SmtpMailer::new(
+            addr,
+            "noreply@example.com".to_string().try_into().unwrap(),
+            Duration::from_secs(5),
+        );
+        let mail = Mail::new(
+            "taro@example.com".to_string().try_into().unwrap(),
+            "Hello".to_string(),
+            "body".to_string(),
+        );
+        assert!(mailer.send(mail).await.is_err());
+    }
+}
@@ -0,0 +1,9 @@
+mod db_saga_repository;
+
+#[cfg(feature = "mock")]
+mod mock_saga_repository;
+
+pub use db_saga_repository::DbSagaRepository;
+
+#[cfg(feature = "mock")]
+pub use mock_saga_repository::MockSagaRepository;
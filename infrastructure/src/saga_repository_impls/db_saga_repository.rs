@@ -0,0 +1,117 @@
+use crate::{transactions::DbTransaction, InfraError};
+use ddd_cqrs_core::{SagaRecord, SagaStatus};
+use domain::repositories::SagaRepository;
+use domain::sagas::orm as saga_orm;
+
+use chrono::{DateTime, Utc};
+use derive_new::new;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+};
+use uuid::Uuid;
+
+/// データベースを用いたSagaRepository
+#[derive(Clone, Debug, new)]
+pub struct DbSagaRepository {
+    conn: DatabaseConnection,
+}
+
+#[async_trait::async_trait]
+impl SagaRepository for DbSagaRepository {
+    type Error = InfraError;
+    type Transaction = DbTransaction;
+
+    async fn save<'t>(
+        &self,
+        record: SagaRecord,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<(), Self::Error> {
+        let insert =
+            saga_orm::Entity::insert(Into::<saga_orm::Model>::into(record).into_active_model());
+
+        match transaction {
+            Some(transaction) => {
+                insert.exec(transaction.inner()).await?;
+            }
+            None => {
+                insert.exec(&self.conn).await?;
+            }
+        }
+
+        Ok(())
+    }
+    async fn update<'t>(
+        &self,
+        record: SagaRecord,
+        expected_version: i64,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<bool, Self::Error> {
+        let model = Into::<saga_orm::Model>::into(record);
+
+        // 版を条件とした更新により，同時に同じサガを進めた場合は一方のみが更新できる
+        let update = saga_orm::Entity::update_many()
+            .col_expr(saga_orm::Column::Status, Expr::value(model.status))
+            .col_expr(saga_orm::Column::Version, Expr::value(model.version))
+            .col_expr(saga_orm::Column::State, Expr::value(model.state))
+            .col_expr(saga_orm::Column::Commands, Expr::value(model.commands))
+            .col_expr(saga_orm::Column::Deadline, Expr::value(model.deadline))
+            .col_expr(
+                saga_orm::Column::NextAttemptAt,
+                Expr::value(model.next_attempt_at),
+            )
+            .filter(saga_orm::Column::Id.eq(model.id))
+            .filter(saga_orm::Column::Version.eq(expected_version));
+
+        let res = match transaction {
+            Some(transaction) => update.exec(transaction.inner()).await?,
+            None => update.exec(&self.conn).await?,
+        };
+
+        Ok(res.rows_affected == 1)
+    }
+    async fn find_by_id<'t>(
+        &self,
+        id: Uuid,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<SagaRecord, Self::Error> {
+        let select = saga_orm::Entity::find_by_id(id);
+
+        let found_saga = match transaction {
+            Some(transaction) => select.one(transaction.inner()).await?,
+            None => select.one(&self.conn).await?,
+        };
+
+        match found_saga {
+            Some(res) => Ok(res.try_into()?),
+            None => Err(InfraError::RecordNotFoundError(format!(
+                "Not found id: {id}"
+            ))),
+        }
+    }
+    async fn find_due<'t>(
+        &self,
+        saga_type: &'static str,
+        now: DateTime<Utc>,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<Vec<SagaRecord>, Self::Error> {
+        let select = saga_orm::Entity::find()
+            .filter(saga_orm::Column::SagaType.eq(saga_type))
+            .filter(
+                saga_orm::Column::Status
+                    .is_in(SagaStatus::ACTIVE.iter().map(SagaStatus::to_string)),
+            )
+            .filter(saga_orm::Column::NextAttemptAt.lte(now))
+            .order_by_asc(saga_orm::Column::NextAttemptAt);
+
+        let models = match transaction {
+            Some(transaction) => select.all(transaction.inner()).await?,
+            None => select.all(&self.conn).await?,
+        };
+
+        Ok(models
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+}
@@ -0,0 +1,47 @@
+use crate::transactions::MockTransaction;
+use crate::InfraError;
+use async_trait::async_trait;
+use ddd_cqrs_core::SagaRecord;
+use domain::repositories::SagaRepository;
+
+use chrono::{DateTime, Utc};
+use mockall::mock;
+use uuid::Uuid;
+
+mock! {
+    /// DbSagaRepositoryのモック
+    #[derive(Clone, Debug)]
+    pub SagaRepository{}
+
+    #[async_trait]
+    impl SagaRepository for SagaRepository {
+        type Error = InfraError;
+        type Transaction = MockTransaction;
+
+        async fn save<'t>(
+            &self,
+            record: SagaRecord,
+            transaction: Option<&'t MockTransaction>,
+        ) -> Result<(), InfraError>;
+
+        async fn update<'t>(
+            &self,
+            record: SagaRecord,
+            expected_version: i64,
+            transaction: Option<&'t MockTransaction>,
+        ) -> Result<bool, InfraError>;
+
+        async fn find_by_id<'t>(
+            &self,
+            id: Uuid,
+            transaction: Option<&'t MockTransaction>,
+        ) -> Result<SagaRecord, InfraError>;
+
+        async fn find_due<'t>(
+            &self,
+            saga_type: &'static str,
+            now: DateTime<Utc>,
+            transaction: Option<&'t MockTransaction>,
+        ) -> Result<Vec<SagaRecord>, InfraError>;
+    }
+}
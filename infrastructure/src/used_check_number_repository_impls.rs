@@ -0,0 +1,9 @@
+mod db_used_check_number_repository;
+
+#[cfg(feature = "mock")]
+mod mock_used_check_number_repository;
+
+pub use db_used_check_number_repository::DbUsedCheckNumberRepository;
+
+#[cfg(feature = "mock")]
+pub use mock_used_check_number_repository::MockUsedCheckNumberRepository;
@@ -0,0 +1,131 @@
+use crate::{transactions::DbTransaction, InfraError};
+use domain::aggregates::bank_account::{
+    used_check_number_orm, BankAccountId, CheckNumber, CheckSettlement,
+};
+use domain::repositories::UsedCheckNumberRepository;
+use domain::{BankAccountError, DomainError};
+
+use chrono::{DateTime, Utc};
+use derive_new::new;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter,
+    QuerySelect, SqlErr,
+};
+use uuid::Uuid;
+
+/// データベースを用いたUsedCheckNumberRepository
+#[derive(Clone, Debug, new)]
+pub struct DbUsedCheckNumberRepository {
+    conn: DatabaseConnection,
+}
+
+#[async_trait::async_trait]
+impl UsedCheckNumberRepository for DbUsedCheckNumberRepository {
+    type Error = InfraError;
+    type Transaction = DbTransaction;
+
+    async fn save<'t>(
+        &self,
+        account_id: BankAccountId,
+        check_number: CheckNumber,
+        used_at: DateTime<Utc>,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<(), Self::Error> {
+        let active_model = used_check_number_orm::Model {
+            id: Uuid::new_v4(),
+            account_id,
+            check_number: check_number.clone(),
+            used_at,
+            settlement: None,
+        }
+        .into_active_model();
+
+        let res = match transaction {
+            Some(transaction) => active_model.insert(transaction.inner()).await,
+            None => active_model.insert(&self.conn).await,
+        };
+
+        match res {
+            Ok(_) => Ok(()),
+            // 同時に呈示された同じ小切手は一意制約で検出する
+            Err(e) if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+                Err(DomainError::from(BankAccountError::DuplicateCheck { check_number }).into())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+    async fn exists<'t>(
+        &self,
+        account_id: BankAccountId,
+        check_number: &CheckNumber,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<bool, Self::Error> {
+        // 一致する行を数えずに，最初の一行のidのみを取得する
+        let select = used_check_number_orm::Entity::find()
+            .select_only()
+            .column(used_check_number_orm::Column::Id)
+            .filter(used_check_number_orm::Column::AccountId.eq(account_id))
+            .filter(used_check_number_orm::Column::CheckNumber.eq(check_number))
+            .limit(1)
+            .into_tuple::<Uuid>();
+
+        let found = match transaction {
+            Some(transaction) => select.one(transaction.inner()).await?,
+            None => select.one(&self.conn).await?,
+        };
+
+        Ok(found.is_some())
+    }
+    async fn find_settlement<'t>(
+        &self,
+        account_id: BankAccountId,
+        check_number: &CheckNumber,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<Option<CheckSettlement>, Self::Error> {
+        let select = used_check_number_orm::Entity::find()
+            .filter(used_check_number_orm::Column::AccountId.eq(account_id))
+            .filter(used_check_number_orm::Column::CheckNumber.eq(check_number));
+
+        let found = match transaction {
+            Some(transaction) => select.one(transaction.inner()).await?,
+            None => select.one(&self.conn).await?,
+        };
+
+        match found {
+            Some(model) => Ok(model.settlement),
+            None => Err(InfraError::RecordNotFoundError(format!(
+                "Not found check number: {check_number}"
+            ))),
+        }
+    }
+    async fn settle<'t>(
+        &self,
+        account_id: BankAccountId,
+        check_number: &CheckNumber,
+        settlement: CheckSettlement,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<(), Self::Error> {
+        // 決済の結果は一度だけ記録する
+        let update = used_check_number_orm::Entity::update_many()
+            .col_expr(
+                used_check_number_orm::Column::Settlement,
+                Expr::value(settlement.as_str()),
+            )
+            .filter(used_check_number_orm::Column::AccountId.eq(account_id))
+            .filter(used_check_number_orm::Column::CheckNumber.eq(check_number))
+            .filter(used_check_number_orm::Column::Settlement.is_null());
+
+        let res = match transaction {
+            Some(transaction) => update.exec(transaction.inner()).await?,
+            None => update.exec(&self.conn).await?,
+        };
+
+        match res.rows_affected {
+            1 => Ok(()),
+            _ => Err(InfraError::ConflictError(format!(
+                "Check {check_number} is not found or already settled"
+            ))),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::transactions::MockTransaction;
+use crate::InfraError;
+use async_trait::async_trait;
+use domain::aggregates::bank_account::{BankAccountId, CheckNumber, CheckSettlement};
+use domain::repositories::UsedCheckNumberRepository;
+
+use chrono::{DateTime, Utc};
+use mockall::mock;
+
+mock! {
+    /// DbUsedCheckNumberRepositoryのモック
+    #[derive(Clone, Debug)]
+    pub UsedCheckNumberRepository{}
+
+    #[async_trait]
+    impl UsedCheckNumberRepository for UsedCheckNumberRepository {
+        type Error = InfraError;
+        type Transaction = MockTransaction;
+
+        async fn save<'t>(
+            &self,
+            account_id: BankAccountId,
+            check_number: CheckNumber,
+            used_at: DateTime<Utc>,
+            transaction: Option<&'t MockTransaction>,
+        ) -> Result<(), InfraError>;
+
+        async fn exists<'t>(
+            &self,
+            account_id: BankAccountId,
+            check_number: &CheckNumber,
+            transaction: Option<&'t MockTransaction>,
+        ) -> Result<bool, InfraError>;
+
+        async fn find_settlement<'t>(
+            &self,
+            account_id: BankAccountId,
+            check_number: &CheckNumber,
+            transaction: Option<&'t MockTransaction>,
+        ) -> Result<Option<CheckSettlement>, InfraError>;
+
+        async fn settle<'t>(
+            &self,
+            account_id: BankAccountId,
+            check_number: &CheckNumber,
+            settlement: CheckSettlement,
+            transaction: Option<&'t MockTransaction>,
+        ) -> Result<(), InfraError>;
+    }
+}
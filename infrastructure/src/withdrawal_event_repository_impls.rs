@@ -0,0 +1,9 @@
+mod db_withdrawal_event_repository;
+
+#[cfg(feature = "mock")]
+mod mock_withdrawal_event_repository;
+
+pub use db_withdrawal_event_repository::DbWithdrawalEventRepository;
+
+#[cfg(feature = "mock")]
+pub use mock_withdrawal_event_repository::MockWithdrawalEventRepository;
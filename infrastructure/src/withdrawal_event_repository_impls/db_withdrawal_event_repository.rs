@@ -0,0 +1,113 @@
+use crate::{transactions::DbTransaction, InfraError};
+use domain::aggregates::atm::AtmId;
+use domain::aggregates::bank_account::BankAccountId;
+use domain::events::bank_account_events::{orm::customer_withdrew_cash, CustomerWithdrewCashEvent};
+use domain::repositories::WithdrawalEventRepository;
+use domain::Decimal;
+
+use chrono::{DateTime, Utc};
+use derive_new::new;
+use sea_orm::sea_query::SimpleExpr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QuerySelect,
+};
+
+/// データベースを用いたWithdrawalEventRepository
+#[derive(Clone, Debug, new)]
+pub struct DbWithdrawalEventRepository {
+    conn: DatabaseConnection,
+}
+
+impl DbWithdrawalEventRepository {
+    /// 条件に合致する引き出し金額の合計
+    async fn sum_amount(
+        &self,
+        condition: SimpleExpr,
+        transaction: Option<&DbTransaction>,
+    ) -> Result<Decimal, InfraError> {
+        let select = customer_withdrew_cash::Entity::find()
+            .select_only()
+            .column_as(customer_withdrew_cash::Column::Amount.sum(), "total")
+            .filter(condition)
+            .into_tuple::<Option<Decimal>>();
+
+        let total = match transaction {
+            Some(transaction) => select.one(transaction.inner()).await?,
+            None => select.one(&self.conn).await?,
+        };
+
+        // 該当する行が無い場合はNULLとなる
+        Ok(total.flatten().unwrap_or_default())
+    }
+}
+
+#[async_trait::async_trait]
+impl WithdrawalEventRepository for DbWithdrawalEventRepository {
+    type Error = InfraError;
+    type Transaction = DbTransaction;
+
+    async fn save<'t>(
+        &self,
+        event: CustomerWithdrewCashEvent,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<(), Self::Error> {
+        let active_model = Into::<customer_withdrew_cash::Model>::into(event).into_active_model();
+
+        match transaction {
+            Some(transaction) => {
+                active_model.insert(transaction.inner()).await?;
+            }
+            None => {
+                active_model.insert(&self.conn).await?;
+            }
+        }
+
+        Ok(())
+    }
+    async fn total_by_account<'t>(
+        &self,
+        account_id: BankAccountId,
+        since: DateTime<Utc>,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<Decimal, Self::Error> {
+        self.sum_amount(
+            customer_withdrew_cash::Column::AccountId
+                .eq(account_id)
+                .and(customer_withdrew_cash::Column::WithdrawnAt.gte(since)),
+            transaction,
+        )
+        .await
+    }
+    async fn count_by_account<'t>(
+        &self,
+        account_id: BankAccountId,
+        since: DateTime<Utc>,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<u64, Self::Error> {
+        let select = customer_withdrew_cash::Entity::find()
+            .filter(customer_withdrew_cash::Column::AccountId.eq(account_id))
+            .filter(customer_withdrew_cash::Column::WithdrawnAt.gte(since));
+
+        let count = match transaction {
+            Some(transaction) => select.count(transaction.inner()).await?,
+            None => select.count(&self.conn).await?,
+        };
+
+        Ok(count)
+    }
+    async fn total_by_atm<'t>(
+        &self,
+        atm_id: AtmId,
+        since: DateTime<Utc>,
+        transaction: Option<&'t Self::Transaction>,
+    ) -> Result<Decimal, Self::Error> {
+        self.sum_amount(
+            customer_withdrew_cash::Column::AtmId
+                .eq(atm_id)
+                .and(customer_withdrew_cash::Column::WithdrawnAt.gte(since)),
+            transaction,
+        )
+        .await
+    }
+}
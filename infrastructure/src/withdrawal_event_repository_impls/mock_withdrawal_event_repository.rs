@@ -0,0 +1,50 @@
+use crate::transactions::MockTransaction;
+use crate::InfraError;
+use async_trait::async_trait;
+use domain::aggregates::atm::AtmId;
+use domain::aggregates::bank_account::BankAccountId;
+use domain::events::bank_account_events::CustomerWithdrewCashEvent;
+use domain::repositories::WithdrawalEventRepository;
+use domain::Decimal;
+
+use chrono::{DateTime, Utc};
+use mockall::mock;
+
+mock! {
+    /// DbWithdrawalEventRepositoryのモック
+    #[derive(Clone, Debug)]
+    pub WithdrawalEventRepository{}
+
+    #[async_trait]
+    impl WithdrawalEventRepository for WithdrawalEventRepository {
+        type Error = InfraError;
+        type Transaction = MockTransaction;
+
+        async fn save<'t>(
+            &self,
+            event: CustomerWithdrewCashEvent,
+            transaction: Option<&'t MockTransaction>,
+        ) -> Result<(), InfraError>;
+
+        async fn total_by_account<'t>(
+            &self,
+            account_id: BankAccountId,
+            since: DateTime<Utc>,
+            transaction: Option<&'t MockTransaction>,
+        ) -> Result<Decimal, InfraError>;
+
+        async fn count_by_account<'t>(
+            &self,
+            account_id: BankAccountId,
+            since: DateTime<Utc>,
+            transaction: Option<&'t MockTransaction>,
+        ) -> Result<u64, InfraError>;
+
+        async fn total_by_atm<'t>(
+            &self,
+            atm_id: AtmId,
+            since: DateTime<Utc>,
+            transaction: Option<&'t MockTransaction>,
+        ) -> Result<Decimal, InfraError>;
+    }
+}
@@ -1,12 +1,58 @@
 pub use sea_orm_migration::prelude::*;
 
 pub mod m20220101_000001_create_table;
+pub mod m20261019_000001_create_rejected_command_table;
+pub mod m20261019_000002_alter_money_columns_to_numeric;
+pub mod m20261019_000003_add_currency_columns;
+pub mod m20261019_000004_replace_opened_with_status;
+pub mod m20261019_000005_add_daily_withdrawal_limits;
+pub mod m20261019_000006_add_overdraft_limit_column;
+pub mod m20261019_000007_add_last_interest_period_column;
+pub mod m20261019_000008_add_account_type_column;
+pub mod m20261019_000009_create_bank_account_owner_table;
+pub mod m20261019_000010_add_pending_email_change_columns;
+pub mod m20261019_000011_create_used_check_number_table;
+pub mod m20261019_000012_add_atm_status_column;
+pub mod m20261019_000013_create_atm_cassette_table;
+pub mod m20261019_000014_create_saga_table;
+pub mod m20261019_000015_replace_atm_location_with_address;
+pub mod m20261019_000016_split_account_name_columns;
+pub mod m20261019_000017_create_ledger_entry_table;
+pub mod m20261019_000018_add_used_check_number_unique_index;
+pub mod m20261019_000019_add_check_settlement_column;
+pub mod m20261019_000020_clear_unregistered_atm_locations;
+pub mod m20261019_000021_normalize_account_names;
+pub mod m20261019_000022_add_saga_version_columns;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_table::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20261019_000001_create_rejected_command_table::Migration),
+            Box::new(m20261019_000002_alter_money_columns_to_numeric::Migration),
+            Box::new(m20261019_000003_add_currency_columns::Migration),
+            Box::new(m20261019_000004_replace_opened_with_status::Migration),
+            Box::new(m20261019_000005_add_daily_withdrawal_limits::Migration),
+            Box::new(m20261019_000006_add_overdraft_limit_column::Migration),
+            Box::new(m20261019_000007_add_last_interest_period_column::Migration),
+            Box::new(m20261019_000008_add_account_type_column::Migration),
+            Box::new(m20261019_000009_create_bank_account_owner_table::Migration),
+            Box::new(m20261019_000010_add_pending_email_change_columns::Migration),
+            Box::new(m20261019_000011_create_used_check_number_table::Migration),
+            Box::new(m20261019_000012_add_atm_status_column::Migration),
+            Box::new(m20261019_000013_create_atm_cassette_table::Migration),
+            Box::new(m20261019_000014_create_saga_table::Migration),
+            Box::new(m20261019_000015_replace_atm_location_with_address::Migration),
+            Box::new(m20261019_000016_split_account_name_columns::Migration),
+            Box::new(m20261019_000017_create_ledger_entry_table::Migration),
+            Box::new(m20261019_000018_add_used_check_number_unique_index::Migration),
+            Box::new(m20261019_000019_add_check_settlement_column::Migration),
+            Box::new(m20261019_000020_clear_unregistered_atm_locations::Migration),
+            Box::new(m20261019_000021_normalize_account_names::Migration),
+            Box::new(m20261019_000022_add_saga_version_columns::Migration),
+        ]
     }
 }
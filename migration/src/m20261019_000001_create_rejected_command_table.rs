@@ -0,0 +1,45 @@
+use common::read_models::rejected_command::Entity as RejectedCommandEntity;
+
+use sea_orm::{DbBackend, EntityName};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::schema::Schema;
+use sea_orm_migration::sea_query::{TableCreateStatement, TableDropStatement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 拒否されたコマンドの監査ログのテーブルを作成するSQLを作成
+pub fn create_rejected_command_table_sql(backend: DbBackend) -> TableCreateStatement {
+    Schema::new(backend)
+        .create_table_from_entity(RejectedCommandEntity)
+        .if_not_exists()
+        .to_owned()
+}
+
+/// 拒否されたコマンドの監査ログのテーブルを削除するSQLを作成
+pub fn drop_rejected_command_table_sql() -> TableDropStatement {
+    Table::drop()
+        .table(RejectedCommandEntity.table_ref())
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(create_rejected_command_table_sql(
+                manager.get_database_backend(),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(drop_rejected_command_table_sql())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use domain::aggregates::atm::orm::{Column as AtmColumn, Entity as AtmEntity};
+use domain::aggregates::bank_account::orm::{
+    Column as BankAccountColumn, Entity as BankAccountEntity,
+};
+
+use sea_orm::EntityName;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::TableAlterStatement;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// BankAccountの残高の列をNUMERIC型に変更するSQLを作成
+pub fn alter_bank_account_balance_to_numeric_sql() -> TableAlterStatement {
+    Table::alter()
+        .table(BankAccountEntity.table_ref())
+        .modify_column(
+            ColumnDef::new(BankAccountColumn::Balance)
+                .decimal()
+                .not_null(),
+        )
+        .to_owned()
+}
+
+/// Atmの現金総額の列をNUMERIC型に変更するSQLを作成
+pub fn alter_atm_total_cash_to_numeric_sql() -> TableAlterStatement {
+    Table::alter()
+        .table(AtmEntity.table_ref())
+        .modify_column(ColumnDef::new(AtmColumn::TotalCash).decimal().not_null())
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(alter_bank_account_balance_to_numeric_sql())
+            .await?;
+
+        manager
+            .alter_table(alter_atm_total_cash_to_numeric_sql())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(BankAccountEntity.table_ref())
+                    .modify_column(
+                        ColumnDef::new(BankAccountColumn::Balance)
+                            .double()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AtmEntity.table_ref())
+                    .modify_column(ColumnDef::new(AtmColumn::TotalCash).double().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
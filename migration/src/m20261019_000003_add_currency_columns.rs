@@ -0,0 +1,87 @@
+use domain::aggregates::atm::orm::{Column as AtmColumn, Entity as AtmEntity};
+use domain::aggregates::bank_account::orm::{
+    Column as BankAccountColumn, Entity as BankAccountEntity,
+};
+
+use sea_orm::{EntityName, Iden};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::TableAlterStatement;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 既存の行の通貨
+const DEFAULT_CURRENCY: &str = "JPY";
+
+/// BankAccountに通貨の列を追加するSQLを作成
+pub fn add_bank_account_currency_sql() -> TableAlterStatement {
+    Table::alter()
+        .table(BankAccountEntity.table_ref())
+        .add_column(
+            ColumnDef::new(BankAccountColumn::Currency)
+                .string()
+                .not_null()
+                .default(DEFAULT_CURRENCY),
+        )
+        .to_owned()
+}
+
+/// Atmに通貨の列を追加するSQLを作成
+pub fn add_atm_currency_sql() -> TableAlterStatement {
+    Table::alter()
+        .table(AtmEntity.table_ref())
+        .add_column(
+            ColumnDef::new(AtmColumn::Currency)
+                .string()
+                .not_null()
+                .default(DEFAULT_CURRENCY),
+        )
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 新規のデータベースではエンティティから作成したテーブルに既に列が存在する
+        if !manager
+            .has_column(
+                BankAccountEntity.table_name(),
+                &BankAccountColumn::Currency.to_string(),
+            )
+            .await?
+        {
+            manager.alter_table(add_bank_account_currency_sql()).await?;
+        }
+
+        if !manager
+            .has_column(AtmEntity.table_name(), &AtmColumn::Currency.to_string())
+            .await?
+        {
+            manager.alter_table(add_atm_currency_sql()).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(BankAccountEntity.table_ref())
+                    .drop_column(BankAccountColumn::Currency)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AtmEntity.table_ref())
+                    .drop_column(AtmColumn::Currency)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
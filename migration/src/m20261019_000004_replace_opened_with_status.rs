@@ -0,0 +1,112 @@
+use domain::aggregates::bank_account::orm::{
+    Column as BankAccountColumn, Entity as BankAccountEntity,
+};
+
+use sea_orm::{EntityName, Iden};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::{TableAlterStatement, UpdateStatement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 以前の口座が有効かどうかを表す列
+#[derive(Iden)]
+enum Opened {
+    #[iden = "opened"]
+    Column,
+}
+
+/// BankAccountに状態の列を追加するSQLを作成
+pub fn add_bank_account_status_sql() -> TableAlterStatement {
+    Table::alter()
+        .table(BankAccountEntity.table_ref())
+        .add_column(
+            ColumnDef::new(BankAccountColumn::Status)
+                .string()
+                .not_null()
+                .default("Pending"),
+        )
+        .to_owned()
+}
+
+/// 有効だった口座の状態をOpenにするSQLを作成
+pub fn update_opened_bank_account_status_sql() -> UpdateStatement {
+    Query::update()
+        .table(BankAccountEntity.table_ref())
+        .value(BankAccountColumn::Status, "Open")
+        .and_where(Expr::col(Opened::Column).eq(true))
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 新規のデータベースではエンティティから作成したテーブルに既に列が存在する
+        if !manager
+            .has_column(
+                BankAccountEntity.table_name(),
+                &BankAccountColumn::Status.to_string(),
+            )
+            .await?
+        {
+            manager.alter_table(add_bank_account_status_sql()).await?;
+        }
+
+        if manager
+            .has_column(BankAccountEntity.table_name(), &Opened::Column.to_string())
+            .await?
+        {
+            manager
+                .exec_stmt(update_opened_bank_account_status_sql())
+                .await?;
+
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(BankAccountEntity.table_ref())
+                        .drop_column(Opened::Column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(BankAccountEntity.table_ref())
+                    .add_column(
+                        ColumnDef::new(Opened::Column)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(BankAccountEntity.table_ref())
+                    .value(Opened::Column, true)
+                    .and_where(Expr::col(BankAccountColumn::Status).eq("Open"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(BankAccountEntity.table_ref())
+                    .drop_column(BankAccountColumn::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use domain::aggregates::bank_account::orm::{
+    Column as BankAccountColumn, Entity as BankAccountEntity,
+};
+use domain::events::bank_account_events::orm::customer_withdrew_cash::Entity as WithdrawalEventEntity;
+
+use sea_orm::{DbBackend, EntityName};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::schema::Schema;
+use sea_orm_migration::sea_query::{TableAlterStatement, TableCreateStatement, TableDropStatement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// BankAccountに区分の列を追加するSQLを作成
+pub fn add_bank_account_tier_sql() -> TableAlterStatement {
+    Table::alter()
+        .table(BankAccountEntity.table_ref())
+        .add_column(
+            ColumnDef::new(BankAccountColumn::Tier)
+                .string()
+                .not_null()
+                .default("Standard"),
+        )
+        .to_owned()
+}
+
+/// 引き出しのイベントのテーブルを作成するSQLを作成
+pub fn create_withdrawal_event_table_sql(backend: DbBackend) -> TableCreateStatement {
+    Schema::new(backend)
+        .create_table_from_entity(WithdrawalEventEntity)
+        .if_not_exists()
+        .to_owned()
+}
+
+/// 引き出しのイベントのテーブルを削除するSQLを作成
+pub fn drop_withdrawal_event_table_sql() -> TableDropStatement {
+    Table::drop()
+        .table(WithdrawalEventEntity.table_ref())
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 新規のデータベースではエンティティから作成したテーブルに既に列が存在する
+        if !manager
+            .has_column(
+                BankAccountEntity.table_name(),
+                &BankAccountColumn::Tier.to_string(),
+            )
+            .await?
+        {
+            manager.alter_table(add_bank_account_tier_sql()).await?;
+        }
+
+        manager
+            .create_table(create_withdrawal_event_table_sql(
+                manager.get_database_backend(),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(drop_withdrawal_event_table_sql())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(BankAccountEntity.table_ref())
+                    .drop_column(BankAccountColumn::Tier)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
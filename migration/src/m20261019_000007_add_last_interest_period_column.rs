@@ -0,0 +1,55 @@
+use domain::aggregates::bank_account::orm::{
+    Column as BankAccountColumn, Entity as BankAccountEntity,
+};
+
+use sea_orm::EntityName;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::TableAlterStatement;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// BankAccountに最後に利息を計上した期間の列を追加するSQLを作成
+pub fn add_bank_account_last_interest_period_sql() -> TableAlterStatement {
+    Table::alter()
+        .table(BankAccountEntity.table_ref())
+        .add_column(
+            ColumnDef::new(BankAccountColumn::LastInterestPeriod)
+                .string()
+                .null(),
+        )
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 新規のデータベースではエンティティから作成したテーブルに既に列が存在する
+        if !manager
+            .has_column(
+                BankAccountEntity.table_name(),
+                &BankAccountColumn::LastInterestPeriod.to_string(),
+            )
+            .await?
+        {
+            manager
+                .alter_table(add_bank_account_last_interest_period_sql())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(BankAccountEntity.table_ref())
+                    .drop_column(BankAccountColumn::LastInterestPeriod)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
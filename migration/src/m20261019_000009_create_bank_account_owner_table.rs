@@ -0,0 +1,43 @@
+use domain::aggregates::bank_account::owner_orm::Entity as OwnerEntity;
+
+use sea_orm::{DbBackend, EntityName};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::schema::Schema;
+use sea_orm_migration::sea_query::{TableCreateStatement, TableDropStatement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 共同名義人のテーブルを作成するSQLを作成
+pub fn create_bank_account_owner_table_sql(backend: DbBackend) -> TableCreateStatement {
+    Schema::new(backend)
+        .create_table_from_entity(OwnerEntity)
+        .if_not_exists()
+        .to_owned()
+}
+
+/// 共同名義人のテーブルを削除するSQLを作成
+pub fn drop_bank_account_owner_table_sql() -> TableDropStatement {
+    Table::drop().table(OwnerEntity.table_ref()).to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(create_bank_account_owner_table_sql(
+                manager.get_database_backend(),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(drop_bank_account_owner_table_sql())
+            .await?;
+
+        Ok(())
+    }
+}
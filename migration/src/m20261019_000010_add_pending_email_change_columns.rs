@@ -0,0 +1,65 @@
+use domain::aggregates::bank_account::orm::{
+    Column as BankAccountColumn, Entity as BankAccountEntity,
+};
+
+use sea_orm::EntityName;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::TableAlterStatement;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// BankAccountに確認待ちのメールアドレスの変更の列を追加するSQLを作成
+pub fn add_bank_account_pending_email_change_sql(column: BankAccountColumn) -> TableAlterStatement {
+    let mut column_def = ColumnDef::new(column);
+    match column {
+        BankAccountColumn::EmailChangeExpiresAt => column_def.timestamp_with_time_zone(),
+        _ => column_def.string(),
+    };
+
+    Table::alter()
+        .table(BankAccountEntity.table_ref())
+        .add_column(column_def.null())
+        .to_owned()
+}
+
+/// 追加する列
+const COLUMNS: [BankAccountColumn; 3] = [
+    BankAccountColumn::PendingEmailAddress,
+    BankAccountColumn::EmailChangeToken,
+    BankAccountColumn::EmailChangeExpiresAt,
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in COLUMNS {
+            // 新規のデータベースではエンティティから作成したテーブルに既に列が存在する
+            if !manager
+                .has_column(BankAccountEntity.table_name(), &column.to_string())
+                .await?
+            {
+                manager
+                    .alter_table(add_bank_account_pending_email_change_sql(column))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in COLUMNS {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(BankAccountEntity.table_ref())
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use domain::aggregates::bank_account::used_check_number_orm::Entity as UsedCheckNumberEntity;
+
+use sea_orm::{DbBackend, EntityName};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::schema::Schema;
+use sea_orm_migration::sea_query::{TableCreateStatement, TableDropStatement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 使用済みの小切手番号のテーブルを作成するSQLを作成
+pub fn create_used_check_number_table_sql(backend: DbBackend) -> TableCreateStatement {
+    Schema::new(backend)
+        .create_table_from_entity(UsedCheckNumberEntity)
+        .if_not_exists()
+        .to_owned()
+}
+
+/// 使用済みの小切手番号のテーブルを削除するSQLを作成
+pub fn drop_used_check_number_table_sql() -> TableDropStatement {
+    Table::drop()
+        .table(UsedCheckNumberEntity.table_ref())
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(create_used_check_number_table_sql(
+                manager.get_database_backend(),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(drop_used_check_number_table_sql())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use domain::aggregates::atm::orm::{Column as AtmColumn, Entity as AtmEntity};
+
+use sea_orm::EntityName;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::TableAlterStatement;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Atmに稼働状態の列を追加するSQLを作成
+pub fn add_atm_status_sql() -> TableAlterStatement {
+    Table::alter()
+        .table(AtmEntity.table_ref())
+        .add_column(
+            ColumnDef::new(AtmColumn::Status)
+                .string()
+                .not_null()
+                .default("InService"),
+        )
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 新規のデータベースではエンティティから作成したテーブルに既に列が存在する
+        if !manager
+            .has_column(AtmEntity.table_name(), &AtmColumn::Status.to_string())
+            .await?
+        {
+            manager.alter_table(add_atm_status_sql()).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AtmEntity.table_ref())
+                    .drop_column(AtmColumn::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use domain::aggregates::atm::cassette_orm::{Entity as CassetteEntity, Model as CassetteModel};
+use domain::aggregates::atm::{orm::Entity as AtmEntity, Atm, NoteBreakdown};
+
+use sea_orm::{DbBackend, EntityName, EntityTrait, IntoActiveModel};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::schema::Schema;
+use sea_orm_migration::sea_query::{TableCreateStatement, TableDropStatement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Atmのカセットのテーブルを作成するSQLを作成
+pub fn create_atm_cassette_table_sql(backend: DbBackend) -> TableCreateStatement {
+    Schema::new(backend)
+        .create_table_from_entity(CassetteEntity)
+        .if_not_exists()
+        .to_owned()
+}
+
+/// Atmのカセットのテーブルを削除するSQLを作成
+pub fn drop_atm_cassette_table_sql() -> TableDropStatement {
+    Table::drop().table(CassetteEntity.table_ref()).to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(create_atm_cassette_table_sql(
+                manager.get_database_backend(),
+            ))
+            .await?;
+
+        // 既存のAtmの現金の総額を額面の大きい紙幣から順にカセットに入れる．
+        // 紙幣で表せない総額のAtmはカセットを空とする
+        let conn = manager.get_connection();
+        for model in AtmEntity::find().all(conn).await? {
+            let atm = Atm::from(model);
+            let Ok(notes) = NoteBreakdown::from_amount(atm.total_cash()) else {
+                continue;
+            };
+            if notes.is_empty() {
+                continue;
+            }
+
+            CassetteEntity::insert_many(notes.iter().map(|(denomination, count)| {
+                CassetteModel::from_notes(atm.id(), denomination, count).into_active_model()
+            }))
+            .exec(conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(drop_atm_cassette_table_sql()).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use domain::sagas::orm::Entity as SagaEntity;
+
+use sea_orm::{DbBackend, EntityName};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::schema::Schema;
+use sea_orm_migration::sea_query::{TableCreateStatement, TableDropStatement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// サガの状態のテーブルを作成するSQLを作成
+pub fn create_saga_table_sql(backend: DbBackend) -> TableCreateStatement {
+    Schema::new(backend)
+        .create_table_from_entity(SagaEntity)
+        .if_not_exists()
+        .to_owned()
+}
+
+/// サガの状態のテーブルを削除するSQLを作成
+pub fn drop_saga_table_sql() -> TableDropStatement {
+    Table::drop().table(SagaEntity.table_ref()).to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(create_saga_table_sql(manager.get_database_backend()))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(drop_saga_table_sql()).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use domain::aggregates::atm::orm::{Column as AtmColumn, Entity as AtmEntity};
+
+use sea_orm::{EntityName, Iden};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::{TableAlterStatement, UpdateStatement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 以前のAtmの場所を表す文字列の列
+#[derive(Iden)]
+enum Location {
+    #[iden = "location"]
+    Column,
+}
+
+/// 住所の列
+const ADDRESS_COLUMNS: [AtmColumn; 4] = [
+    AtmColumn::PostalCode,
+    AtmColumn::Region,
+    AtmColumn::City,
+    AtmColumn::Street,
+];
+
+/// 座標の列
+const COORDINATE_COLUMNS: [AtmColumn; 2] = [AtmColumn::Latitude, AtmColumn::Longitude];
+
+/// Atmに住所の列を追加するSQLを作成
+pub fn add_atm_address_column_sql(column: AtmColumn) -> TableAlterStatement {
+    Table::alter()
+        .table(AtmEntity.table_ref())
+        .add_column(ColumnDef::new(column).string().not_null().default("-"))
+        .to_owned()
+}
+
+/// Atmに座標の列を追加するSQLを作成
+pub fn add_atm_coordinate_column_sql(column: AtmColumn) -> TableAlterStatement {
+    Table::alter()
+        .table(AtmEntity.table_ref())
+        .add_column(ColumnDef::new(column).double().not_null().default(0.0))
+        .to_owned()
+}
+
+/// 以前の場所の文字列を町名・番地の列に移すSQLを作成．
+/// 座標は分からないため，移設のコマンドにより設定し直す
+pub fn move_atm_location_to_street_sql() -> UpdateStatement {
+    Query::update()
+        .table(AtmEntity.table_ref())
+        .value(AtmColumn::Street, Expr::col(Location::Column))
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 新規のデータベースではエンティティから作成したテーブルに既に列が存在する
+        for column in ADDRESS_COLUMNS {
+            if !manager
+                .has_column(AtmEntity.table_name(), &column.to_string())
+                .await?
+            {
+                manager
+                    .alter_table(add_atm_address_column_sql(column))
+                    .await?;
+            }
+        }
+        for column in COORDINATE_COLUMNS {
+            if !manager
+                .has_column(AtmEntity.table_name(), &column.to_string())
+                .await?
+            {
+                manager
+                    .alter_table(add_atm_coordinate_column_sql(column))
+                    .await?;
+            }
+        }
+
+        if manager
+            .has_column(AtmEntity.table_name(), &Location::Column.to_string())
+            .await?
+        {
+            manager.exec_stmt(move_atm_location_to_street_sql()).await?;
+
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(AtmEntity.table_ref())
+                        .drop_column(Location::Column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AtmEntity.table_ref())
+                    .add_column(
+                        ColumnDef::new(Location::Column)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(AtmEntity.table_ref())
+                    .value(Location::Column, Expr::col(AtmColumn::Street))
+                    .to_owned(),
+            )
+            .await?;
+
+        for column in ADDRESS_COLUMNS.into_iter().chain(COORDINATE_COLUMNS) {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(AtmEntity.table_ref())
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use domain::aggregates::bank_account::orm::Entity as BankAccountEntity;
+use domain::aggregates::bank_account::owner_orm::Entity as OwnerEntity;
+
+use sea_orm::{EntityName, Iden};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::{TableAlterStatement, TableRef, UpdateStatement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 口座名の列．以前は名・姓を空白で結合した文字列として保持していた
+#[derive(Iden)]
+enum AccountName {
+    #[iden = "account_name"]
+    Joined,
+    #[iden = "first_name"]
+    FirstName,
+    #[iden = "last_name"]
+    LastName,
+}
+
+/// 口座名を持つテーブル
+fn account_name_tables() -> [(TableRef, String); 2] {
+    [
+        (
+            BankAccountEntity.table_ref(),
+            BankAccountEntity.table_name().to_string(),
+        ),
+        (
+            OwnerEntity.table_ref(),
+            OwnerEntity.table_name().to_string(),
+        ),
+    ]
+}
+
+/// 名・姓の列を追加するSQLを作成
+pub fn add_name_part_column_sql(table: TableRef, column: impl IntoIden) -> TableAlterStatement {
+    Table::alter()
+        .table(table)
+        .add_column(ColumnDef::new(column).string().not_null().default(""))
+        .to_owned()
+}
+
+/// 結合された口座名を最初の空白で名・姓に分けて移すSQLを作成．
+/// 正規化と検証，正しく分けられなかった口座名の修復はm20261019_000021で行う
+pub fn split_account_name_sql(table: TableRef) -> UpdateStatement {
+    Query::update()
+        .table(table)
+        .value(
+            AccountName::FirstName,
+            Expr::cust(r#"split_part("account_name", ' ', 1)"#),
+        )
+        .value(
+            AccountName::LastName,
+            Expr::cust(r#"substr("account_name", strpos("account_name", ' ') + 1)"#),
+        )
+        .to_owned()
+}
+
+/// 名・姓を空白で結合して口座名の列に戻すSQLを作成
+pub fn join_account_name_sql(table: TableRef) -> UpdateStatement {
+    Query::update()
+        .table(table)
+        .value(
+            AccountName::Joined,
+            Expr::cust(r#"concat("first_name", ' ', "last_name")"#),
+        )
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (table, table_name) in account_name_tables() {
+            // 新規のデータベースではエンティティから作成したテーブルに既に列が存在する
+            for column in [AccountName::FirstName, AccountName::LastName] {
+                if !manager.has_column(&table_name, &column.to_string()).await? {
+                    manager
+                        .alter_table(add_name_part_column_sql(table.clone(), column))
+                        .await?;
+                }
+            }
+
+            if manager
+                .has_column(&table_name, &AccountName::Joined.to_string())
+                .await?
+            {
+                manager
+                    .exec_stmt(split_account_name_sql(table.clone()))
+                    .await?;
+
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(table)
+                            .drop_column(AccountName::Joined)
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (table, _) in account_name_tables() {
+            manager
+                .alter_table(add_name_part_column_sql(table.clone(), AccountName::Joined))
+                .await?;
+
+            manager
+                .exec_stmt(join_account_name_sql(table.clone()))
+                .await?;
+
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(AccountName::FirstName)
+                        .drop_column(AccountName::LastName)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
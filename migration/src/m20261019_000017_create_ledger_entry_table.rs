@@ -0,0 +1,43 @@
+use common::read_models::ledger_entry::Entity as LedgerEntryEntity;
+
+use sea_orm::{DbBackend, EntityName};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::schema::Schema;
+use sea_orm_migration::sea_query::{TableCreateStatement, TableDropStatement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 口座の記帳のテーブルを作成するSQLを作成
+pub fn create_ledger_entry_table_sql(backend: DbBackend) -> TableCreateStatement {
+    Schema::new(backend)
+        .create_table_from_entity(LedgerEntryEntity)
+        .if_not_exists()
+        .to_owned()
+}
+
+/// 口座の記帳のテーブルを削除するSQLを作成
+pub fn drop_ledger_entry_table_sql() -> TableDropStatement {
+    Table::drop()
+        .table(LedgerEntryEntity.table_ref())
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(create_ledger_entry_table_sql(
+                manager.get_database_backend(),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(drop_ledger_entry_table_sql()).await?;
+
+        Ok(())
+    }
+}
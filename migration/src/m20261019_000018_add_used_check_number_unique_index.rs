@@ -0,0 +1,67 @@
+use domain::aggregates::bank_account::used_check_number_orm::{
+    Column as UsedCheckNumberColumn, Entity as UsedCheckNumberEntity,
+};
+
+use sea_orm::EntityName;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::{DeleteStatement, IndexCreateStatement, IndexDropStatement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 口座ごとの小切手番号の一意なインデックスの名前
+const INDEX_NAME: &str = "idx_used_check_number_account_id_check_number";
+
+/// 同じ口座・小切手番号の重複した行を，最も早く使用したもの以外削除するSQLを作成
+pub fn delete_duplicate_check_number_sql() -> DeleteStatement {
+    Query::delete()
+        .from_table(UsedCheckNumberEntity.table_ref())
+        .and_where(Expr::cust(
+            r#""id" NOT IN (SELECT DISTINCT ON ("account_id", "check_number") "id" FROM "used_check_number" ORDER BY "account_id", "check_number", "used_at", "id")"#,
+        ))
+        .to_owned()
+}
+
+/// 口座ごとの小切手番号の一意なインデックスを作成するSQLを作成
+pub fn create_check_number_unique_index_sql() -> IndexCreateStatement {
+    Index::create()
+        .name(INDEX_NAME)
+        .table(UsedCheckNumberEntity.table_ref())
+        .col(UsedCheckNumberColumn::AccountId)
+        .col(UsedCheckNumberColumn::CheckNumber)
+        .unique()
+        .if_not_exists()
+        .to_owned()
+}
+
+/// 口座ごとの小切手番号の一意なインデックスを削除するSQLを作成
+pub fn drop_check_number_unique_index_sql() -> IndexDropStatement {
+    Index::drop()
+        .name(INDEX_NAME)
+        .table(UsedCheckNumberEntity.table_ref())
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // インデックスが無い間に同時に呈示された小切手は重複している可能性がある
+        manager
+            .exec_stmt(delete_duplicate_check_number_sql())
+            .await?;
+
+        manager
+            .create_index(create_check_number_unique_index_sql())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(drop_check_number_unique_index_sql())
+            .await?;
+
+        Ok(())
+    }
+}
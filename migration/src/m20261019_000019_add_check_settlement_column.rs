@@ -0,0 +1,54 @@
+use domain::aggregates::bank_account::used_check_number_orm::{
+    Column as UsedCheckNumberColumn, Entity as UsedCheckNumberEntity,
+};
+
+use sea_orm::{EntityName, Iden};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::TableAlterStatement;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 小切手の決済の結果の列を追加するSQLを作成
+pub fn add_settlement_column_sql() -> TableAlterStatement {
+    Table::alter()
+        .table(UsedCheckNumberEntity.table_ref())
+        .add_column(
+            ColumnDef::new(UsedCheckNumberColumn::Settlement)
+                .string()
+                .null(),
+        )
+        .to_owned()
+}
+
+/// 小切手の決済の結果の列を削除するSQLを作成
+pub fn drop_settlement_column_sql() -> TableAlterStatement {
+    Table::alter()
+        .table(UsedCheckNumberEntity.table_ref())
+        .drop_column(UsedCheckNumberColumn::Settlement)
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 新規のデータベースではエンティティから作成したテーブルに既に列が存在する
+        if !manager
+            .has_column(
+                UsedCheckNumberEntity.table_name(),
+                &UsedCheckNumberColumn::Settlement.to_string(),
+            )
+            .await?
+        {
+            manager.alter_table(add_settlement_column_sql()).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.alter_table(drop_settlement_column_sql()).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use domain::aggregates::atm::orm::{Column as AtmColumn, Entity as AtmEntity};
+use domain::aggregates::atm::AtmStatus;
+
+use sea_orm::{EntityName, Iden};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::{TableAlterStatement, UpdateStatement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 住所を登録する前の場所の文字列を残す列．運用担当者が移設のコマンドで場所を登録する際に参照する
+#[derive(Iden)]
+enum LegacyLocation {
+    #[iden = "legacy_location"]
+    Column,
+}
+
+/// 以前の場所の文字列から移行したAtmの町名・番地以外の住所の要素．AtmAddressとしては不正な値である
+const UNREGISTERED_ADDRESS_PART: &str = "-";
+
+/// 住所の列
+const ADDRESS_COLUMNS: [AtmColumn; 4] = [
+    AtmColumn::PostalCode,
+    AtmColumn::Region,
+    AtmColumn::City,
+    AtmColumn::Street,
+];
+
+/// 座標の列
+const COORDINATE_COLUMNS: [AtmColumn; 2] = [AtmColumn::Latitude, AtmColumn::Longitude];
+
+/// 以前の場所の文字列から移行したAtmの条件
+fn unregistered_atm_cond() -> SimpleExpr {
+    Expr::col(AtmColumn::PostalCode).eq(UNREGISTERED_ADDRESS_PART)
+}
+
+/// 以前の場所の文字列を残す列を追加するSQLを作成
+pub fn add_legacy_location_column_sql() -> TableAlterStatement {
+    Table::alter()
+        .table(AtmEntity.table_ref())
+        .add_column(ColumnDef::new(LegacyLocation::Column).string().null())
+        .to_owned()
+}
+
+/// 場所が未登録のAtmの以前の場所の文字列を残し，稼働中のものを停止するSQLを作成
+pub fn keep_legacy_location_sql() -> UpdateStatement {
+    Query::update()
+        .table(AtmEntity.table_ref())
+        .value(LegacyLocation::Column, Expr::col(AtmColumn::Street))
+        .value(
+            AtmColumn::Status,
+            Expr::case(
+                Expr::col(AtmColumn::Status).eq(AtmStatus::InService.as_str()),
+                AtmStatus::OutOfService.as_str(),
+            )
+            .finally(Expr::col(AtmColumn::Status)),
+        )
+        .cond_where(unregistered_atm_cond())
+        .to_owned()
+}
+
+/// 住所・座標の列をNULLを許す既定値の無い列に変更するSQLを作成
+pub fn alter_location_column_to_nullable_sql(column: AtmColumn) -> TableAlterStatement {
+    Table::alter()
+        .table(AtmEntity.table_ref())
+        .modify_column(ColumnDef::new(column).null().extra(format!(
+            r#"ALTER COLUMN "{}" DROP DEFAULT"#,
+            column.to_string()
+        )))
+        .to_owned()
+}
+
+/// 場所が未登録のAtmの住所・座標をNULLにするSQLを作成
+pub fn clear_unregistered_location_sql() -> UpdateStatement {
+    let mut update = Query::update();
+    update.table(AtmEntity.table_ref());
+    for column in ADDRESS_COLUMNS {
+        update.value(column, Option::<String>::None);
+    }
+    for column in COORDINATE_COLUMNS {
+        update.value(column, Option::<f64>::None);
+    }
+    update.cond_where(unregistered_atm_cond()).to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if !manager
+            .has_column(AtmEntity.table_name(), &LegacyLocation::Column.to_string())
+            .await?
+        {
+            manager
+                .alter_table(add_legacy_location_column_sql())
+                .await?;
+        }
+
+        // 住所・座標が不明なため，最寄りのAtmとして案内しないように停止する
+        manager.exec_stmt(keep_legacy_location_sql()).await?;
+
+        for column in ADDRESS_COLUMNS.into_iter().chain(COORDINATE_COLUMNS) {
+            manager
+                .alter_table(alter_location_column_to_nullable_sql(column))
+                .await?;
+        }
+
+        manager.exec_stmt(clear_unregistered_location_sql()).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let legacy_cond = Expr::col(AtmColumn::PostalCode).is_null();
+
+        let mut update = Query::update();
+        update
+            .table(AtmEntity.table_ref())
+            .value(
+                AtmColumn::Street,
+                Expr::col(LegacyLocation::Column).if_null(""),
+            )
+            .value(AtmColumn::Region, UNREGISTERED_ADDRESS_PART)
+            .value(AtmColumn::City, UNREGISTERED_ADDRESS_PART);
+        for column in COORDINATE_COLUMNS {
+            update.value(column, 0.0);
+        }
+        // 郵便番号は条件に用いるため最後に更新する
+        update
+            .value(AtmColumn::PostalCode, UNREGISTERED_ADDRESS_PART)
+            .cond_where(legacy_cond);
+        manager.exec_stmt(update).await?;
+
+        for column in ADDRESS_COLUMNS {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(AtmEntity.table_ref())
+                        .modify_column(
+                            ColumnDef::new(column)
+                                .not_null()
+                                .default(UNREGISTERED_ADDRESS_PART),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+        for column in COORDINATE_COLUMNS {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(AtmEntity.table_ref())
+                        .modify_column(ColumnDef::new(column).not_null().default(0.0))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AtmEntity.table_ref())
+                    .drop_column(LegacyLocation::Column)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
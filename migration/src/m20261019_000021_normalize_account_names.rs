@@ -0,0 +1,242 @@
+use domain::aggregates::bank_account::orm::Entity as BankAccountEntity;
+use domain::aggregates::bank_account::owner_orm::Entity as OwnerEntity;
+use domain::aggregates::bank_account::AccountName;
+
+use sea_orm::prelude::Uuid;
+use sea_orm::{ConnectionTrait, EntityName, Iden};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::{
+    InsertStatement, SelectStatement, TableCreateStatement, TableDropStatement, TableRef,
+    UpdateStatement,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 口座名の列
+#[derive(Iden)]
+enum AccountNameColumn {
+    #[iden = "id"]
+    Id,
+    #[iden = "first_name"]
+    FirstName,
+    #[iden = "last_name"]
+    LastName,
+}
+
+/// 名・姓に正しく分けられたか確認が必要な口座名のテーブル．運用担当者が確認して修正する
+#[derive(Iden)]
+enum AccountNameReview {
+    Table,
+    TableName,
+    RowId,
+    FirstName,
+    LastName,
+    Reason,
+}
+
+/// 口座名を持つテーブル
+fn account_name_tables() -> [(TableRef, String); 2] {
+    [
+        (
+            BankAccountEntity.table_ref(),
+            BankAccountEntity.table_name().to_string(),
+        ),
+        (
+            OwnerEntity.table_ref(),
+            OwnerEntity.table_name().to_string(),
+        ),
+    ]
+}
+
+/// 確認が必要な口座名のテーブルを作成するSQLを作成
+pub fn create_account_name_review_table_sql() -> TableCreateStatement {
+    Table::create()
+        .table(AccountNameReview::Table)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(AccountNameReview::TableName)
+                .string()
+                .not_null(),
+        )
+        .col(ColumnDef::new(AccountNameReview::RowId).uuid().not_null())
+        .col(
+            ColumnDef::new(AccountNameReview::FirstName)
+                .string()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(AccountNameReview::LastName)
+                .string()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(AccountNameReview::Reason)
+                .string()
+                .not_null(),
+        )
+        .primary_key(
+            Index::create()
+                .col(AccountNameReview::TableName)
+                .col(AccountNameReview::RowId),
+        )
+        .to_owned()
+}
+
+/// 確認が必要な口座名のテーブルを削除するSQLを作成
+pub fn drop_account_name_review_table_sql() -> TableDropStatement {
+    Table::drop()
+        .table(AccountNameReview::Table)
+        .if_exists()
+        .to_owned()
+}
+
+/// 口座名の列を取得するSQLを作成
+pub fn select_account_name_sql(table: TableRef) -> SelectStatement {
+    Query::select()
+        .columns([
+            AccountNameColumn::Id,
+            AccountNameColumn::FirstName,
+            AccountNameColumn::LastName,
+        ])
+        .from(table)
+        .to_owned()
+}
+
+/// 正規化した口座名に更新するSQLを作成
+pub fn update_account_name_sql(table: TableRef, id: Uuid, name: &AccountName) -> UpdateStatement {
+    Query::update()
+        .table(table)
+        .value(AccountNameColumn::FirstName, name.first_name())
+        .value(AccountNameColumn::LastName, name.last_name())
+        .and_where(Expr::col(AccountNameColumn::Id).eq(id))
+        .to_owned()
+}
+
+/// 確認が必要な口座名を記録するSQLを作成
+pub fn insert_account_name_review_sql(
+    table_name: &str,
+    id: Uuid,
+    name: &AccountName,
+    reason: &str,
+) -> InsertStatement {
+    Query::insert()
+        .into_table(AccountNameReview::Table)
+        .columns([
+            AccountNameReview::TableName,
+            AccountNameReview::RowId,
+            AccountNameReview::FirstName,
+            AccountNameReview::LastName,
+            AccountNameReview::Reason,
+        ])
+        .values_panic([
+            table_name.into(),
+            id.into(),
+            name.first_name().into(),
+            name.last_name().into(),
+            reason.into(),
+        ])
+        .on_conflict(
+            OnConflict::columns([AccountNameReview::TableName, AccountNameReview::RowId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .to_owned()
+}
+
+/// 分割済みの名・姓を正規化して検証する．検証できない場合はエラーの理由を返す．
+///
+/// 以前の移行は最初の半角空白で分割したため，先頭や連続する空白・全角空白を含む口座名は
+/// 正しく分割されていない．その場合は結合し直して最初の空白で分割する．
+/// 空白を含まない口座名は名・姓の両方に同じ文字列が入っている
+fn normalize_account_name(first_name: &str, last_name: &str) -> Result<AccountName, String> {
+    if let Ok(name) = AccountName::from_primitives(first_name.to_string(), last_name.to_string()) {
+        return Ok(name);
+    }
+
+    let joined = if first_name == last_name {
+        first_name.to_string()
+    } else {
+        format!("{first_name} {last_name}")
+    };
+    // 全角空白を含め，正規化の前後で空白として扱われる文字で分割する
+    let mut parts = joined.split_whitespace();
+    let first_name = parts.next().unwrap_or_default().to_string();
+    let last_name = parts.collect::<Vec<_>>().join(" ");
+
+    AccountName::from_primitives(first_name, last_name).map_err(|e| e.to_string())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(create_account_name_review_table_sql())
+            .await?;
+
+        let conn = manager.get_connection();
+        let backend = manager.get_database_backend();
+
+        // 修復できない口座名は保存された名前の検証を前提とした読み込みを壊すため，まとめて報告して移行を中止する
+        let mut invalid_names = Vec::new();
+        for (table, table_name) in account_name_tables() {
+            let rows = conn
+                .query_all(backend.build(&select_account_name_sql(table.clone())))
+                .await?;
+
+            for row in rows.into_iter() {
+                let id: Uuid = row.try_get("", &AccountNameColumn::Id.to_string())?;
+                let first_name: String =
+                    row.try_get("", &AccountNameColumn::FirstName.to_string())?;
+                let last_name: String =
+                    row.try_get("", &AccountNameColumn::LastName.to_string())?;
+
+                let name = match normalize_account_name(&first_name, &last_name) {
+                    Ok(name) => name,
+                    Err(reason) => {
+                        invalid_names.push(format!(
+                            "{table_name} {id} ({first_name:?}, {last_name:?}): {reason}"
+                        ));
+                        continue;
+                    }
+                };
+
+                if name.first_name() != first_name || name.last_name() != last_name {
+                    manager
+                        .exec_stmt(update_account_name_sql(table.clone(), id, &name))
+                        .await?;
+                }
+                // 空白を含まない口座名と，名・姓が同じ口座名は区別できない
+                if name.first_name() == name.last_name() {
+                    manager
+                        .exec_stmt(insert_account_name_review_sql(
+                            &table_name,
+                            id,
+                            &name,
+                            "first_name and last_name are the same. The name may not have been split.",
+                        ))
+                        .await?;
+                }
+            }
+        }
+
+        if !invalid_names.is_empty() {
+            return Err(DbErr::Migration(format!(
+                "{} account names cannot be normalized. Fix them and run the migration again: {}",
+                invalid_names.len(),
+                invalid_names.join("; ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 正規化した口座名は元に戻さない
+        manager
+            .drop_table(drop_account_name_review_table_sql())
+            .await?;
+
+        Ok(())
+    }
+}
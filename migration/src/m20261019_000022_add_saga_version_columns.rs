@@ -0,0 +1,105 @@
+use domain::sagas::orm::{Column as SagaColumn, Entity as SagaEntity};
+
+use sea_orm::{EntityName, Iden};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::{TableAlterStatement, UpdateStatement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 追加する列
+const COLUMNS: [SagaColumn; 3] = [
+    SagaColumn::Version,
+    SagaColumn::Commands,
+    SagaColumn::NextAttemptAt,
+];
+
+/// サガに版・実行を待っているコマンド・次に進める時刻の列を追加するSQLを作成．
+/// 既存のサガは版を0，コマンドを空とし，自動で進める状態のものはすぐに進める
+pub fn add_saga_column_sql(column: SagaColumn) -> TableAlterStatement {
+    let mut column_def = ColumnDef::new(column);
+    match column {
+        SagaColumn::Version => column_def.big_integer().default(0),
+        SagaColumn::Commands => column_def.json().default("[]"),
+        _ => column_def
+            .timestamp_with_time_zone()
+            .default(Expr::current_timestamp()),
+    };
+
+    Table::alter()
+        .table(SagaEntity.table_ref())
+        .add_column(column_def.not_null())
+        .to_owned()
+}
+
+/// 返金する前に中断した小切手の決済のサガに，状態から返金のコマンドを復元するSQLを作成．
+/// 以前は返金する前に補償中として保存していたため，返金されたかどうかは分からないが，返金は何度実行してもよい
+pub fn restore_refund_commands_sql() -> UpdateStatement {
+    Query::update()
+        .table(SagaEntity.table_ref())
+        .value(
+            SagaColumn::Commands,
+            Expr::cust(
+                r#"json_build_array(json_build_object('RefundBouncedCheck', json_build_object(
+                    'account_id', "state" -> 'account_id',
+                    'check_number', "state" -> 'check_number',
+                    'amount', "state" -> 'amount',
+                    'reason', 'Check clearing was interrupted')))"#,
+            ),
+        )
+        .and_where(Expr::col(SagaColumn::SagaType).eq("CheckClearingSaga"))
+        .and_where(Expr::col(SagaColumn::Status).eq("Compensating"))
+        .to_owned()
+}
+
+/// 以前のバージョンが扱えない進行状況を戻すSQLを作成．
+/// コマンドの実行中のものは終了したものとし，手動での確認を待っているものは手動での対応が必要なものとする
+pub fn revert_status_sql(from: &str, to: &str) -> UpdateStatement {
+    Query::update()
+        .table(SagaEntity.table_ref())
+        .value(SagaColumn::Status, to)
+        .and_where(Expr::col(SagaColumn::Status).eq(from))
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 新規のデータベースではエンティティから作成したテーブルに既に列が存在する
+        if manager
+            .has_column(SagaEntity.table_name(), &SagaColumn::Version.to_string())
+            .await?
+        {
+            return Ok(());
+        }
+
+        for column in COLUMNS {
+            manager.alter_table(add_saga_column_sql(column)).await?;
+        }
+        manager.exec_stmt(restore_refund_commands_sql()).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(revert_status_sql("Completing", "Completed"))
+            .await?;
+        manager
+            .exec_stmt(revert_status_sql("Suspended", "Failed"))
+            .await?;
+
+        for column in COLUMNS {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(SagaEntity.table_ref())
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}